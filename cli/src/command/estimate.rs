@@ -0,0 +1,28 @@
+use clap::Args;
+use nexus_sdk::{stwo::seq::Stwo, Local, Prover};
+
+use super::ProgramArgs;
+
+#[derive(Debug, Args)]
+pub struct EstimateArgs {
+    #[command(flatten)]
+    pub program: ProgramArgs,
+}
+
+pub fn handle_command(args: EstimateArgs) -> anyhow::Result<()> {
+    let (public_input, private_input) = args.program.read_inputs()?;
+    let prover = Stwo::<Local>::new_from_file(&args.program.elf)?;
+
+    let report = prover.estimate_encoded(&private_input, &public_input)?;
+    println!("cycles: {}", report.cycles);
+    println!(
+        "trace rows: {} (log size {})",
+        report.rows(),
+        report.log_size
+    );
+    println!("committed trace: {} bytes", report.trace_bytes);
+    for (opcode, rows) in &report.opcode_rows {
+        println!("  {opcode}: {rows}");
+    }
+    Ok(())
+}
@@ -1,13 +1,55 @@
-use clap::Subcommand;
+use std::path::PathBuf;
+
+use clap::{Args, Subcommand};
 
 use super::ENV;
+use crate::utils::read_or_empty;
 
+pub mod estimate;
 pub mod host;
+pub mod prove;
+pub mod run;
+pub mod verify;
 
 #[derive(Debug, Subcommand)]
 pub enum Command {
     /// Create a new host/guest Nexus package at <path>.
     Host(host::HostArgs),
+    /// Run a guest program, without proving it.
+    Run(run::RunArgs),
+    /// Run a guest program and prove its execution.
+    Prove(prove::ProveArgs),
+    /// Verify a proof of the execution of a guest program.
+    Verify(verify::VerifyArgs),
+    /// Estimate the cost of proving the execution of a guest program, without proving it.
+    Estimate(estimate::EstimateArgs),
+}
+
+/// A guest program and the inputs to execute it on.
+///
+/// Input files hold the encoded input tapes, as produced by `nexus_sdk::stwo::seq::encode_private_input`, and a
+/// missing input is empty.
+#[derive(Debug, Args)]
+pub struct ProgramArgs {
+    /// Path to the guest ELF.
+    #[arg(name = "elf")]
+    pub elf: PathBuf,
+    /// File holding the encoded public input.
+    #[arg(long)]
+    pub public_input: Option<PathBuf>,
+    /// File holding the encoded private input.
+    #[arg(long, alias = "input")]
+    pub private_input: Option<PathBuf>,
+}
+
+impl ProgramArgs {
+    /// Read the encoded public and private input.
+    fn read_inputs(&self) -> anyhow::Result<(Vec<u8>, Vec<u8>)> {
+        Ok((
+            read_or_empty(self.public_input.as_deref())?,
+            read_or_empty(self.private_input.as_deref())?,
+        ))
+    }
 }
 
 pub fn handle_command(cmd: Command) -> anyhow::Result<()> {
@@ -15,5 +57,9 @@ pub fn handle_command(cmd: Command) -> anyhow::Result<()> {
 
     match cmd {
         Command::Host(args) => host::handle_command(args),
+        Command::Run(args) => run::handle_command(args),
+        Command::Prove(args) => prove::handle_command(args),
+        Command::Verify(args) => verify::handle_command(args),
+        Command::Estimate(args) => estimate::handle_command(args),
     }
 }
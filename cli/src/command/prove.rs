@@ -0,0 +1,61 @@
+use std::{fs, path::PathBuf};
+
+use clap::Args;
+use nexus_core::nvm::{ElfFile, InputTranscript};
+use nexus_sdk::{
+    stwo::seq::{program_digest, ProverConfig, Stwo},
+    Local, Prover,
+};
+
+use super::ProgramArgs;
+use crate::utils::format_digest;
+
+#[derive(Debug, Args)]
+pub struct ProveArgs {
+    #[command(flatten)]
+    pub program: ProgramArgs,
+    /// File to write the proof to.
+    #[arg(short, long, default_value = "proof.nxp")]
+    pub output: PathBuf,
+    /// File to write the encoded public output to.
+    #[arg(long)]
+    pub public_output: Option<PathBuf>,
+    /// The conjectured security, in bits, the proof must provide.
+    #[arg(long, default_value_t = 96)]
+    pub security_bits: u32,
+    /// Log2 of the FRI blowup factor, trading proving time for proof size.
+    #[arg(long, default_value_t = ProverConfig::MIN_LOG_BLOWUP_FACTOR)]
+    pub log_blowup_factor: u32,
+    /// Number of proof-of-work bits, trading proving time for proof size.
+    #[arg(long, default_value_t = 10)]
+    pub pow_bits: u32,
+}
+
+pub fn handle_command(args: ProveArgs) -> anyhow::Result<()> {
+    let (public_input, private_input) = args.program.read_inputs()?;
+    let elf = ElfFile::from_path(&args.program.elf)?;
+    let prover = Stwo::<Local>::new(&elf)?.with_prover_config(ProverConfig::with_security_bits(
+        args.security_bits,
+        args.log_blowup_factor,
+        args.pow_bits,
+    ));
+
+    // The inputs are read already encoded, so their types are unknown to the prover.
+    let (view, proof) = prover.replay(
+        &InputTranscript {
+            public_input,
+            private_input,
+            associated_data: Vec::new(),
+        },
+        None,
+    )?;
+    fs::write(&args.output, proof.to_bytes())?;
+    if let Some(path) = args.public_output {
+        fs::write(path, view.view_public_output().unwrap_or_default())?;
+    }
+
+    println!("halted: {:?}", view.halt_reason());
+    println!("program digest: {}", format_digest(&program_digest(&elf)?));
+    println!("proof written to {}", args.output.display());
+    Ok(())
+}
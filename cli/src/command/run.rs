@@ -0,0 +1,31 @@
+use std::{fs, path::PathBuf};
+
+use clap::Args;
+use nexus_sdk::{stwo::seq::Stwo, Local, Prover};
+
+use super::ProgramArgs;
+
+#[derive(Debug, Args)]
+pub struct RunArgs {
+    #[command(flatten)]
+    pub program: ProgramArgs,
+    /// File to write the encoded public output to.
+    #[arg(long)]
+    pub public_output: Option<PathBuf>,
+}
+
+pub fn handle_command(args: RunArgs) -> anyhow::Result<()> {
+    let (public_input, private_input) = args.program.read_inputs()?;
+    let prover = Stwo::<Local>::new_from_file(&args.program.elf)?;
+
+    let view = prover.run_encoded(&private_input, &public_input)?;
+    for log in view.view_debug_logs().unwrap_or_default() {
+        print!("{}", String::from_utf8_lossy(&log));
+    }
+    println!("halted: {:?}", view.halt_reason());
+
+    if let Some(path) = args.public_output {
+        fs::write(path, view.view_public_output().unwrap_or_default())?;
+    }
+    Ok(())
+}
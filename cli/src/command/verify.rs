@@ -0,0 +1,64 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use nexus_core::nvm::{ElfFile, View};
+use nexus_sdk::{
+    stwo::seq::{program_digest, Proof, ProverConfig},
+    CheckedView, Verifiable,
+};
+
+use crate::utils::{format_digest, parse_digest, read_or_empty};
+
+#[derive(Debug, Args)]
+pub struct VerifyArgs {
+    /// Path to the proof.
+    #[arg(name = "proof")]
+    pub proof: PathBuf,
+    /// Path to the guest ELF the proof is expected to be for.
+    #[arg(long)]
+    pub elf: PathBuf,
+    /// The hex-encoded digest the guest ELF is expected to have, so that a verifier can pin the program.
+    #[arg(long)]
+    pub program_digest: Option<String>,
+    /// File holding the expected encoded public input.
+    #[arg(long)]
+    pub public_input: Option<PathBuf>,
+    /// File holding the expected encoded public output.
+    #[arg(long)]
+    pub public_output: Option<PathBuf>,
+    /// The expected exit code.
+    #[arg(long, default_value_t = 0)]
+    pub exit_code: u32,
+    /// The conjectured security, in bits, the proof must provide.
+    #[arg(long, default_value_t = ProverConfig::RECOMMENDED_MIN_SECURITY_BITS)]
+    pub min_security_bits: u32,
+}
+
+pub fn handle_command(args: VerifyArgs) -> anyhow::Result<()> {
+    let elf = ElfFile::from_path(&args.elf)?;
+    if let Some(expected) = &args.program_digest {
+        let digest = program_digest(&elf)?;
+        if digest != parse_digest(expected)? {
+            anyhow::bail!(
+                "program digest mismatch: {} has digest {}",
+                args.elf.display(),
+                format_digest(&digest)
+            );
+        }
+    }
+
+    let proof = Proof::from_bytes(&std::fs::read(&args.proof)?)?;
+    proof.check_security(args.min_security_bits)?;
+    let view = View::new_from_expected(
+        proof.get_memory_layout(),
+        &read_or_empty(args.public_input.as_deref())?,
+        &args.exit_code.to_le_bytes(),
+        &read_or_empty(args.public_output.as_deref())?,
+        &elf,
+        &[],
+    );
+    proof.verify(&view)?;
+
+    println!("proof verified");
+    Ok(())
+}
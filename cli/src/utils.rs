@@ -1,3 +1,4 @@
+use anyhow::Context;
 use std::{ffi::OsStr, path::Path, process::Command};
 
 pub fn cargo<I, S>(dir: Option<&Path>, args: I) -> anyhow::Result<()>
@@ -16,3 +17,48 @@ where
     }
     Ok(())
 }
+
+/// Read the file at `path`, or return no bytes if there is none.
+pub fn read_or_empty(path: Option<&Path>) -> anyhow::Result<Vec<u8>> {
+    match path {
+        Some(path) => {
+            std::fs::read(path).with_context(|| format!("failed to read {}", path.display()))
+        }
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Parse a hex-encoded program digest, with or without a `0x` prefix.
+pub fn parse_digest(hex: &str) -> anyhow::Result<[u8; 32]> {
+    let hex = hex.strip_prefix("0x").unwrap_or(hex);
+    if hex.len() != 64 || !hex.is_ascii() {
+        anyhow::bail!("a program digest is 32 hex-encoded bytes");
+    }
+
+    let mut digest = [0u8; 32];
+    for (byte, chunk) in digest.iter_mut().zip(hex.as_bytes().chunks(2)) {
+        *byte = u8::from_str_radix(std::str::from_utf8(chunk)?, 16)?;
+    }
+    Ok(digest)
+}
+
+/// Hex-encode a program digest, with a `0x` prefix.
+pub fn format_digest(digest: &[u8; 32]) -> String {
+    let hex: String = digest.iter().map(|byte| format!("{byte:02x}")).collect();
+    format!("0x{hex}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digest_round_trip() {
+        let digest: [u8; 32] = std::array::from_fn(|i| i as u8 * 7);
+
+        let hex = format_digest(&digest);
+        assert_eq!(parse_digest(&hex).unwrap(), digest);
+        assert_eq!(parse_digest(&hex[2..]).unwrap(), digest);
+        assert!(parse_digest("0x1234").is_err());
+    }
+}
@@ -0,0 +1,20 @@
+#[cfg(target_arch = "riscv32")]
+mod riscv32;
+#[cfg(target_arch = "riscv32")]
+pub use riscv32::*;
+
+#[cfg(not(target_arch = "riscv32"))]
+pub use tiny_keccak::*;
+
+/// Computes the Keccak-256 hash of `input`, as used by Ethereum.
+///
+/// On the guest, every permutation of the sponge is a single `keccakf` custom instruction, which is proven by the
+/// keccak extension of the prover rather than by thousands of RV32 instructions.
+pub fn keccak256(input: &[u8]) -> [u8; 32] {
+    let mut keccak = Keccak::v256();
+    keccak.update(input);
+
+    let mut output = [0u8; 32];
+    keccak.finalize(&mut output);
+    output
+}
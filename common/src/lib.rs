@@ -1,5 +1,18 @@
+#![cfg_attr(target_arch = "riscv32", no_std)]
+
+#[cfg(not(target_arch = "riscv32"))]
 pub mod constants;
+#[cfg(not(target_arch = "riscv32"))]
 pub mod cpu;
+#[cfg(not(target_arch = "riscv32"))]
 pub mod error;
+#[cfg(not(target_arch = "riscv32"))]
 pub mod memory;
+#[cfg(not(target_arch = "riscv32"))]
 pub mod riscv;
+
+// Shared by the guest runtime and the host, so that both compute the same digests and random streams.
+pub mod keccak;
+pub use keccak::keccak256;
+pub mod merkle;
+pub mod rand;
@@ -0,0 +1,119 @@
+//! Binary Merkle trees over Keccak-256, for committing to large public outputs.
+//!
+//! A guest can return the [`root`] of its output instead of the output itself, keeping the
+//! public output segment (and so the verifier's work) constant-size. Individual elements can
+//! later be opened against the committed root using a [`path`] and [`verify_path`].
+//!
+//! Alternatively, a guest can return only the [`output_digest`] of its whole output, which a
+//! verifier holding the full output recomputes.
+//!
+//! Leaves and internal nodes are domain-separated, and odd levels are padded with an all-zero
+//! digest, so the same scheme is used identically by the guest and the host.
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use serde::Serialize;
+
+use crate::keccak::{Hasher, Keccak};
+
+/// A Merkle tree node or root.
+pub type Digest = [u8; 32];
+
+/// Digest used to pad levels with an odd number of nodes, and as the root of an empty tree.
+pub const EMPTY_DIGEST: Digest = [0u8; 32];
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+/// Hash a leaf of the tree.
+pub fn hash_leaf(leaf: &[u8]) -> Digest {
+    let mut out = EMPTY_DIGEST;
+    let mut hasher = Keccak::v256();
+    hasher.update(&[LEAF_PREFIX]);
+    hasher.update(leaf);
+    hasher.finalize(&mut out);
+    out
+}
+
+/// Hash two sibling nodes into their parent.
+pub fn hash_node(left: &Digest, right: &Digest) -> Digest {
+    let mut out = EMPTY_DIGEST;
+    let mut hasher = Keccak::v256();
+    hasher.update(&[NODE_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize(&mut out);
+    out
+}
+
+/// Compute the digest of a whole output, that is the root of the tree whose only leaf is its
+/// postcard encoding.
+///
+/// # Panics
+///
+/// Panics if the output cannot be encoded.
+pub fn output_digest<T: Serialize + ?Sized>(output: &T) -> Digest {
+    hash_leaf(&postcard::to_allocvec(output).expect("failed to encode output"))
+}
+
+fn next_level(level: &[Digest]) -> Vec<Digest> {
+    level
+        .chunks(2)
+        .map(|pair| hash_node(&pair[0], pair.get(1).unwrap_or(&EMPTY_DIGEST)))
+        .collect()
+}
+
+/// Compute the root of the tree with the given leaves.
+pub fn root<L: AsRef<[u8]>>(leaves: &[L]) -> Digest {
+    let mut level: Vec<Digest> = leaves.iter().map(|l| hash_leaf(l.as_ref())).collect();
+    if level.is_empty() {
+        return EMPTY_DIGEST;
+    }
+
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    level[0]
+}
+
+/// Compute the authentication path for the leaf at `index`, ordered from the leaf level up.
+///
+/// Returns `None` if `index` is out of bounds.
+pub fn path<L: AsRef<[u8]>>(leaves: &[L], index: usize) -> Option<Vec<Digest>> {
+    if index >= leaves.len() {
+        return None;
+    }
+
+    let mut level: Vec<Digest> = leaves.iter().map(|l| hash_leaf(l.as_ref())).collect();
+    let mut index = index;
+    let mut path = Vec::new();
+
+    while level.len() > 1 {
+        path.push(*level.get(index ^ 1).unwrap_or(&EMPTY_DIGEST));
+        level = next_level(&level);
+        index >>= 1;
+    }
+    Some(path)
+}
+
+/// Check that `leaf` is the element at `index` of the tree committed to by `root`.
+pub fn verify_path(root: &Digest, index: usize, leaf: &[u8], path: &[Digest]) -> bool {
+    if path.len() < usize::BITS as usize && index >> path.len() != 0 {
+        return false;
+    }
+
+    let mut node = hash_leaf(leaf);
+    let mut index = index;
+
+    for sibling in path {
+        node = if index & 1 == 0 {
+            hash_node(&node, sibling)
+        } else {
+            hash_node(sibling, &node)
+        };
+        index >>= 1;
+    }
+    node == *root
+}
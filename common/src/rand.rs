@@ -0,0 +1,76 @@
+//! Deterministic randomness expanded from a seed.
+//!
+//! The zkVM has no source of entropy, so randomness must come from the host as a seed, such as a public input, which
+//! is then committed in the public values of the proof. [`Rng`] expands the seed with Keccak-256 in counter mode, so
+//! the stream depends on nothing but the seed: both emulator passes and the proof see the same bytes, and a verifier
+//! can recompute the exact stream the guest consumed by running the same [`Rng`] on the host.
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use crate::keccak::{Hasher, Keccak};
+
+/// Separates the blocks of the stream from other Keccak-256 digests of the seed.
+const DOMAIN: &[u8] = b"nexus-rt/rand";
+
+/// A deterministic random number generator expanding a 32-byte seed.
+///
+/// The `i`-th 32-byte block of the stream is `keccak256("nexus-rt/rand" || seed || i)`, with `i` a little-endian
+/// `u64`. This is not a source of secret randomness: anyone knowing the seed knows the stream.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    seed: [u8; 32],
+    counter: u64,
+    block: [u8; 32],
+    position: usize,
+}
+
+impl Rng {
+    /// Create a generator whose stream is determined by `seed`.
+    pub fn new(seed: [u8; 32]) -> Self {
+        Self {
+            seed,
+            counter: 0,
+            block: [0; 32],
+            // The first block is generated on first use.
+            position: 32,
+        }
+    }
+
+    /// Fill `out` with the next bytes of the stream.
+    pub fn fill_bytes(&mut self, out: &mut [u8]) {
+        for byte in out {
+            if self.position == self.block.len() {
+                self.next_block();
+            }
+            *byte = self.block[self.position];
+            self.position += 1;
+        }
+    }
+
+    /// Returns the next `n` bytes of the stream.
+    pub fn rand_bytes(&mut self, n: usize) -> Vec<u8> {
+        let mut bytes = alloc::vec![0; n];
+        self.fill_bytes(&mut bytes);
+        bytes
+    }
+
+    /// Returns the next four bytes of the stream, as a little-endian `u32`.
+    pub fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_block(&mut self) {
+        let mut hasher = Keccak::v256();
+        hasher.update(DOMAIN);
+        hasher.update(&self.seed);
+        hasher.update(&self.counter.to_le_bytes());
+        hasher.finalize(&mut self.block);
+
+        self.counter += 1;
+        self.position = 0;
+    }
+}
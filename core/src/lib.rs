@@ -3,11 +3,23 @@
 
 /// RISC-V processing
 pub mod nvm {
+    #[cfg(feature = "gdb")]
+    pub use nexus_vm::emulator::debug_with_hooks;
     pub use nexus_vm::{
-        elf::{ElfError, ElfFile},
-        emulator::View,
+        elf::{ElfError, ElfFile, FunctionSymbol},
+        emulator::{
+            CallEdge, ExecutionLimits, FileInputProvider, FunctionProfile, HaltReason,
+            InputProvider, InputTranscript, InstrClass, LimitUsage, LogLevel, LogRecord,
+            MemoryUsage, Profile, TrapKind, View,
+        },
         error::VMError,
-        trace::{bb_trace, k_trace, BBTrace, UniformTrace},
+        export::{export_trace, AccessColumns, StepColumns, TraceExport},
+        system::{HostCall, HostFunction, HostFunctions, HOST_CALL_CODES},
+        trace::{
+            bb_trace, emulate_with_hooks, k_trace, k_trace_with_hooks, k_trace_with_limits,
+            BBTrace, ExecutionHooks, Trace, UniformTrace,
+        },
+        SyscallCode,
     };
     pub mod internals {
         pub use nexus_vm::emulator::{
@@ -20,5 +32,11 @@ pub mod nvm {
 
 /// Stwo proving
 pub mod stwo {
-    pub use nexus_vm_prover::{prove, verify, Proof, ProvingError, VerificationError};
+    pub use nexus_vm_prover::{
+        chips, prove, prove_checked, prove_from_steps, prove_monitored, prove_with_config,
+        prove_with_deadline, prove_with_min_log_size, prove_with_preprocessed_trace,
+        trace::{program::program_steps, PreprocessedTraces, ProgramStep},
+        verify, verify_with_deadline, verify_with_min_security, DeadlineVerificationError, Proof,
+        ProveError, ProveMonitor, ProvePhase, ProverConfig, ProvingError, VerificationError,
+    };
 }
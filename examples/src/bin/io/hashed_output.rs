@@ -0,0 +1,15 @@
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use nexus_rt::merkle;
+
+// Commit to a large output by returning only its digest; a host holding the full output can
+// check it against the digest bound into the proof.
+#[nexus_rt::main]
+fn main() -> merkle::Digest {
+    let output: Vec<u32> = (0..1024u32).map(|i| i * i).collect();
+
+    merkle::output_digest(&output)
+}
@@ -0,0 +1,12 @@
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+use nexus_rt::merkle;
+
+// Commit to a large output by returning only its Merkle root; individual elements can be opened
+// by the host against the root bound into the proof.
+#[nexus_rt::main]
+fn main() -> merkle::Digest {
+    let output: [[u8; 4]; 256] = core::array::from_fn(|i| ((i * i) as u32).to_le_bytes());
+
+    merkle::root(&output)
+}
@@ -0,0 +1,13 @@
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+use nexus_rt::rand::Rng;
+
+// Draw random numbers from a stream seeded by the public input, so that a verifier can recompute
+// exactly which numbers the guest drew.
+#[nexus_rt::main]
+#[nexus_rt::public_input(seed)]
+fn main(seed: [u8; 32]) -> [u32; 10] {
+    let mut rng = Rng::new(seed);
+
+    core::array::from_fn(|_| rng.next_u32())
+}
@@ -13,6 +13,7 @@ use nexus_vm_prover::{
         program::iter_program_steps,
         program_trace::{ProgramTraceRef, ProgramTracesBuilder},
         sidenote::SideNote,
+        utils::finalize_columns_serial,
         PreprocessedTraces, TracesBuilder,
     },
     traits::{generate_interaction_trace, MachineChip},
@@ -103,6 +104,12 @@ fn bench_trace_gen(c: &mut Criterion) {
                 black_box(prover_traces.clone().finalize());
             })
         });
+        // The main trace is the widest one, compare column-parallel finalization against the serial baseline.
+        group.bench_function("FinalizeTraceSerial", |b| {
+            b.iter(|| {
+                black_box(finalize_columns_serial(prover_traces.clone().into_inner()));
+            })
+        });
         let finalized_trace = prover_traces.finalize();
         let finalized_program_trace = program_traces.finalize();
         group.bench_function("InteractionTrace", |b| {
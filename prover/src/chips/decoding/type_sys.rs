@@ -95,7 +95,7 @@ impl MachineChip for TypeSysChip {
 mod test {
     use crate::{
         chips::{
-            AddChip, CpuChip, DecodingCheckChip, ProgramMemCheckChip, RangeCheckChip,
+            AddChip, CpuChip, DecodingCheckChip, ProgramMemCheckChip, RangeCheckChips,
             RegisterMemCheckChip, TimestampChip,
         },
         test_utils::assert_chip,
@@ -133,7 +133,7 @@ mod test {
             RegisterMemCheckChip,
             ProgramMemCheckChip,
             TimestampChip,
-            RangeCheckChip,
+            RangeCheckChips,
         );
         let basic_block = setup_basic_block_ir();
         let k = 1;
@@ -157,6 +157,7 @@ mod test {
                 &ExtensionsConfig::default(),
             );
         }
+        Chips::finalize_main_trace(&mut traces, &mut side_note, &ExtensionsConfig::default());
         assert_chip::<Chips>(traces, Some(program_trace.finalize()));
     }
 }
@@ -143,7 +143,7 @@ mod test {
 
     use crate::{
         chips::{
-            CpuChip, DecodingCheckChip, ProgramMemCheckChip, RangeCheckChip, RegisterMemCheckChip,
+            CpuChip, DecodingCheckChip, ProgramMemCheckChip, RangeCheckChips, RegisterMemCheckChip,
             TimestampChip,
         },
         test_utils::assert_chip,
@@ -220,7 +220,7 @@ mod test {
             RegisterMemCheckChip,
             ProgramMemCheckChip,
             TimestampChip,
-            RangeCheckChip,
+            RangeCheckChips,
         );
         let basic_block = setup_basic_block_ir();
         let k = 1;
@@ -244,6 +244,7 @@ mod test {
                 &ExtensionsConfig::default(),
             );
         }
+        Chips::finalize_main_trace(&mut traces, &mut side_note, &ExtensionsConfig::default());
         assert_chip::<Chips>(traces, Some(program_trace.finalize()));
     }
 }
@@ -230,7 +230,7 @@ impl MachineChip for BgeChip {
 mod test {
     use crate::{
         chips::{
-            AddChip, CpuChip, DecodingCheckChip, ProgramMemCheckChip, RangeCheckChip,
+            AddChip, CpuChip, DecodingCheckChip, ProgramMemCheckChip, RangeCheckChips,
             RegisterMemCheckChip, SubChip,
         },
         test_utils::assert_chip,
@@ -325,7 +325,7 @@ mod test {
             BgeChip,
             RegisterMemCheckChip,
             ProgramMemCheckChip,
-            RangeCheckChip,
+            RangeCheckChips,
         );
         let basic_block = setup_basic_block_ir();
         let k = 1;
@@ -350,6 +350,7 @@ mod test {
                 &ExtensionsConfig::default(),
             );
         }
+        Chips::finalize_main_trace(&mut traces, &mut side_note, &ExtensionsConfig::default());
         assert_chip::<Chips>(traces, Some(program_traces.finalize()));
     }
 }
@@ -509,6 +509,7 @@ mod test {
                 &ExtensionsConfig::default(),
             );
         }
+        Chips::finalize_main_trace(&mut traces, &mut side_note, &ExtensionsConfig::default());
 
         let and_vals = traces
             .column(2, ValueA)
@@ -218,7 +218,7 @@ impl MachineChip for BltChip {
 mod test {
     use crate::{
         chips::{
-            AddChip, CpuChip, DecodingCheckChip, ProgramMemCheckChip, RangeCheckChip,
+            AddChip, CpuChip, DecodingCheckChip, ProgramMemCheckChip, RangeCheckChips,
             RegisterMemCheckChip, SubChip,
         },
         test_utils::assert_chip,
@@ -297,7 +297,7 @@ mod test {
             BltChip,
             RegisterMemCheckChip,
             ProgramMemCheckChip,
-            RangeCheckChip,
+            RangeCheckChips,
         );
         let basic_block = setup_basic_block_ir();
         let k = 1;
@@ -322,6 +322,7 @@ mod test {
                 &ExtensionsConfig::default(),
             );
         }
+        Chips::finalize_main_trace(&mut traces, &mut side_note, &ExtensionsConfig::default());
 
         assert_chip::<Chips>(traces, Some(program_traces.finalize()));
     }
@@ -265,6 +265,7 @@ mod test {
                 &ExtensionsConfig::default(),
             );
         }
+        Chips::finalize_main_trace(&mut traces, &mut side_note, &ExtensionsConfig::default());
 
         assert_chip::<Chips>(traces, Some(program_trace.finalize()));
     }
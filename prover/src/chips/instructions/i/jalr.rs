@@ -208,7 +208,7 @@ impl MachineChip for JalrChip {
 mod test {
     use crate::{
         chips::{
-            AddChip, CpuChip, DecodingCheckChip, LuiChip, ProgramMemCheckChip, RangeCheckChip,
+            AddChip, CpuChip, DecodingCheckChip, LuiChip, ProgramMemCheckChip, RangeCheckChips,
             RegisterMemCheckChip,
         },
         test_utils::assert_chip,
@@ -276,7 +276,7 @@ mod test {
             JalrChip,
             RegisterMemCheckChip,
             ProgramMemCheckChip,
-            RangeCheckChip,
+            RangeCheckChips,
         );
         let basic_block = setup_basic_block_ir();
         let k = 1;
@@ -301,6 +301,7 @@ mod test {
                 &ExtensionsConfig::default(),
             );
         }
+        Chips::finalize_main_trace(&mut traces, &mut side_note, &ExtensionsConfig::default());
         assert_chip::<Chips>(traces, Some(program_traces.finalize()));
     }
 }
@@ -919,20 +919,25 @@ mod test {
             },
             AddChip, BeqChip, BitOpChip, CpuChip, DecodingCheckChip, RegisterMemCheckChip, SllChip,
         },
-        machine::Machine,
+        machine::{Machine, ProveError, BASE_EXTENSIONS},
         test_utils::assert_chip,
         trace::{
-            program::iter_program_steps, program_trace::ProgramTracesBuilder, PreprocessedTraces,
+            program::iter_program_steps,
+            program_trace::{ProgramTraceRef, ProgramTracesBuilder},
+            PreprocessedTraces,
         },
+        traits::generate_interaction_trace,
     };
 
     use super::*;
     use nexus_vm::{
-        emulator::InternalView,
-        memory::MemoryRecords,
+        emulator::{InternalView, View},
+        memory::{MemoryRecord, MemoryRecords},
         riscv::{BasicBlock, BuiltinOpcode, Instruction, Opcode},
-        trace::k_trace_direct,
+        trace::{k_trace_direct, UniformTrace},
     };
+    use num_traits::Zero;
+    use stwo::core::{channel::Blake2sChannel, fields::qm31::SecureField};
     use stwo::prover::ProvingError;
 
     const LOG_SIZE: u32 = PreprocessedTraces::MIN_LOG_SIZE;
@@ -1032,6 +1037,7 @@ mod test {
                 &ExtensionsConfig::default(),
             );
         }
+        Chips::finalize_main_trace(&mut traces, &mut side_note, &ExtensionsConfig::default());
 
         // Assert results of loads
         let load_vals = traces
@@ -1115,6 +1121,155 @@ mod test {
         store_step.memory_records = MemoryRecords::from_iter([memory_record]);
 
         let result = Machine::<Chips>::prove(&vm_traces, &view);
-        assert!(matches!(result, Err(ProvingError::ConstraintsNotSatisfied)));
+        assert!(matches!(
+            result,
+            Err(ProveError::Proving(ProvingError::ConstraintsNotSatisfied))
+        ));
+    }
+
+    fn setup_lane_basic_block() -> Vec<BasicBlock> {
+        let basic_block = BasicBlock::new(vec![
+            // Create the address 0x81008 in x2, as in `setup_basic_block_ir`
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 1, 0, 1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SLLI), 1, 1, 19),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADD), 2, 1, 2),
+            // Set x3 = 0x07ff07ff, so that every byte lane of a word access is non-zero
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 3, 0, 0x7ff),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SLLI), 4, 3, 16),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADD), 3, 3, 4),
+            // Row 6: store a byte to *x2
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SB), 2, 3, 0),
+            // Row 7: store two bytes to *x2 + 4
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SH), 2, 3, 4),
+            // Row 8: store four bytes to *x2 + 8
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SW), 2, 3, 8),
+            // Rows 9, 10 and 11: load the same values back
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::LB), 5, 2, 0),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::LH), 6, 2, 4),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::LW), 7, 2, 8),
+        ]);
+        vec![basic_block]
+    }
+
+    /// Fills the main trace and the base extensions, and returns the logup sum over all components.
+    fn total_claimed_sum(view: &View, vm_traces: &UniformTrace) -> (TracesBuilder, SecureField) {
+        let init_memory = [
+            view.get_ro_initial_memory(),
+            view.get_rw_initial_memory(),
+            view.get_public_input(),
+        ]
+        .concat();
+        let program_trace_ref = ProgramTraceRef {
+            program_memory: view.get_program_memory(),
+            init_memory: &init_memory,
+            exit_code: view.get_exit_code(),
+            public_output: view.get_public_output(),
+        };
+
+        let mut traces = TracesBuilder::new(LOG_SIZE);
+        let program_traces = ProgramTracesBuilder::new(LOG_SIZE, program_trace_ref);
+        let mut side_note = SideNote::new(&program_traces, view);
+        let program_steps = iter_program_steps(vm_traces, traces.num_rows());
+        for (row_idx, program_step) in program_steps.enumerate() {
+            Chips::fill_main_trace(
+                &mut traces,
+                row_idx,
+                &program_step,
+                &mut side_note,
+                &ExtensionsConfig::default(),
+            );
+        }
+        Chips::finalize_main_trace(&mut traces, &mut side_note, &ExtensionsConfig::default());
+
+        let log_sizes: Vec<u32> = BASE_EXTENSIONS
+            .iter()
+            .map(|ext| ext.compute_log_size(&side_note))
+            .collect();
+        let extension_traces: Vec<_> = BASE_EXTENSIONS
+            .iter()
+            .zip(&log_sizes)
+            .map(|(ext, log_size)| {
+                ext.generate_component_trace(*log_size, program_trace_ref, &mut side_note)
+            })
+            .collect();
+
+        let mut lookup_elements = AllLookupElements::default();
+        Chips::draw_lookup_elements(
+            &mut lookup_elements,
+            &mut Blake2sChannel::default(),
+            &ExtensionsConfig::default(),
+        );
+        let (_, mut claimed_sum) = generate_interaction_trace::<Chips>(
+            &traces.clone().finalize(),
+            &PreprocessedTraces::new(LOG_SIZE),
+            &program_traces.finalize(),
+            &lookup_elements,
+        );
+        for (ext, extension_trace) in BASE_EXTENSIONS.iter().zip(extension_traces) {
+            let (_, ext_claimed_sum) =
+                ext.generate_interaction_trace(extension_trace, &side_note, &lookup_elements);
+            claimed_sum += ext_claimed_sum;
+        }
+        (traces, claimed_sum)
+    }
+
+    #[test]
+    fn test_load_store_byte_lanes() {
+        let basic_block = setup_lane_basic_block();
+        let (view, vm_traces) = k_trace_direct(&basic_block, 1).expect("Failed to create trace");
+
+        let (traces, claimed_sum) = total_claimed_sum(&view, &vm_traces);
+
+        let byte = [0xff, 0, 0, 0];
+        let half_word = [0xff, 0x07, 0, 0];
+        let word = [0xff, 0x07, 0xff, 0x07];
+        for (row_idx, expected) in [
+            (6, byte),
+            (7, half_word),
+            (8, word),
+            (9, byte),
+            (10, half_word),
+            (11, word),
+        ] {
+            let lanes = [Ram1ValCur, Ram2ValCur, Ram3ValCur, Ram4ValCur]
+                .map(|col| traces.column::<1>(row_idx, col)[0].0);
+            assert_eq!(lanes, expected, "unexpected RAM lanes at row {row_idx}");
+        }
+        assert_eq!(claimed_sum, SecureField::zero());
+    }
+
+    #[test]
+    fn test_half_word_store_into_third_lane() {
+        let basic_block = setup_lane_basic_block();
+        let (view, mut vm_traces) =
+            k_trace_direct(&basic_block, 1).expect("Failed to create trace");
+
+        // Forge the SH record into a word access, writing its third byte lane.
+        let store_step = &mut vm_traces.blocks[7].steps[0];
+        assert_eq!(
+            store_step.instruction.opcode.builtin(),
+            Some(BuiltinOpcode::SH)
+        );
+        let memory_record = std::mem::take(&mut store_step.memory_records)
+            .into_iter()
+            .next()
+            .unwrap();
+        let memory_record = match memory_record {
+            MemoryRecord::StoreRecord((MemAccessSize::HalfWord, addr, value, prev), ts) => {
+                MemoryRecord::StoreRecord((MemAccessSize::Word, addr, value | 0xff_0000, prev), ts)
+            }
+            _ => panic!("half-word store record expected"),
+        };
+        store_step.memory_records = MemoryRecords::from_iter([memory_record]);
+
+        let (traces, claimed_sum) = total_claimed_sum(&view, &vm_traces);
+        assert_eq!(traces.column::<1>(7, Ram3ValCur)[0].0, 0xff);
+        assert_ne!(claimed_sum, SecureField::zero());
+
+        let result = Machine::<Chips>::prove(&vm_traces, &view);
+        assert!(matches!(
+            result,
+            Err(ProveError::Proving(ProvingError::ConstraintsNotSatisfied))
+        ));
     }
 }
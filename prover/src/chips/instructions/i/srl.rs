@@ -229,9 +229,10 @@ impl MachineChip for SrlChip {
 mod test {
     use crate::{
         chips::{
-            AddChip, CpuChip, DecodingCheckChip, ProgramMemCheckChip, RangeCheckChip,
+            AddChip, BitOpChip, CpuChip, DecodingCheckChip, ProgramMemCheckChip, RangeCheckChips,
             RegisterMemCheckChip, SllChip, SubChip,
         },
+        machine::{Machine, ProveError},
         test_utils::assert_chip,
         trace::{
             program::iter_program_steps, program_trace::ProgramTracesBuilder, PreprocessedTraces,
@@ -244,6 +245,8 @@ mod test {
         riscv::{BasicBlock, BuiltinOpcode, Instruction, Opcode},
         trace::k_trace_direct,
     };
+    use num_traits::Zero;
+    use stwo::core::fields::{m31::BaseField, qm31::SecureField};
 
     const LOG_SIZE: u32 = PreprocessedTraces::MIN_LOG_SIZE;
 
@@ -301,7 +304,7 @@ mod test {
             SllChip,
             RegisterMemCheckChip,
             ProgramMemCheckChip,
-            RangeCheckChip,
+            RangeCheckChips,
         );
         let basic_block = setup_basic_block_ir();
         let k = 1;
@@ -326,6 +329,81 @@ mod test {
                 &ExtensionsConfig::default(),
             );
         }
+        Chips::finalize_main_trace(&mut traces, &mut side_note, &ExtensionsConfig::default());
         assert_chip::<Chips>(traces, Some(program_traces.finalize()));
     }
+
+    /// Row of `SRLI x8, x7, 8` in [`setup_basic_block_ir`], which divides `0xFFFFFFFF` by `exp1_3 = 1` limb by limb.
+    const DIVISION_ROW: usize = 5;
+
+    /// Forges the lowest limb of the division on [`DIVISION_ROW`] into `255 = 1 * 254 + 1`, which satisfies
+    /// `dividend = divisor * quotient + remainder` but not `remainder < divisor`, making `RemDiff` negative.
+    ///
+    /// The quotient limb doesn't affect the result of a shift by 8, so every constraint still holds, and only the
+    /// range check of `RemDiff` catches the forgery. It runs after the range check chips have counted the honest
+    /// values, as a malicious prover would.
+    struct ForgedRemainderChip;
+
+    impl MachineChip for ForgedRemainderChip {
+        fn fill_main_trace(
+            _traces: &mut TracesBuilder,
+            _row_idx: usize,
+            _vm_step: &Option<ProgramStep>,
+            _side_note: &mut SideNote,
+            _config: &ExtensionsConfig,
+        ) {
+        }
+
+        fn finalize_main_trace(
+            traces: &mut TracesBuilder,
+            _side_note: &mut SideNote,
+            _config: &ExtensionsConfig,
+        ) {
+            let [exp1_3] = traces.column(DIVISION_ROW, Column::Exp1_3);
+            assert_eq!(exp1_3, BaseField::one());
+
+            *traces.column_mut::<WORD_SIZE>(DIVISION_ROW, Column::Rem)[0] = BaseField::one();
+            *traces.column_mut::<WORD_SIZE>(DIVISION_ROW, Column::Qt)[0] = BaseField::from(254u32);
+            // exp1_3 - 1 - rem
+            *traces.column_mut::<WORD_SIZE>(DIVISION_ROW, Column::RemDiff)[0] = -BaseField::one();
+        }
+
+        fn add_constraints<E: EvalAtRow>(
+            _eval: &mut E,
+            _trace_eval: &TraceEval<E>,
+            _lookup_elements: &AllLookupElements,
+            _config: &ExtensionsConfig,
+        ) {
+        }
+    }
+
+    #[test]
+    fn test_srl_remainder_below_divisor() {
+        type Chips = (
+            CpuChip,
+            DecodingCheckChip,
+            SubChip,
+            AddChip,
+            SrlChip,
+            SllChip,
+            BitOpChip,
+            RegisterMemCheckChip,
+            ProgramMemCheckChip,
+            RangeCheckChips,
+        );
+        type ForgedChips = (Chips, ForgedRemainderChip);
+
+        let basic_block = setup_basic_block_ir();
+        let (view, vm_traces) = k_trace_direct(&basic_block, 1).expect("Failed to create trace");
+
+        let proof = Machine::<Chips>::prove_checked(&vm_traces, &view).unwrap();
+        assert!(proof.claimed_sum.iter().sum::<SecureField>().is_zero());
+
+        let result = Machine::<ForgedChips>::prove_checked(&vm_traces, &view);
+        match result {
+            Err(ProveError::UnbalancedRelation { sum, .. }) => assert!(!sum.is_zero()),
+            Err(err) => panic!("unexpected error: {err}"),
+            Ok(_) => panic!("forged remainder must not balance"),
+        }
+    }
 }
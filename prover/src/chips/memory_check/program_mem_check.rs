@@ -85,15 +85,20 @@ impl MachineChip for ProgramMemCheckChip {
                 .last_access_counter
                 .insert(pc, new_access_counter);
         }
-        // Use accessed_program_memory sidenote to fill in the final program memory contents
-        if row_idx == traces.num_rows() - 1 {
-            for (pc, counter) in side_note.program_mem_check.last_access_counter.iter() {
-                let target_row_idx = side_note
-                    .program_mem_check
-                    .find_row_idx(*pc)
-                    .expect("Pc not found in program trace");
-                traces.fill_columns(target_row_idx, *counter, Column::FinalPrgMemoryCtr);
-            }
+    }
+
+    /// Fills `FinalPrgMemoryCtr` columns from the last access counters in the side note
+    fn finalize_main_trace(
+        traces: &mut TracesBuilder,
+        side_note: &mut SideNote,
+        _config: &ExtensionsConfig,
+    ) {
+        for (pc, counter) in side_note.program_mem_check.last_access_counter.iter() {
+            let target_row_idx = side_note
+                .program_mem_check
+                .find_row_idx(*pc)
+                .expect("Pc not found in program trace");
+            traces.fill_columns(target_row_idx, *counter, Column::FinalPrgMemoryCtr);
         }
     }
 
@@ -650,6 +655,11 @@ mod test {
                 &ExtensionsConfig::default(),
             );
         }
+        ProgramMemCheckChip::finalize_main_trace(
+            &mut traces,
+            &mut side_note,
+            &ExtensionsConfig::default(),
+        );
 
         for i in 0..num_steps {
             assert_eq!(
@@ -13,12 +13,14 @@ pub use instructions::{
 };
 
 // M extension
-pub use instructions::MExtensionChips;
+pub use instructions::{
+    DivRemChip, DivuRemuChip, MExtensionChips, MulChip, MulhMulhsuChip, MulhuChip,
+};
 
 pub use cpu::CpuChip;
 pub use custom::CustomInstructionChip;
 pub use decoding::DecodingCheckChip;
 pub use memory_check::{ProgramMemCheckChip, RegisterMemCheckChip, TimestampChip};
-pub use range_check::RangeCheckChip;
+pub use range_check::{range256::Range256Chip, RangeCheckChip, RangeCheckChips};
 
 mod utils;
@@ -4,6 +4,10 @@
 //! This is done with the use of [`stwo_prover::constraint_framework::logup::LookupElements`]
 //! (excluding {0, 1} bool check).
 //!
+//! Every range `0..MAX` is checked by the same [`RangeCheckChip<MAX>`], which reads what to check from its
+//! [`RangeCheckTable`]: a lookup relation, the multiplicities in the side note, and a list of [`RangeCheck`]s. Checking
+//! another column, or another range, only requires adding to such a list.
+//!
 //! Currently a verifier is not protected against summing up multiplicity of the tuple to the modulus of M31.
 //! This may allow the prover to lookup invalid values, but it also requires using the same constrained tuple
 //! exactly `M31::P` times.
@@ -11,6 +15,32 @@
 //! The current guard is to limit the size of the trace such that `2.pow(trace_log_size) * NUM_CHECKED_COLS < M31::P`
 //! for every chip.
 
+use num_traits::{One, Zero};
+#[cfg(feature = "parallel")]
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use stwo::{
+    core::fields::m31::BaseField,
+    prover::backend::simd::{
+        m31::{PackedBaseField, LOG_N_LANES},
+        qm31::PackedSecureField,
+    },
+};
+use stwo_constraint_framework::{
+    EvalAtRow, LogupTraceGenerator, Relation, RelationEFTraitBound, RelationEntry,
+};
+
+use crate::{
+    column::Column,
+    components::{AllLookupElements, RegisteredLookupBound},
+    extensions::ExtensionsConfig,
+    trace::{
+        eval::TraceEval, program_trace::ProgramTraces, sidenote::SideNote, FinalizedTraces,
+        PreprocessedTraces, ProgramStep, TracesBuilder,
+    },
+    traits::MachineChip,
+    virtual_column::{self, VirtualColumn},
+};
+
 pub(crate) mod range128;
 pub(crate) mod range16;
 pub(crate) mod range256;
@@ -18,7 +48,7 @@ pub(crate) mod range32;
 pub(crate) mod range8;
 pub(crate) mod range_bool;
 
-pub type RangeCheckChip = (
+pub type RangeCheckChips = (
     range8::Range8Chip,
     range16::Range16Chip,
     range32::Range32Chip,
@@ -26,3 +56,321 @@ pub type RangeCheckChip = (
     range256::Range256Chip,
     range_bool::RangeBoolChip,
 );
+
+/// The rows a [`RangeCheck`] applies to, given by a flag that is one on those rows and zero elsewhere.
+#[derive(Debug, Clone, Copy)]
+pub enum Selector {
+    /// Every row, including padding rows.
+    Always,
+    /// Every row except padding rows, which are filled with zeros.
+    NotPadding,
+    /// Rows where one of the flag columns is set.
+    AnyOf(&'static [Column]),
+    /// Rows of type R instructions.
+    TypeR,
+    /// Rows of type I instructions, except shifts by an immediate.
+    TypeINoShift,
+    /// Rows of shifts by an immediate.
+    AluImmShift,
+}
+
+impl Selector {
+    fn read_from_traces_builder(self, traces: &TracesBuilder, row_idx: usize) -> BaseField {
+        match self {
+            Self::Always => BaseField::one(),
+            Self::NotPadding => {
+                let [is_padding] =
+                    virtual_column::IsPadding::read_from_traces_builder(traces, row_idx);
+                BaseField::one() - is_padding
+            }
+            Self::AnyOf(flags) => flags
+                .iter()
+                .map(|flag| traces.column::<1>(row_idx, *flag)[0])
+                .sum(),
+            Self::TypeR => virtual_column::IsTypeR::read_from_traces_builder(traces, row_idx)[0],
+            Self::TypeINoShift => {
+                virtual_column::IsTypeINoShift::read_from_traces_builder(traces, row_idx)[0]
+            }
+            Self::AluImmShift => {
+                virtual_column::IsAluImmShift::read_from_traces_builder(traces, row_idx)[0]
+            }
+        }
+    }
+
+    fn read_from_finalized_traces(
+        self,
+        traces: &FinalizedTraces,
+        vec_row: usize,
+    ) -> PackedBaseField {
+        match self {
+            Self::Always => PackedBaseField::one(),
+            Self::NotPadding => {
+                let [is_padding] =
+                    virtual_column::IsPadding::read_from_finalized_traces(traces, vec_row);
+                PackedBaseField::one() - is_padding
+            }
+            Self::AnyOf(flags) => flags
+                .iter()
+                .map(|flag| traces.get_base_column::<1>(*flag)[0].data[vec_row])
+                .sum(),
+            Self::TypeR => virtual_column::IsTypeR::read_from_finalized_traces(traces, vec_row)[0],
+            Self::TypeINoShift => {
+                virtual_column::IsTypeINoShift::read_from_finalized_traces(traces, vec_row)[0]
+            }
+            Self::AluImmShift => {
+                virtual_column::IsAluImmShift::read_from_finalized_traces(traces, vec_row)[0]
+            }
+        }
+    }
+
+    fn eval<E: EvalAtRow>(self, trace_eval: &TraceEval<E>) -> E::F {
+        match self {
+            Self::Always => E::F::one(),
+            Self::NotPadding => {
+                let [is_padding] = virtual_column::IsPadding::eval(trace_eval);
+                E::F::one() - is_padding
+            }
+            Self::AnyOf(flags) => flags.iter().fold(E::F::zero(), |acc, flag| {
+                let [is_flag] = trace_eval.column_eval(*flag);
+                acc + is_flag
+            }),
+            Self::TypeR => virtual_column::IsTypeR::eval(trace_eval)[0].clone(),
+            Self::TypeINoShift => virtual_column::IsTypeINoShift::eval(trace_eval)[0].clone(),
+            Self::AluImmShift => virtual_column::IsAluImmShift::eval(trace_eval)[0].clone(),
+        }
+    }
+}
+
+/// A limb of a column checked by [`RangeCheckChip`] on the rows picked by its [`Selector`].
+#[derive(Debug, Clone, Copy)]
+pub struct RangeCheck {
+    column: Column,
+    limb: usize,
+    selector: Selector,
+}
+
+impl RangeCheck {
+    /// Checks `limb` of `column` on the rows picked by `selector`.
+    pub const fn limb(column: Column, limb: usize, selector: Selector) -> Self {
+        assert!(limb < column.size(), "limb out of range");
+        Self {
+            column,
+            limb,
+            selector,
+        }
+    }
+
+    /// Checks every limb of each of `columns` on the rows picked by `selector`.
+    pub fn columns(columns: &[Column], selector: Selector) -> impl Iterator<Item = Self> + '_ {
+        columns.iter().flat_map(move |&column| {
+            (0..column.size()).map(move |limb| Self::limb(column, limb, selector))
+        })
+    }
+}
+
+/// What [`RangeCheckChip`] checks against the table of values in `0..MAX`.
+pub trait RangeCheckTable {
+    /// The relation values are looked up in, which the multiplicity extension of the table balances.
+    type LookupElements: RegisteredLookupBound;
+
+    /// Limbs checked against the table, in the order of their interaction trace columns.
+    fn checks() -> Vec<RangeCheck>;
+
+    /// The number of times each value of the table is checked, counted while filling the main trace.
+    fn multiplicity(side_note: &mut SideNote) -> &mut [u32];
+}
+
+/// A Chip for range-checking values for `0..MAX`, as listed by its [`RangeCheckTable`]
+///
+/// Range check chips need to be located at the end of the chip composition
+pub struct RangeCheckChip<const MAX: u32>;
+
+impl<const MAX: u32> MachineChip for RangeCheckChip<MAX>
+where
+    Self: RangeCheckTable,
+{
+    fn draw_lookup_elements(
+        all_elements: &mut AllLookupElements,
+        channel: &mut impl stwo::core::channel::Channel,
+        _config: &ExtensionsConfig,
+    ) {
+        all_elements.insert(<Self as RangeCheckTable>::LookupElements::draw(channel));
+    }
+
+    fn fill_main_trace(
+        _traces: &mut TracesBuilder,
+        _row_idx: usize,
+        _step: &Option<ProgramStep>,
+        _side_note: &mut SideNote,
+        _config: &ExtensionsConfig,
+    ) {
+        // Intentionally empty. Other chips write checked values on arbitrary rows, they are counted on finalization.
+    }
+
+    /// Increments the multiplicity of every number checked
+    fn finalize_main_trace(
+        traces: &mut TracesBuilder,
+        side_note: &mut SideNote,
+        _config: &ExtensionsConfig,
+    ) {
+        let traces: &TracesBuilder = traces;
+        let checks = Self::checks();
+        let count_row = |multiplicity: &mut [u32], row_idx: usize| {
+            for check in checks.iter() {
+                let selector = check.selector.read_from_traces_builder(traces, row_idx);
+                if selector.is_zero() {
+                    continue;
+                }
+                let checked = traces.cols[check.column.offset() + check.limb][row_idx].0;
+                #[cfg(not(test))] // Tests need to go past this assertion and break constraints.
+                assert!(
+                    checked < MAX,
+                    "{:?}[{}] is out of range {}",
+                    check.column,
+                    check.limb,
+                    checked
+                );
+                multiplicity[checked as usize] += 1;
+            }
+        };
+
+        let multiplicity = Self::multiplicity(side_note);
+        #[cfg(feature = "parallel")]
+        {
+            let counts = (0..traces.num_rows())
+                .into_par_iter()
+                .fold(
+                    || vec![0u32; MAX as usize],
+                    |mut counts, row_idx| {
+                        count_row(&mut counts, row_idx);
+                        counts
+                    },
+                )
+                .reduce_with(|mut a, b| {
+                    a.iter_mut().zip(b).for_each(|(a, b)| *a += b);
+                    a
+                });
+            for (total, count) in multiplicity.iter_mut().zip(counts.into_iter().flatten()) {
+                *total += count;
+            }
+        }
+        #[cfg(not(feature = "parallel"))]
+        for row_idx in 0..traces.num_rows() {
+            count_row(multiplicity, row_idx);
+        }
+    }
+
+    /// Fills the whole interaction trace in one-go using SIMD in the stwo-usual way
+    ///
+    /// data[vec_row] contains sixteen rows. A single write_frac() adds sixteen numbers.
+    fn fill_interaction_trace(
+        logup_trace_gen: &mut LogupTraceGenerator,
+        original_traces: &FinalizedTraces,
+        _preprocessed_traces: &PreprocessedTraces,
+        _program_traces: &ProgramTraces,
+        lookup_elements: &AllLookupElements,
+    ) {
+        let lookup_elements: &<Self as RangeCheckTable>::LookupElements = lookup_elements.as_ref();
+        let lookup_elements =
+            lookup_elements.as_relation_ref::<PackedBaseField, PackedSecureField>();
+        let log_size = original_traces.log_size();
+
+        // Checks are batched sharing a column, see [`Precombined`].
+        for batch in Self::checks().chunks(CHECKS_PER_COLUMN) {
+            // vec_row is row_idx divided by 16. Because SIMD.
+            let fraction = |vec_row: usize| {
+                batch
+                    .iter()
+                    .map(|check| {
+                        let value = original_traces.get_limb(check.column, check.limb);
+                        let denom: PackedSecureField =
+                            lookup_elements.combine(&[value.data[vec_row]]);
+                        let numerator: PackedSecureField = check
+                            .selector
+                            .read_from_finalized_traces(original_traces, vec_row)
+                            .into();
+                        (numerator, denom)
+                    })
+                    .reduce(|(n_a, d_a), (n_b, d_b)| (n_a * d_b + n_b * d_a, d_a * d_b))
+                    .expect("chunks are not empty")
+            };
+
+            let mut logup_col_gen = logup_trace_gen.new_col();
+            // Fractions are combined in parallel, but written in row order, so the column is the same either way.
+            #[cfg(feature = "parallel")]
+            {
+                let fractions: Vec<_> = (0..1 << (log_size - LOG_N_LANES))
+                    .into_par_iter()
+                    .map(fraction)
+                    .collect();
+                for (vec_row, (numerator, denom)) in fractions.into_iter().enumerate() {
+                    logup_col_gen.write_frac(vec_row, numerator, denom);
+                }
+            }
+            #[cfg(not(feature = "parallel"))]
+            for vec_row in 0..(1 << (log_size - LOG_N_LANES)) {
+                let (numerator, denom) = fraction(vec_row);
+                logup_col_gen.write_frac(vec_row, numerator, denom);
+            }
+            logup_col_gen.finalize_col();
+        }
+    }
+
+    fn add_constraints<E: EvalAtRow>(
+        eval: &mut E,
+        trace_eval: &TraceEval<E>,
+        lookup_elements: &AllLookupElements,
+        _config: &ExtensionsConfig,
+    ) {
+        let lookup_elements: &<Self as RangeCheckTable>::LookupElements = lookup_elements.as_ref();
+
+        // Add checked occurrences to logup sum, batched as in the interaction trace.
+        for batch in Self::checks().chunks(CHECKS_PER_COLUMN) {
+            let (numerator, denom) = batch
+                .iter()
+                .map(|check| {
+                    let value = trace_eval.limb_eval(check.column, check.limb);
+                    let denom: E::EF = lookup_elements
+                        .as_relation_ref::<E::F, E::EF>()
+                        .combine(&[value]);
+                    let numerator: E::EF = check.selector.eval(trace_eval).into();
+                    (numerator, denom)
+                })
+                .reduce(|(n_a, d_a), (n_b, d_b)| {
+                    let numerator = n_a * d_b.clone() + n_b * d_a.clone();
+                    (numerator, d_a * d_b)
+                })
+                .expect("chunks are not empty");
+
+            eval.add_to_relation(RelationEntry::new(&Precombined(denom), numerator, &[]));
+        }
+    }
+}
+
+/// The number of range checks sharing an interaction trace column, see [`Precombined`].
+///
+/// Every check in a column adds one to the degree of its logup constraint, so `k` checks give degree `k + 1` as long as
+/// selectors have degree at most two. Three checks reach degree four, the most the main component allows with its
+/// [`LOG_CONSTRAINT_DEGREE`](crate::components::LOG_CONSTRAINT_DEGREE) of two.
+const CHECKS_PER_COLUMN: usize = 3;
+
+/// A relation whose denominator is combined beforehand, for adding up the fractions of several checks into a single
+/// interaction trace column: `n_a / d_a + n_b / d_b = (n_a·d_b + n_b·d_a) / (d_a·d_b)`, applied repeatedly.
+///
+/// This divides the interaction trace of range checks by [`CHECKS_PER_COLUMN`], at the cost of raising the degree of
+/// their logup constraints.
+struct Precombined<EF>(EF);
+
+impl<F: Clone, EF: RelationEFTraitBound<F>> Relation<F, EF> for Precombined<EF> {
+    fn combine(&self, _values: &[F]) -> EF {
+        self.0.clone()
+    }
+
+    fn get_name(&self) -> &str {
+        "Precombined"
+    }
+
+    fn get_size(&self) -> usize {
+        0
+    }
+}
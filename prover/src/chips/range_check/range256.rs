@@ -2,43 +2,38 @@
 
 use stwo_constraint_framework::{LogupTraceGenerator, Relation, RelationEntry};
 
-use nexus_vm::WORD_SIZE;
-use num_traits::{One, Zero};
-use stwo::{
-    core::fields::{m31::BaseField, qm31::SecureField},
-    prover::backend::simd::{column::BaseColumn, m31::LOG_N_LANES},
-};
+use stwo::prover::backend::simd::column::BaseColumn;
 
 use crate::{
-    column::Column::{
-        self, CReg1TsPrev, CReg2TsPrev, CReg3TsPrev, FinalPrgMemoryCtr, Helper1, HelperT, HelperU,
-        InstrVal, MulP1, MulP3Prime, MulP3PrimePrime, MulP5, OpC16_23, OpC24_31, Pc, PcNextAux,
-        PrevCtr, ProgCtrCur, ProgCtrPrev, Qt, Quotient, Ram1TsPrev, Ram1TsPrevAux, Ram1ValCur,
-        Ram1ValPrev, Ram2TsPrev, Ram2TsPrevAux, Ram2ValCur, Ram2ValPrev, Ram3TsPrev, Ram3TsPrevAux,
-        Ram3ValCur, Ram3ValPrev, Ram4TsPrev, Ram4TsPrevAux, Ram4ValCur, Ram4ValPrev, RamBaseAddr,
-        Reg1TsPrev, Reg2TsPrev, Reg3TsPrev, Rem, RemDiff, Remainder, ValueA, ValueAAbs,
-        ValueAAbsHigh, ValueALow, ValueB, ValueBAbs, ValueC, ValueCAbs,
+    column::{
+        Column::{
+            self, CReg1TsPrev, CReg2TsPrev, CReg3TsPrev, FinalPrgMemoryCtr, Helper1, HelperT,
+            HelperU, InstrVal, MulP1, MulP3Prime, MulP3PrimePrime, MulP5, OpC16_23, OpC24_31, Pc,
+            PcNextAux, PrevCtr, ProgCtrCur, ProgCtrPrev, Qt, Quotient, Ram1TsPrev, Ram1TsPrevAux,
+            Ram2TsPrev, Ram2TsPrevAux, Ram3TsPrev, Ram3TsPrevAux, Ram4TsPrev, Ram4TsPrevAux,
+            RamBaseAddr, Reg1TsPrev, Reg2TsPrev, Reg3TsPrev, Rem, RemDiff, Remainder, ValueA,
+            ValueAAbs, ValueAAbsHigh, ValueALow, ValueB, ValueBAbs, ValueC, ValueCAbs,
+        },
+        ColumnGroup,
     },
     components::AllLookupElements,
-    extensions::ExtensionsConfig,
-    trace::{
-        eval::TraceEval, program_trace::ProgramTraces, sidenote::SideNote, FinalizedTraces,
-        PreprocessedTraces, ProgramStep, TracesBuilder,
-    },
-    traits::MachineChip,
-    virtual_column::{self, VirtualColumn},
+    trace::sidenote::SideNote,
+    virtual_column::{IsTypeU, VirtualColumnForSum},
 };
 
+use super::{RangeCheck, RangeCheckChips, RangeCheckTable, Selector};
+
 /// A Chip for range-checking values for 0..=255
 ///
 /// Range256Chip needs to be located at the end of the chip composition together with the other range check chips
-pub struct Range256Chip;
+pub type Range256Chip = RangeCheckChip<256>;
 
 const LOOKUP_TUPLE_SIZE: usize = 1;
 stwo_constraint_framework::relation!(Range256LookupElements, LOOKUP_TUPLE_SIZE);
 
 impl Range256Chip {
-    const CHECKED_WORDS: [Column; 38] = [
+    /// Columns checked on every row except padding rows, which are filled with zeros.
+    const CHECKED_WORDS: [Column; 37] = [
         Pc,
         PcNextAux,
         InstrVal,
@@ -52,7 +47,6 @@ impl Range256Chip {
         Helper1,
         ProgCtrCur,
         ProgCtrPrev,
-        FinalPrgMemoryCtr,
         CReg1TsPrev,
         CReg2TsPrev,
         CReg3TsPrev,
@@ -79,211 +73,85 @@ impl Range256Chip {
         ValueALow,
     ];
 
-    const CHECKED_BYTES: [Column; 8] = [
-        Ram1ValCur,
-        Ram2ValCur,
-        Ram3ValCur,
-        Ram4ValCur,
-        Ram1ValPrev,
-        Ram2ValPrev,
-        Ram3ValPrev,
-        Ram4ValPrev,
-    ];
+    /// Columns checked on every row including padding rows, because they are filled along the program memory rather
+    /// than the execution.
+    const PROGRAM_CHECKED_WORDS: [Column; 1] = [FinalPrgMemoryCtr];
+
+    const CHECKED_BYTES: [ColumnGroup; 2] = [ColumnGroup::RamValues, ColumnGroup::RamPrevValues];
 
     const CHECKED_HALF_WORDS: [Column; 4] = [MulP1, MulP3Prime, MulP3PrimePrime, MulP5];
 
     const TYPE_U_CHECKED_BYTES: [Column; 2] = [OpC16_23, OpC24_31];
-}
 
-impl MachineChip for Range256Chip {
-    fn draw_lookup_elements(
-        all_elements: &mut AllLookupElements,
-        channel: &mut impl stwo::core::channel::Channel,
-        _config: &ExtensionsConfig,
-    ) {
-        all_elements.insert(Range256LookupElements::draw(channel));
-    }
-
-    /// Increments Multiplicity256 for every number checked
-    fn fill_main_trace(
-        traces: &mut TracesBuilder,
-        row_idx: usize,
-        _step: &Option<ProgramStep>,
-        side_note: &mut SideNote,
-        _config: &ExtensionsConfig,
-    ) {
-        // This chip needs to wait till every other chip finishes writing bytes.
-        // Since some other chips write bytes above the current row, we need to wait till other chips finished filling for the last row.
-        if row_idx + 1 < traces.num_rows() {
-            return;
-        }
-        for row_idx in 0..traces.num_rows() {
-            for col in Self::CHECKED_WORDS.iter() {
-                let value_col: [BaseField; WORD_SIZE] = traces.column(row_idx, *col);
-                fill_main_cols(value_col, side_note);
-            }
-            for col in Self::CHECKED_HALF_WORDS.iter() {
-                let value_col: [BaseField; 2] = traces.column::<2>(row_idx, *col);
-                fill_main_cols(value_col, side_note);
-            }
-            for col in Self::CHECKED_BYTES.iter() {
-                let value_col = traces.column::<1>(row_idx, *col);
-                fill_main_cols(value_col, side_note);
-            }
-            let [type_u] = virtual_column::IsTypeU::read_from_traces_builder(traces, row_idx);
-            if !type_u.is_zero() {
-                for col in Self::TYPE_U_CHECKED_BYTES.iter() {
-                    let value_col = traces.column::<1>(row_idx, *col);
-                    fill_main_cols(value_col, side_note);
-                }
-            }
-        }
-    }
-    /// Fills the whole interaction trace in one-go using SIMD in the stwo-usual way
+    /// Adds a range-check fraction for every row of `value` against the shared 0..=255 table, weighted by `numerator`.
     ///
-    /// data[vec_row] contains sixteen rows. A single write_frac() adds sixteen numbers.
-    fn fill_interaction_trace(
+    /// This lets another chip range-check an intermediate value that isn't stored in one of the columns listed above.
+    /// Callers must do both of the following, or the logup sum won't match:
+    /// - call [`SideNote::range256_add`] during main trace generation once for every row with a non-zero numerator,
+    /// - add the matching relation entry with [`Range256Chip::constrain_checked_value`] in the same order.
+    pub fn add_checked_value(
         logup_trace_gen: &mut LogupTraceGenerator,
-        original_traces: &FinalizedTraces,
-        _preprocessed_traces: &PreprocessedTraces,
-        _program_traces: &ProgramTraces,
-        lookup_element: &AllLookupElements,
+        lookup_elements: &AllLookupElements,
+        value: &BaseColumn,
+        numerator: &BaseColumn,
     ) {
-        let lookup_element: &Range256LookupElements = lookup_element.as_ref();
-
-        // Add checked occurrences to logup sum.
-        for col in Self::CHECKED_WORDS.iter() {
-            let value_basecolumn: [_; WORD_SIZE] = original_traces.get_base_column(*col);
-            check_bytes(
-                value_basecolumn,
-                original_traces.log_size(),
-                logup_trace_gen,
-                lookup_element,
-            );
-        }
-
-        for col in Self::CHECKED_HALF_WORDS.iter() {
-            let value_basecolumn: [_; 2] = original_traces.get_base_column::<2>(*col);
-            check_bytes(
-                value_basecolumn,
-                original_traces.log_size(),
-                logup_trace_gen,
-                lookup_element,
-            );
-        }
-
-        for col in Self::CHECKED_BYTES.iter() {
-            let value_basecolumn = original_traces.get_base_column::<1>(*col);
-            check_bytes(
-                value_basecolumn,
-                original_traces.log_size(),
-                logup_trace_gen,
-                lookup_element,
-            );
-        }
-        for col in Self::TYPE_U_CHECKED_BYTES.iter() {
-            let value_basecolumn = original_traces.get_base_column::<1>(*col);
-            {
-                let log_size = original_traces.log_size();
-                // TODO: we can deal with two limbs at a time.
-                for limb in value_basecolumn.iter() {
-                    let mut logup_col_gen = logup_trace_gen.new_col();
-                    // vec_row is row_idx divided by 16. Because SIMD.
-                    for vec_row in 0..(1 << (log_size - LOG_N_LANES)) {
-                        let checked_tuple = vec![limb.data[vec_row]];
-                        let denom = lookup_element.combine(&checked_tuple);
-                        let [type_u] = virtual_column::IsTypeU::read_from_finalized_traces(
-                            original_traces,
-                            vec_row,
-                        );
-                        logup_col_gen.write_frac(vec_row, type_u.into(), denom);
-                    }
-                    logup_col_gen.finalize_col();
-                }
-            };
+        let lookup_element: &Range256LookupElements = lookup_elements.as_ref();
+        let mut logup_col_gen = logup_trace_gen.new_col();
+        // vec_row is row_idx divided by 16. Because SIMD.
+        for vec_row in 0..value.data.len() {
+            let checked_tuple = vec![value.data[vec_row]];
+            let denom = lookup_element.combine(&checked_tuple);
+            logup_col_gen.write_frac(vec_row, numerator.data[vec_row].into(), denom);
         }
+        logup_col_gen.finalize_col();
     }
 
-    fn add_constraints<E: stwo_constraint_framework::EvalAtRow>(
+    /// Constrains a value checked with [`Range256Chip::add_checked_value`].
+    pub fn constrain_checked_value<E: stwo_constraint_framework::EvalAtRow>(
         eval: &mut E,
-        trace_eval: &TraceEval<E>,
         lookup_elements: &AllLookupElements,
-        _config: &ExtensionsConfig,
+        value: E::F,
+        numerator: E::F,
     ) {
         let lookup_elements: &Range256LookupElements = lookup_elements.as_ref();
-
-        // Add checked occurrences to logup sum.
-        for col in Self::CHECKED_WORDS.iter() {
-            // not using trace_eval! macro because it doesn't accept *col as an argument.
-            let value = trace_eval.column_eval::<WORD_SIZE>(*col);
-            for limb in value.into_iter().take(WORD_SIZE) {
-                eval.add_to_relation(RelationEntry::new(
-                    lookup_elements,
-                    SecureField::one().into(),
-                    &[limb],
-                ));
-            }
-        }
-
-        for col in Self::CHECKED_HALF_WORDS.iter() {
-            let value = trace_eval.column_eval::<2>(*col);
-            for limb in value.into_iter().take(2) {
-                eval.add_to_relation(RelationEntry::new(
-                    lookup_elements,
-                    SecureField::one().into(),
-                    &[limb],
-                ));
-            }
-        }
-
-        for col in Self::CHECKED_BYTES.iter() {
-            let [value] = trace_eval.column_eval(*col);
-
-            eval.add_to_relation(RelationEntry::new(
-                lookup_elements,
-                SecureField::one().into(),
-                &[value],
-            ));
-        }
-
-        for col in Self::TYPE_U_CHECKED_BYTES.iter() {
-            let [value] = trace_eval.column_eval(*col);
-            let [numerator] = virtual_column::IsTypeU::eval(trace_eval);
-
-            eval.add_to_relation(RelationEntry::new(
-                lookup_elements,
-                numerator.into(),
-                &[value],
-            ));
-        }
+        eval.add_to_relation(RelationEntry::new(
+            lookup_elements,
+            numerator.into(),
+            &[value],
+        ));
     }
 }
 
-fn fill_main_cols<const N: usize>(value_col: [BaseField; N], side_note: &mut SideNote) {
-    for (_limb_index, limb) in value_col.iter().enumerate() {
-        let checked = limb.0;
-        #[cfg(not(test))] // Tests need to go past this assertion and break constraints.
-        assert!(checked < 256, "value[{}] is out of range", _limb_index);
-        side_note.range256.multiplicity[checked as usize] += 1;
+impl RangeCheckTable for Range256Chip {
+    type LookupElements = Range256LookupElements;
+
+    fn checks() -> Vec<RangeCheck> {
+        let checked_bytes: Vec<Column> = Self::CHECKED_BYTES
+            .iter()
+            .flat_map(|group| group.columns())
+            .copied()
+            .collect();
+
+        RangeCheck::columns(&Self::CHECKED_WORDS, Selector::NotPadding)
+            .chain(RangeCheck::columns(
+                &Self::PROGRAM_CHECKED_WORDS,
+                Selector::Always,
+            ))
+            .chain(RangeCheck::columns(
+                &Self::CHECKED_HALF_WORDS,
+                Selector::NotPadding,
+            ))
+            .chain(RangeCheck::columns(&checked_bytes, Selector::NotPadding))
+            // IsTypeU is zero on padding rows, so it needs no extra gating.
+            .chain(RangeCheck::columns(
+                &Self::TYPE_U_CHECKED_BYTES,
+                Selector::AnyOf(IsTypeU::columns()),
+            ))
+            .collect()
     }
-}
 
-fn check_bytes<const N: usize>(
-    basecolumn: [&BaseColumn; N],
-    log_size: u32,
-    logup_trace_gen: &mut LogupTraceGenerator,
-    lookup_element: &Range256LookupElements,
-) {
-    // TODO: we can deal with two limbs at a time.
-    for limb in basecolumn.iter() {
-        let mut logup_col_gen = logup_trace_gen.new_col();
-        // vec_row is row_idx divided by 16. Because SIMD.
-        for vec_row in 0..(1 << (log_size - LOG_N_LANES)) {
-            let checked_tuple = vec![limb.data[vec_row]];
-            let denom = lookup_element.combine(&checked_tuple);
-            logup_col_gen.write_frac(vec_row, SecureField::one().into(), denom);
-        }
-        logup_col_gen.finalize_col();
+    fn multiplicity(side_note: &mut SideNote) -> &mut [u32] {
+        &mut side_note.range256.multiplicity
     }
 }
 
@@ -293,15 +161,30 @@ mod test {
 
     use super::*;
 
-    use crate::extensions::ExtensionComponent;
+    use crate::chips::{
+        range_check::{
+            range128::Range128Chip, range16::Range16Chip, range32::Range32Chip, range8::Range8Chip,
+        },
+        AddChip, BitOpChip, CpuChip, DecodingCheckChip, RegisterMemCheckChip,
+    };
+    use crate::extensions::{ExtensionComponent, ExtensionsConfig};
+    use crate::machine::Machine;
     use crate::test_utils::{assert_chip, commit_traces, test_params, CommittedTraces};
-    use crate::trace::program_trace::{ProgramTraceRef, ProgramTracesBuilder};
-    use crate::trace::{preprocessed::PreprocessedBuilder, Word};
+    use crate::trace::program_trace::{ProgramTraceRef, ProgramTraces, ProgramTracesBuilder};
+    use crate::trace::{
+        eval::TraceEval, preprocessed::PreprocessedBuilder, FinalizedTraces, PreprocessedTraces,
+        ProgramStep, TracesBuilder, Word,
+    };
     use crate::traits::MachineChip;
 
-    use nexus_vm::emulator::{Emulator, HarvardEmulator, ProgramInfo};
+    use nexus_vm::emulator::{Emulator, HarvardEmulator, InternalView, ProgramInfo};
+    use nexus_vm::riscv::{BasicBlock, BuiltinOpcode, Instruction, Opcode};
+    use nexus_vm::trace::k_trace_direct;
 
-    use stwo::core::fields::m31::BaseField;
+    use nexus_vm::WORD_SIZE;
+    use num_traits::{One, Zero};
+    use stwo::core::fields::{m31::BaseField, qm31::SecureField};
+    use stwo::prover::backend::simd::m31::PackedBaseField;
 
     #[test]
     fn test_range256_chip_success() {
@@ -325,6 +208,46 @@ mod test {
                 &ExtensionsConfig::default(),
             );
         }
+        Range256Chip::finalize_main_trace(
+            &mut traces,
+            &mut side_note,
+            &ExtensionsConfig::default(),
+        );
+        assert_chip::<Range256Chip>(traces, None);
+    }
+
+    #[test]
+    fn test_range256_padding_rows_not_counted() {
+        const LOG_SIZE: u32 = PreprocessedTraces::MIN_LOG_SIZE;
+        let mut traces = TracesBuilder::new(LOG_SIZE);
+        let program_traces = ProgramTracesBuilder::dummy(LOG_SIZE);
+        let mut side_note = SideNote::new(&program_traces, &HarvardEmulator::default().finalize());
+        // Every row is padding, with in-range values that would otherwise be counted.
+        for row_idx in 0..traces.num_rows() {
+            let buf: Word = array::from_fn(|i| (row_idx + i + 1) as u8);
+
+            traces.fill_columns(row_idx, true, Column::IsPadding);
+            traces.fill_columns_bytes(row_idx, &buf, ValueA);
+            traces.fill_columns_bytes(row_idx, &buf, ValueB);
+            traces.fill_columns_bytes(row_idx, &buf, ValueC);
+
+            Range256Chip::fill_main_trace(
+                &mut traces,
+                row_idx,
+                &None,
+                &mut side_note,
+                &ExtensionsConfig::default(),
+            );
+        }
+        Range256Chip::finalize_main_trace(
+            &mut traces,
+            &mut side_note,
+            &ExtensionsConfig::default(),
+        );
+        // Only the program memory counters, which are zero, are counted on padding rows.
+        let multiplicity = &side_note.range256.multiplicity;
+        assert_eq!(multiplicity[0], (traces.num_rows() * WORD_SIZE) as u32);
+        assert!(multiplicity[1..].iter().all(|m| *m == 0));
         assert_chip::<Range256Chip>(traces, None);
     }
 
@@ -358,6 +281,11 @@ mod test {
                 &ExtensionsConfig::default(),
             );
         }
+        Range256Chip::finalize_main_trace(
+            &mut traces,
+            &mut side_note,
+            &ExtensionsConfig::default(),
+        );
         // modify looked up value
         *traces.column_mut::<{ ValueB.size() }>(12, ValueB)[0] = BaseField::from(256u32);
 
@@ -378,4 +306,109 @@ mod test {
             ext.generate_interaction_trace(component_trace, &side_note, &lookup_elements);
         assert_ne!(claimed_sum + claimed_sum_2, SecureField::zero());
     }
+
+    /// Range-checks the complement `255 - ValueA[0]`, which isn't stored in any column.
+    struct ComplementCheckChip;
+
+    impl MachineChip for ComplementCheckChip {
+        fn fill_main_trace(
+            _traces: &mut TracesBuilder,
+            _row_idx: usize,
+            _step: &Option<ProgramStep>,
+            _side_note: &mut SideNote,
+            _config: &ExtensionsConfig,
+        ) {
+        }
+
+        fn finalize_main_trace(
+            traces: &mut TracesBuilder,
+            side_note: &mut SideNote,
+            _config: &ExtensionsConfig,
+        ) {
+            for row_idx in 0..traces.num_rows() {
+                let [value_a, ..] = traces.column::<WORD_SIZE>(row_idx, ValueA);
+                side_note.range256_add(255 - value_a.0 as u8);
+            }
+        }
+
+        fn fill_interaction_trace(
+            logup_trace_gen: &mut LogupTraceGenerator,
+            original_traces: &FinalizedTraces,
+            _preprocessed_traces: &PreprocessedTraces,
+            _program_traces: &ProgramTraces,
+            lookup_elements: &AllLookupElements,
+        ) {
+            let [value_a, ..] = original_traces.get_base_column::<WORD_SIZE>(ValueA);
+            let complement = BaseColumn {
+                data: value_a
+                    .data
+                    .iter()
+                    .map(|limb| PackedBaseField::broadcast(BaseField::from(255u32)) - *limb)
+                    .collect(),
+                length: value_a.length,
+            };
+            let ones = BaseColumn {
+                data: vec![PackedBaseField::broadcast(BaseField::one()); value_a.data.len()],
+                length: value_a.length,
+            };
+            Range256Chip::add_checked_value(logup_trace_gen, lookup_elements, &complement, &ones);
+        }
+
+        fn add_constraints<E: stwo_constraint_framework::EvalAtRow>(
+            eval: &mut E,
+            trace_eval: &TraceEval<E>,
+            lookup_elements: &AllLookupElements,
+            _config: &ExtensionsConfig,
+        ) {
+            let [value_a, ..] = trace_eval.column_eval::<WORD_SIZE>(ValueA);
+            Range256Chip::constrain_checked_value(
+                eval,
+                lookup_elements,
+                E::F::from(BaseField::from(255u32)) - value_a,
+                E::F::one(),
+            );
+        }
+    }
+
+    #[test]
+    fn test_range256_external_checked_value() {
+        type Chips = (
+            CpuChip,
+            DecodingCheckChip,
+            AddChip,
+            RegisterMemCheckChip,
+            ComplementCheckChip,
+            Range8Chip,
+            Range16Chip,
+            Range32Chip,
+            Range128Chip,
+            Range256Chip,
+            BitOpChip,
+        );
+
+        let basic_block = vec![BasicBlock::new(vec![
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 1, 0, 1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 2, 1, 200),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADD), 3, 2, 1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADD), 4, 3, 2),
+        ])];
+        let (view, vm_traces) = k_trace_direct(&basic_block, 1).expect("Failed to create trace");
+
+        let proof = Machine::<Chips>::prove(&vm_traces, &view).unwrap();
+        Machine::<Chips>::verify(
+            proof,
+            view.get_program_memory(),
+            view.view_associated_data().as_deref().unwrap_or_default(),
+            &[
+                // preprocessed trace is sensitive to this ordering
+                view.get_ro_initial_memory(),
+                view.get_rw_initial_memory(),
+                view.get_public_input(),
+            ]
+            .concat(),
+            view.get_exit_code(),
+            view.get_public_output(),
+        )
+        .unwrap();
+    }
 }
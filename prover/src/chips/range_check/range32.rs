@@ -1,126 +1,48 @@
 // This file contains range-checking values for 0..=31.
 
-use stwo_constraint_framework::{LogupTraceGenerator, Relation, RelationEntry};
-
-use num_traits::One;
-use stwo::{
-    core::fields::{m31::BaseField, qm31::SecureField},
-    prover::backend::simd::m31::LOG_N_LANES,
-};
-
 use crate::{
     column::Column::{self, OpA, OpB, Reg1Address, Reg2Address, Reg3Address},
-    components::AllLookupElements,
-    extensions::ExtensionsConfig,
-    trace::{
-        eval::TraceEval, program_trace::ProgramTraces, sidenote::SideNote, FinalizedTraces,
-        PreprocessedTraces, ProgramStep, TracesBuilder,
-    },
-    traits::MachineChip,
+    trace::sidenote::SideNote,
 };
 
+use super::{RangeCheck, RangeCheckChips, RangeCheckTable, Selector};
+
 /// A Chip for range-checking values for 0..=31
 ///
 /// Range32Chip needs to be located at the end of the chip composition together with the other range check chips
-pub struct Range32Chip;
+pub type Range32Chip = RangeCheckChip<32>;
 
 const LOOKUP_TUPLE_SIZE: usize = 1;
 stwo_constraint_framework::relation!(Range32LookupElements, LOOKUP_TUPLE_SIZE);
 
 const CHECKED: [Column; 5] = [OpA, OpB, Reg1Address, Reg2Address, Reg3Address];
 
-impl MachineChip for Range32Chip {
-    fn draw_lookup_elements(
-        all_elements: &mut AllLookupElements,
-        channel: &mut impl stwo::core::channel::Channel,
-        _config: &ExtensionsConfig,
-    ) {
-        all_elements.insert(Range32LookupElements::draw(channel));
-    }
+impl RangeCheckTable for Range32Chip {
+    type LookupElements = Range32LookupElements;
 
-    /// Increments Multiplicity32 for every number checked
-    fn fill_main_trace(
-        traces: &mut TracesBuilder,
-        row_idx: usize,
-        _step: &Option<ProgramStep>,
-        side_note: &mut SideNote,
-        _config: &ExtensionsConfig,
-    ) {
-        for col in CHECKED.into_iter() {
-            let [val] = traces.column(row_idx, col);
-            fill_main_elm(val, side_note);
-        }
-    }
-    /// Fills the whole interaction trace in one-go using SIMD in the stwo-usual way
-    ///
-    /// data[vec_row] contains sixteen rows. A single write_frac() adds sixteen numbers.
-    fn fill_interaction_trace(
-        logup_trace_gen: &mut LogupTraceGenerator,
-        original_traces: &FinalizedTraces,
-        _preprocessed_traces: &PreprocessedTraces,
-        _program_traces: &ProgramTraces,
-        lookup_element: &AllLookupElements,
-    ) {
-        let lookup_element: &Range32LookupElements = lookup_element.as_ref();
-
-        // Add checked occurrences to logup sum.
-        // TODO: range-check other byte-ranged columns.
-        for col in CHECKED.iter() {
-            let [value_basecolumn]: [_; 1] = original_traces.get_base_column(*col);
-            let log_size = original_traces.log_size();
-            // TODO: we can deal with two limbs at a time.
-            let mut logup_col_gen = logup_trace_gen.new_col();
-            // vec_row is row_idx divided by 16. Because SIMD.
-            for vec_row in 0..(1 << (log_size - LOG_N_LANES)) {
-                let checked_tuple = vec![value_basecolumn.data[vec_row]];
-                let denom = lookup_element.combine(&checked_tuple);
-                logup_col_gen.write_frac(vec_row, SecureField::one().into(), denom);
-            }
-            logup_col_gen.finalize_col();
-        }
+    fn checks() -> Vec<RangeCheck> {
+        RangeCheck::columns(&CHECKED, Selector::Always).collect()
     }
 
-    fn add_constraints<E: stwo_constraint_framework::EvalAtRow>(
-        eval: &mut E,
-        trace_eval: &TraceEval<E>,
-        lookup_elements: &AllLookupElements,
-        _config: &ExtensionsConfig,
-    ) {
-        let lookup_elements: &Range32LookupElements = lookup_elements.as_ref();
-
-        // Add checked occurrences to logup sum.
-        for col in CHECKED.iter() {
-            // not using trace_eval! macro because it doesn't accept *col as an argument.
-            let [value] = trace_eval.column_eval(*col);
-
-            eval.add_to_relation(RelationEntry::new(
-                lookup_elements,
-                SecureField::one().into(),
-                &[value],
-            ));
-        }
+    fn multiplicity(side_note: &mut SideNote) -> &mut [u32] {
+        &mut side_note.range32.multiplicity
     }
 }
 
-fn fill_main_elm(col: BaseField, side_note: &mut SideNote) {
-    let checked = col.0;
-    #[cfg(not(test))] // Tests need to go past this assertion and break constraints.
-    assert!(checked < 32, "value is out of range {}", checked);
-    side_note.range32.multiplicity[checked as usize] += 1;
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
 
-    use crate::extensions::ExtensionComponent;
+    use crate::extensions::{ExtensionComponent, ExtensionsConfig};
     use crate::test_utils::{assert_chip, commit_traces, test_params, CommittedTraces};
     use crate::trace::preprocessed::PreprocessedBuilder;
     use crate::trace::program_trace::{ProgramTraceRef, ProgramTracesBuilder};
+    use crate::trace::{ProgramStep, TracesBuilder};
     use crate::traits::MachineChip;
 
     use nexus_vm::emulator::{Emulator, HarvardEmulator, ProgramInfo};
     use num_traits::Zero;
+    use stwo::core::fields::{m31::BaseField, qm31::SecureField};
 
     #[test]
     fn test_range32_chip_success() {
@@ -144,6 +66,7 @@ mod test {
                 &ExtensionsConfig::default(),
             );
         }
+        Range32Chip::finalize_main_trace(&mut traces, &mut side_note, &ExtensionsConfig::default());
         assert_chip::<Range32Chip>(traces, None);
     }
 
@@ -174,6 +97,7 @@ mod test {
                 &ExtensionsConfig::default(),
             );
         }
+        Range32Chip::finalize_main_trace(&mut traces, &mut side_note, &ExtensionsConfig::default());
         // modify looked up value
         *traces.column_mut::<{ OpA.size() }>(11, OpA)[0] = BaseField::from(32u32);
 
@@ -17,6 +17,92 @@ impl Column {
     pub(crate) const fn reads_next_row_mask(&self) -> bool {
         matches!(self, Self::Pc | Self::IsPadding)
     }
+
+    /// Returns `true` if the column is legitimately all-zero in some traces, e.g. the flag of an opcode
+    /// that the program never executes, and so is not reported by [`FinalizedTraces::unfilled_columns`].
+    ///
+    /// [`FinalizedTraces::unfilled_columns`]: crate::trace::FinalizedTraces::unfilled_columns
+    pub const fn may_be_all_zero(&self) -> bool {
+        matches!(
+            self,
+            Self::IsAdd
+                | Self::IsOr
+                | Self::IsAnd
+                | Self::IsXor
+                | Self::IsSub
+                | Self::IsSltu
+                | Self::IsSlt
+                | Self::IsBne
+                | Self::IsBeq
+                | Self::IsBltu
+                | Self::IsBlt
+                | Self::IsBgeu
+                | Self::IsBge
+                | Self::IsJal
+                | Self::IsSb
+                | Self::IsSh
+                | Self::IsSw
+                | Self::IsLb
+                | Self::IsLh
+                | Self::IsLbu
+                | Self::IsLhu
+                | Self::IsLw
+                | Self::IsLui
+                | Self::IsAuipc
+                | Self::IsJalr
+                | Self::IsSll
+                | Self::IsSrl
+                | Self::IsSra
+                | Self::IsMul
+                | Self::IsMulhu
+                | Self::IsMulh
+                | Self::IsMulhsu
+                | Self::IsDivu
+                | Self::IsDiv
+                | Self::IsRemu
+                | Self::IsRem
+                | Self::IsEcall
+                | Self::IsEbreak
+                | Self::IsSysDebug
+                | Self::IsSysMemoryAdvise
+                | Self::IsSysHalt
+                | Self::IsSysPrivInput
+                | Self::IsSysCycleCount
+                | Self::IsSysStackReset
+                | Self::IsSysHeapReset
+                | Self::IsCustomKeccak
+                | Self::IsPadding
+        )
+    }
+}
+
+/// A set of related columns that are always handled together, such as the lanes of a memory access.
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum ColumnGroup {
+    /// The new values of the four read-write memory lanes, `Ram1ValCur..=Ram4ValCur`.
+    RamValues,
+    /// The previous values of the four read-write memory lanes, `Ram1ValPrev..=Ram4ValPrev`.
+    RamPrevValues,
+    /// The previous timestamps of the four read-write memory lanes, `Ram1TsPrev..=Ram4TsPrev`.
+    RamTimestamps,
+    /// The auxiliary columns for comparing the previous timestamps of the read-write memory lanes with the clock.
+    RamTimestampsAux,
+    /// The previous timestamps of the three register accesses, `Reg1TsPrev..=Reg3TsPrev`.
+    RegTimestamps,
+}
+
+impl ColumnGroup {
+    /// Returns the member columns, ordered by lane or register access.
+    pub const fn columns(self) -> &'static [Column] {
+        use Column::*;
+        match self {
+            Self::RamValues => &[Ram1ValCur, Ram2ValCur, Ram3ValCur, Ram4ValCur],
+            Self::RamPrevValues => &[Ram1ValPrev, Ram2ValPrev, Ram3ValPrev, Ram4ValPrev],
+            Self::RamTimestamps => &[Ram1TsPrev, Ram2TsPrev, Ram3TsPrev, Ram4TsPrev],
+            Self::RamTimestampsAux => &[Ram1TsPrevAux, Ram2TsPrevAux, Ram3TsPrevAux, Ram4TsPrevAux],
+            Self::RegTimestamps => &[Reg1TsPrev, Reg2TsPrev, Reg3TsPrev],
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, ColumnsEnum)]
@@ -670,3 +756,39 @@ pub enum PreprocessedColumn {
 //     pub const fn size(self) -> usize { /* ... */ }
 //     pub const fn offset(self) -> usize { /* ... */ }
 // }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn column_group_members() {
+        use Column::*;
+
+        let expected: [(ColumnGroup, &[Column]); 5] = [
+            (
+                ColumnGroup::RamValues,
+                &[Ram1ValCur, Ram2ValCur, Ram3ValCur, Ram4ValCur],
+            ),
+            (
+                ColumnGroup::RamPrevValues,
+                &[Ram1ValPrev, Ram2ValPrev, Ram3ValPrev, Ram4ValPrev],
+            ),
+            (
+                ColumnGroup::RamTimestamps,
+                &[Ram1TsPrev, Ram2TsPrev, Ram3TsPrev, Ram4TsPrev],
+            ),
+            (
+                ColumnGroup::RamTimestampsAux,
+                &[Ram1TsPrevAux, Ram2TsPrevAux, Ram3TsPrevAux, Ram4TsPrevAux],
+            ),
+            (
+                ColumnGroup::RegTimestamps,
+                &[Reg1TsPrev, Reg2TsPrev, Reg3TsPrev],
+            ),
+        ];
+        for (group, columns) in expected {
+            assert_eq!(group.columns(), columns, "{group:?}");
+        }
+    }
+}
@@ -8,8 +8,17 @@
 //! Internally, [`AllLookupElements`] is a hashmap storing a set of generated alphas and z (=lookup elements) for each
 //! type. Since [`stwo_constraint_framework::Relation`] is not object safe and cannot be boxed, the only way
 //! to store it is by using an enum.
+//!
+//! Relations declared outside of this crate cannot be added to the enum, instead they implement
+//! [`CustomLookupElements`] and are stored type-erased, see [`AllLookupElements::insert_custom`].
+
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    sync::Arc,
+};
 
-use std::{any::TypeId, collections::HashMap};
+use stwo::core::channel::Blake2sChannel;
 
 pub use crate::chips::{
     custom::keccak_lookups::{
@@ -50,32 +59,142 @@ register_relation! {
         KeccakStateLookupElements,
         KeccakBitRotateLookupElements,
     };
-    pub(crate) trait RegisteredLookupBound {}
+    pub trait RegisteredLookupBound {}
+}
+
+/// Lookup elements of a relation declared outside of this crate, typically with [`stwo_constraint_framework::relation`].
+///
+/// # Example
+///
+/// ```ignore
+/// stwo_constraint_framework::relation!(PrecompileLookupElements, 3);
+///
+/// impl CustomLookupElements for PrecompileLookupElements {
+///     fn draw(channel: &mut Blake2sChannel) -> Self {
+///         Self::draw(channel)
+///     }
+/// }
+/// ```
+pub trait CustomLookupElements: Clone + Send + Sync + 'static {
+    fn draw(channel: &mut Blake2sChannel) -> Self;
+}
+
+trait ErasedLookupElements: Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+
+    fn redraw(&self, channel: &mut Blake2sChannel) -> Arc<dyn ErasedLookupElements>;
+}
+
+impl<T: CustomLookupElements> ErasedLookupElements for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn redraw(&self, channel: &mut Blake2sChannel) -> Arc<dyn ErasedLookupElements> {
+        Arc::new(<T as CustomLookupElements>::draw(channel))
+    }
+}
+
+#[derive(Clone)]
+struct CustomVariant {
+    name: &'static str,
+    elements: Arc<dyn ErasedLookupElements>,
+}
+
+impl std::fmt::Debug for CustomVariant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple(self.name).finish_non_exhaustive()
+    }
 }
 
 #[derive(Default, Debug, Clone)]
-pub struct AllLookupElements(HashMap<TypeId, RelationVariant>);
+pub struct AllLookupElements {
+    relations: HashMap<TypeId, RelationVariant>,
+    custom: HashMap<TypeId, CustomVariant>,
+}
 
 impl AllLookupElements {
     pub fn insert<T: Into<RelationVariant> + 'static>(&mut self, relation: T) {
-        if self.0.insert(TypeId::of::<T>(), relation.into()).is_some() {
+        if self
+            .relations
+            .insert(TypeId::of::<T>(), relation.into())
+            .is_some()
+        {
+            panic!("attempt to insert duplicate relation")
+        }
+    }
+
+    /// Inserts lookup elements of a relation declared outside of this crate, to be retrieved with [`Self::custom`].
+    pub fn insert_custom<T: CustomLookupElements>(&mut self, relation: T) {
+        let variant = CustomVariant {
+            name: std::any::type_name::<T>(),
+            elements: Arc::new(relation),
+        };
+        if self.custom.insert(TypeId::of::<T>(), variant).is_some() {
             panic!("attempt to insert duplicate relation")
         }
     }
 
+    /// Returns lookup elements inserted with [`Self::insert_custom`].
+    pub fn custom<T: CustomLookupElements>(&self) -> &T {
+        self.custom
+            .get(&TypeId::of::<T>())
+            .and_then(|variant| variant.elements.as_any().downcast_ref())
+            .expect("lookup elements weren't initialized")
+    }
+
     pub fn dummy() -> Self {
-        Self(HashMap::from_iter(RelationVariant::dummy_array()))
+        Self {
+            relations: HashMap::from_iter(RelationVariant::dummy_array()),
+            custom: HashMap::new(),
+        }
     }
 
     pub fn is_empty(&self) -> bool {
-        self.0.is_empty()
+        self.relations.is_empty() && self.custom.is_empty()
+    }
+
+    /// For each relation, returns its name together with a copy of all lookup elements where only this relation's
+    /// elements are drawn anew from `channel`. Relations are ordered by name.
+    pub(crate) fn redraw_each(&self, channel: &mut Blake2sChannel) -> Vec<(&'static str, Self)> {
+        let mut names: Vec<(&'static str, TypeId)> = self
+            .relations
+            .iter()
+            .map(|(type_id, relation)| (relation.name(), *type_id))
+            .chain(
+                self.custom
+                    .iter()
+                    .map(|(type_id, variant)| (variant.name, *type_id)),
+            )
+            .collect();
+        names.sort();
+
+        names
+            .into_iter()
+            .map(|(name, type_id)| {
+                let mut redrawn = self.clone();
+                if let Some(relation) = self.relations.get(&type_id) {
+                    redrawn.relations.insert(type_id, relation.redraw(channel));
+                } else {
+                    let variant = &self.custom[&type_id];
+                    redrawn.custom.insert(
+                        type_id,
+                        CustomVariant {
+                            name,
+                            elements: variant.elements.redraw(channel),
+                        },
+                    );
+                }
+                (name, redrawn)
+            })
+            .collect()
     }
 }
 
 impl<T: RegisteredLookupBound> AsRef<T> for AllLookupElements {
     fn as_ref(&self) -> &T {
         let variant = self
-            .0
+            .relations
             .get(&TypeId::of::<T>())
             .expect("lookup elements weren't initialized");
         T::unwrap_ref(variant)
@@ -90,7 +209,7 @@ macro_rules! register_relation {
             $($name($name),)*
         }
 
-        $_vis trait $_trait: Sync + Clone + 'static {
+        $_vis trait $_trait: Into<$_enum> + Sync + Clone + 'static {
             type Relation<
                 F: Clone,
                 EF: stwo_constraint_framework::RelationEFTraitBound<F>
@@ -105,6 +224,8 @@ macro_rules! register_relation {
 
             fn unwrap_ref(it: &$_enum) -> &Self;
 
+            fn draw(channel: &mut impl stwo::core::channel::Channel) -> Self;
+
             fn dummy() -> Self;
         }
 
@@ -137,6 +258,10 @@ macro_rules! register_relation {
                     }
                 }
 
+                fn draw(channel: &mut impl stwo::core::channel::Channel) -> Self {
+                    Self::draw(channel)
+                }
+
                 fn dummy() -> Self {
                     Self::dummy()
                 }
@@ -150,6 +275,18 @@ macro_rules! register_relation {
                 <[()]>::len(&[$($crate::components::lookups::replace_expr!($name ())),*])
             };
 
+            fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$name(_) => stringify!($name),)*
+                }
+            }
+
+            fn redraw(&self, channel: &mut impl stwo::core::channel::Channel) -> Self {
+                match self {
+                    $(Self::$name(_) => Self::$name($name::draw(channel)),)*
+                }
+            }
+
             fn dummy_array() -> [(std::any::TypeId, Self); Self::NUM_VARIANTS] {
                 [
                     $(
@@ -1,5 +1,6 @@
 use std::marker::PhantomData;
 
+use stwo::core::channel::Blake2sChannel;
 use stwo_constraint_framework::{EvalAtRow, FrameworkComponent, FrameworkEval, InfoEvaluator};
 
 use crate::extensions::ExtensionsConfig;
@@ -11,7 +12,7 @@ pub(crate) use lookups::RegisteredLookupBound;
 
 pub(super) const LOG_CONSTRAINT_DEGREE: u32 = 2;
 
-pub use lookups::AllLookupElements;
+pub use lookups::{AllLookupElements, CustomLookupElements};
 pub type MachineComponent<C> = FrameworkComponent<MachineEval<C>>;
 
 pub struct MachineEval<C> {
@@ -57,9 +58,16 @@ impl<C: MachineChip> FrameworkEval for MachineEval<C> {
 }
 
 pub(crate) fn machine_component_info<C: MachineChip>(config: ExtensionsConfig) -> InfoEvaluator {
+    // The values of lookup elements don't matter, but chips may look up relations declared outside of this crate,
+    // which only they and custom extensions know how to draw.
+    let mut lookup_elements = AllLookupElements::default();
+    let channel = &mut Blake2sChannel::default();
+    C::draw_lookup_elements(&mut lookup_elements, channel, &config);
+    config.draw_custom_lookup_elements(&mut lookup_elements, channel);
+
     let eval = MachineEval::<C> {
         log_n_rows: 1,
-        lookup_elements: AllLookupElements::dummy(),
+        lookup_elements,
         config,
         _phantom_data: PhantomData,
     };
@@ -0,0 +1,391 @@
+//! Row-by-row constraint evaluation for debugging chips.
+//!
+//! A proof over a trace that violates a constraint fails without telling which one, since the prover only sees that
+//! the composition polynomial is not low-degree. [`find_constraint_violation`] instead evaluates the constraints of
+//! each chip on every row of the trace itself, and reports the first one that is nonzero.
+//! [`Machine::prove_checked`](crate::machine::Machine::prove_checked) runs the same check before proving.
+//!
+//! Lookup relations are not evaluated: a relation only balances over the whole trace, which is checked by
+//! [`Machine::prove_checked`](crate::machine::Machine::prove_checked).
+//!
+//! [`MemoryReport`] similarly attributes the memory taken by the columns of the main and interaction traces to the
+//! chips that fill them, see [`Machine::prove_with_memory_report`](crate::machine::Machine::prove_with_memory_report).
+
+use std::{fmt, ops::Mul};
+
+use num_traits::{One, Zero};
+use stwo::{
+    core::{
+        fields::{
+            m31::BaseField,
+            qm31::{SecureField, SECURE_EXTENSION_DEGREE},
+        },
+        utils::{bit_reverse_index, coset_index_to_circle_domain_index},
+    },
+    prover::backend::simd::{column::BaseColumn, m31::LOG_N_LANES, qm31::PackedSecureField},
+};
+use stwo_constraint_framework::{EvalAtRow, LogupTraceGenerator, Relation, RelationEntry};
+
+use crate::{
+    components::AllLookupElements,
+    extensions::ExtensionsConfig,
+    trace::{
+        eval::{TraceEval, ORIGINAL_TRACE_IDX, PREPROCESSED_TRACE_IDX},
+        program_trace::ProgramTraces,
+        FinalizedTraces, PreprocessedTraces, TracesBuilder,
+    },
+    traits::MachineChip,
+};
+
+/// A constraint that doesn't hold on some row of the trace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConstraintViolation {
+    /// Type name of the chip that added the constraint.
+    pub chip: &'static str,
+    /// Index of the constraint among those added by the chip on this row, in the order of
+    /// [`MachineChip::add_constraints`] calls to [`EvalAtRow::add_constraint`].
+    pub constraint_index: usize,
+    /// Row of the main trace, in the order it was filled.
+    pub row: usize,
+    /// The nonzero value the constraint evaluates to on the row.
+    pub value: SecureField,
+    /// The cells the value of the constraint depends on, in the order they are read.
+    ///
+    /// Chips read every column of the row up front, so these are found by changing each cell in turn and keeping
+    /// those that change the value of the constraint.
+    pub cells: Vec<TraceCell>,
+}
+
+/// A cell of the preprocessed or main trace, see [`ConstraintViolation::cells`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceCell {
+    /// Either [`PREPROCESSED_TRACE_IDX`] or [`ORIGINAL_TRACE_IDX`].
+    pub interaction: usize,
+    /// Index of the column in the trace. Columns of the preprocessed trace are followed by those of the program
+    /// trace.
+    pub column: usize,
+    /// Row of the cell, in the order the trace was filled.
+    pub row: usize,
+    pub value: BaseField,
+}
+
+impl fmt::Display for TraceCell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let trace = if self.interaction == PREPROCESSED_TRACE_IDX {
+            "preprocessed"
+        } else {
+            "main"
+        };
+        write!(
+            f,
+            "{trace} column {} on row {} = {}",
+            self.column, self.row, self.value
+        )
+    }
+}
+
+/// Evaluates constraints of a single row over the trace values, recording the first one that is nonzero.
+pub struct DiagnosticEvaluator<'a> {
+    trace: &'a [Vec<&'a [BaseField]>; 2],
+    col_index: [usize; 2],
+    log_size: u32,
+    row: usize,
+    chip: &'static str,
+    constraint_index: usize,
+    violation: Option<ConstraintViolation>,
+    /// Every cell read so far, in order.
+    cells: Vec<TraceCell>,
+    /// Index in `cells` of the cell whose value is changed when read.
+    changed_cell: Option<usize>,
+    /// The value of the constraint of `violation`, when re-evaluated with `changed_cell`.
+    violation_value: Option<SecureField>,
+}
+
+impl<'a> DiagnosticEvaluator<'a> {
+    fn new(trace: &'a [Vec<&'a [BaseField]>; 2], log_size: u32, row: usize) -> Self {
+        Self {
+            trace,
+            col_index: [0; 2],
+            log_size,
+            row,
+            chip: "",
+            constraint_index: 0,
+            violation: None,
+            cells: Vec::new(),
+            changed_cell: None,
+            violation_value: None,
+        }
+    }
+
+    /// Attributes the constraints added from now on to `chip`.
+    pub fn set_chip(&mut self, chip: &'static str) {
+        self.chip = chip;
+        self.constraint_index = 0;
+    }
+}
+
+impl EvalAtRow for DiagnosticEvaluator<'_> {
+    type F = BaseField;
+    type EF = SecureField;
+
+    fn next_interaction_mask<const N: usize>(
+        &mut self,
+        interaction: usize,
+        offsets: [isize; N],
+    ) -> [Self::F; N] {
+        let column = self.col_index[interaction];
+        let col = self.trace[interaction][column];
+        self.col_index[interaction] += 1;
+
+        let num_rows = 1isize << self.log_size;
+        offsets.map(|offset| {
+            // Offsets are relative to the trace order, and wrap around the column.
+            let row = (self.row as isize + offset).rem_euclid(num_rows) as usize;
+            let value = col[bit_reverse_index(
+                coset_index_to_circle_domain_index(row, self.log_size),
+                self.log_size,
+            )];
+            let changed = self.changed_cell == Some(self.cells.len());
+            self.cells.push(TraceCell {
+                interaction,
+                column,
+                row,
+                value,
+            });
+            if changed {
+                value + BaseField::one()
+            } else {
+                value
+            }
+        })
+    }
+
+    fn add_constraint<G>(&mut self, constraint: G)
+    where
+        Self::EF: Mul<G, Output = Self::EF> + From<G>,
+    {
+        let value = SecureField::from(constraint);
+        match &self.violation {
+            None if self.changed_cell.is_none() && !value.is_zero() => {
+                self.violation = Some(ConstraintViolation {
+                    chip: self.chip,
+                    constraint_index: self.constraint_index,
+                    row: self.row,
+                    value,
+                    cells: Vec::new(),
+                });
+            }
+            Some(violation)
+                if violation.chip == self.chip
+                    && violation.constraint_index == self.constraint_index =>
+            {
+                self.violation_value = Some(value);
+            }
+            _ => {}
+        }
+        self.constraint_index += 1;
+    }
+
+    fn combine_ef(values: [Self::F; SECURE_EXTENSION_DEGREE]) -> Self::EF {
+        SecureField::from_m31_array(values)
+    }
+
+    fn add_to_relation<R: Relation<Self::F, Self::EF>>(
+        &mut self,
+        _entry: RelationEntry<'_, Self::F, Self::EF, R>,
+    ) {
+    }
+
+    fn finalize_logup(&mut self) {}
+
+    fn finalize_logup_in_pairs(&mut self) {}
+}
+
+/// Evaluates the constraints of `C` on every row of the trace, and returns the first one that doesn't hold along with
+/// the cells it depends on.
+///
+/// Rows are checked in order, and within a row chips are checked in the order of `C`.
+pub fn find_constraint_violation<C: MachineChip>(
+    original_traces: &FinalizedTraces,
+    preprocessed_trace: &PreprocessedTraces,
+    program_traces: &ProgramTraces,
+    lookup_elements: &AllLookupElements,
+    config: &ExtensionsConfig,
+) -> Option<ConstraintViolation> {
+    let log_size = original_traces.log_size();
+    let preprocessed_cols: Vec<BaseColumn> = preprocessed_trace
+        .clone()
+        .into_circle_evaluation()
+        .into_iter()
+        .chain(program_traces.clone().into_circle_evaluation())
+        .map(|eval| eval.values)
+        .collect();
+    let original_cols: Vec<BaseColumn> = original_traces
+        .clone()
+        .into_circle_evaluation()
+        .into_iter()
+        .map(|eval| eval.values)
+        .collect();
+
+    let mut trace = [Vec::new(), Vec::new()];
+    trace[PREPROCESSED_TRACE_IDX] = preprocessed_cols.iter().map(BaseColumn::as_slice).collect();
+    trace[ORIGINAL_TRACE_IDX] = original_cols.iter().map(BaseColumn::as_slice).collect();
+
+    let evaluate_row = |row, violation: Option<&ConstraintViolation>, changed_cell| {
+        let mut eval = DiagnosticEvaluator::new(&trace, log_size, row);
+        eval.violation = violation.cloned();
+        eval.changed_cell = changed_cell;
+        let trace_eval = TraceEval::new(&mut eval);
+        C::add_diagnosed_constraints(&mut eval, &trace_eval, lookup_elements, config);
+        eval
+    };
+
+    (0..1 << log_size).find_map(|row| {
+        let eval = evaluate_row(row, None, None);
+        let violation = eval.violation?;
+        let cells = eval
+            .cells
+            .into_iter()
+            .enumerate()
+            .filter(|(changed_cell, _)| {
+                let changed = evaluate_row(row, Some(&violation), Some(*changed_cell));
+                changed.violation_value != Some(violation.value)
+            })
+            .map(|(_, cell)| cell)
+            .collect();
+        Some(ConstraintViolation { cells, ..violation })
+    })
+}
+
+/// Memory taken by the trace columns of a chip, see [`MemoryReport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChipMemory {
+    /// Type name of the chip.
+    pub name: &'static str,
+    /// Bytes of the main trace columns first written by the chip.
+    pub main_bytes: usize,
+    /// Bytes of the interaction trace columns generated by the chip for its lookups.
+    pub interaction_bytes: usize,
+}
+
+impl ChipMemory {
+    /// Name of the entry holding main trace columns that no chip was observed writing.
+    pub const UNATTRIBUTED: &'static str = "unattributed";
+
+    pub fn total_bytes(&self) -> usize {
+        self.main_bytes + self.interaction_bytes
+    }
+}
+
+/// Attributes the memory of trace columns to chips.
+///
+/// Main trace columns are shared between chips, so each one is attributed to the first chip observed changing it.
+/// Observing every row would be too slow, so only the first row of each instruction kind and the first padding row
+/// are observed, and columns only written on other rows (or never written) are reported as
+/// [`ChipMemory::UNATTRIBUTED`]. The sizes of extension components are not included.
+#[derive(Debug, Default)]
+pub struct MemoryReport {
+    chips: Vec<ChipMemory>,
+    column_owners: Vec<Option<usize>>,
+    observe_row: bool,
+}
+
+impl MemoryReport {
+    fn chip_index(&mut self, chip: &'static str) -> usize {
+        match self.chips.iter().position(|entry| entry.name == chip) {
+            Some(index) => index,
+            None => {
+                self.chips.push(ChipMemory {
+                    name: chip,
+                    main_bytes: 0,
+                    interaction_bytes: 0,
+                });
+                self.chips.len() - 1
+            }
+        }
+    }
+
+    /// Sets whether the changes made to the next row are observed.
+    pub(crate) fn observe_row(&mut self, observe: bool) {
+        self.observe_row = observe;
+    }
+
+    /// Runs `fill` on `row_idx` of the main trace, attributing the columns it changes to `chip`.
+    pub fn fill_main_trace(
+        &mut self,
+        chip: &'static str,
+        traces: &mut TracesBuilder,
+        row_idx: usize,
+        fill: impl FnOnce(&mut TracesBuilder),
+    ) {
+        let chip = self.chip_index(chip);
+        if !self.observe_row {
+            fill(traces);
+            return;
+        }
+
+        let cols = traces.cols.len();
+        self.column_owners.resize(cols, None);
+        let before: Vec<BaseField> = traces.cols.iter().map(|col| col[row_idx]).collect();
+        fill(traces);
+
+        for (col, owner) in self.column_owners.iter_mut().enumerate() {
+            if owner.is_none() && traces.cols[col][row_idx] != before[col] {
+                *owner = Some(chip);
+            }
+        }
+    }
+
+    /// Runs `fill` on an interaction trace generator, attributing the columns it generates to `chip`.
+    pub fn fill_interaction_trace(
+        &mut self,
+        chip: &'static str,
+        log_size: u32,
+        fill: impl FnOnce(&mut LogupTraceGenerator),
+    ) {
+        let mut logup_trace_gen = LogupTraceGenerator::new(log_size);
+
+        // Finalizing requires at least one column, so start with one that sums to zero and exclude it.
+        let mut logup_col_gen = logup_trace_gen.new_col();
+        for vec_row in 0..1 << (log_size - LOG_N_LANES) {
+            logup_col_gen.write_frac(
+                vec_row,
+                PackedSecureField::broadcast(SecureField::zero()),
+                PackedSecureField::broadcast(SecureField::one()),
+            );
+        }
+        logup_col_gen.finalize_col();
+
+        fill(&mut logup_trace_gen);
+        let (cols, _) = logup_trace_gen.finalize_last();
+
+        let chip = self.chip_index(chip);
+        self.chips[chip].interaction_bytes +=
+            (cols.len() - SECURE_EXTENSION_DEGREE) * column_bytes(log_size);
+    }
+
+    /// Returns the memory taken by each chip, in the order chips were first seen, followed by the unattributed
+    /// main trace columns if there are any.
+    pub fn finish(mut self, log_size: u32) -> Vec<ChipMemory> {
+        let mut unattributed = 0;
+        for owner in &self.column_owners {
+            match owner {
+                Some(chip) => self.chips[*chip].main_bytes += column_bytes(log_size),
+                None => unattributed += column_bytes(log_size),
+            }
+        }
+
+        if unattributed > 0 {
+            self.chips.push(ChipMemory {
+                name: ChipMemory::UNATTRIBUTED,
+                main_bytes: unattributed,
+                interaction_bytes: 0,
+            });
+        }
+        self.chips
+    }
+}
+
+fn column_bytes(log_size: u32) -> usize {
+    (1 << log_size) * std::mem::size_of::<BaseField>()
+}
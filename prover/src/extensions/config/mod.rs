@@ -3,7 +3,10 @@
 
 use std::collections::HashSet;
 
+use stwo::core::channel::Blake2sChannel;
+
 use super::ExtensionComponent;
+use crate::components::AllLookupElements;
 
 #[derive(Default, Debug, Clone)]
 pub struct ExtensionsConfig(HashSet<ExtensionComponent>);
@@ -36,6 +39,25 @@ impl ExtensionsConfig {
 
         result
     }
+
+    /// Returns whether the extension defined outside of this crate with the given name is enabled, so that a chip
+    /// looking it up can enable its constraints and trace generation.
+    pub fn is_custom_enabled(&self, name: &str) -> bool {
+        self.0
+            .iter()
+            .any(|ext| matches!(ext, ExtensionComponent::Custom(custom) if custom.name() == name))
+    }
+
+    /// Draw lookup elements of custom extensions, in no particular order.
+    pub(crate) fn draw_custom_lookup_elements(
+        &self,
+        lookup_elements: &mut AllLookupElements,
+        channel: &mut Blake2sChannel,
+    ) {
+        for ext in &self.0 {
+            ext.draw_lookup_elements(lookup_elements, channel);
+        }
+    }
 }
 
 #[cfg(test)]
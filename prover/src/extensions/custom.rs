@@ -0,0 +1,139 @@
+//! Extension components defined outside of this crate, such as precompiles.
+//!
+//! Unlike [`BuiltInExtension`](super::BuiltInExtension), [`CustomExtension`] is object safe, so that the prover can
+//! compose components it knows nothing about. A custom extension typically declares its own relation, drawn in
+//! [`CustomExtension::draw_lookup_elements`] and inserted with [`AllLookupElements::insert_custom`], and looks up
+//! values emitted by a chip of the main component, which keeps its data for the extension in the
+//! [side note](SideNote::custom_mut).
+
+use std::{
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+use stwo::{
+    core::{
+        air::Component,
+        channel::Blake2sChannel,
+        fields::{m31::BaseField, qm31::SecureField},
+        pcs::TreeVec,
+        ColumnVec,
+    },
+    prover::{
+        backend::simd::SimdBackend,
+        poly::{circle::CircleEvaluation, BitReversedOrder},
+        ComponentProver,
+    },
+};
+use stwo_constraint_framework::TraceLocationAllocator;
+
+use crate::{
+    components::AllLookupElements,
+    trace::{program_trace::ProgramTraceRef, sidenote::SideNote},
+};
+
+use super::ComponentTrace;
+
+/// An extension component defined outside of this crate.
+///
+/// The methods mirror those of built-in extensions: the prover calls them in the same order, and the claimed sum of
+/// the interaction trace is accumulated together with the ones of every other component, so that it must balance
+/// the sum emitted by the chips looking up the extension.
+pub trait CustomExtension: Send + Sync + 'static {
+    /// Returns the name of the extension, which must be unique among the extensions of the machine.
+    fn name(&self) -> &'static str;
+
+    /// Draw lookup elements of the relations declared by the extension, after those of the main component.
+    fn draw_lookup_elements(
+        &self,
+        _lookup_elements: &mut AllLookupElements,
+        _channel: &mut Blake2sChannel,
+    ) {
+    }
+
+    fn generate_preprocessed_trace(
+        &self,
+        log_size: u32,
+        program_trace_ref: ProgramTraceRef,
+    ) -> ColumnVec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>>;
+
+    fn generate_component_trace(
+        &self,
+        log_size: u32,
+        program_trace_ref: ProgramTraceRef,
+        side_note: &mut SideNote,
+    ) -> ComponentTrace;
+
+    fn generate_interaction_trace(
+        &self,
+        component_trace: ComponentTrace,
+        side_note: &SideNote,
+        lookup_elements: &AllLookupElements,
+    ) -> (
+        ColumnVec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>>,
+        SecureField,
+    );
+
+    fn to_component_prover(
+        &self,
+        tree_span_provider: &mut TraceLocationAllocator,
+        lookup_elements: &AllLookupElements,
+        log_size: u32,
+        claimed_sum: SecureField,
+    ) -> Box<dyn ComponentProver<SimdBackend>>;
+
+    fn to_component(
+        &self,
+        tree_span_provider: &mut TraceLocationAllocator,
+        lookup_elements: &AllLookupElements,
+        log_size: u32,
+        claimed_sum: SecureField,
+    ) -> Box<dyn Component>;
+
+    fn compute_log_size(&self, side_note: &SideNote) -> u32;
+
+    /// Returns the log_size of the component padded to at least `min_log_size`, so that its shape does not depend
+    /// on the execution. Components with a fixed size ignore `min_log_size`.
+    fn compute_padded_log_size(&self, side_note: &SideNote, _min_log_size: u32) -> u32 {
+        self.compute_log_size(side_note)
+    }
+
+    /// Returns the log_sizes of the columns in each tree, usually computed with
+    /// [`InfoEvaluator`](stwo_constraint_framework::InfoEvaluator).
+    fn trace_sizes(&self, log_size: u32) -> TreeVec<Vec<u32>>;
+
+    /// Returns the log_sizes of each preprocessed columns
+    fn preprocessed_trace_sizes(&self, log_size: u32) -> Vec<u32>;
+}
+
+/// A [`CustomExtension`] within [`ExtensionComponent`](super::ExtensionComponent), identified by its name.
+#[derive(Clone)]
+pub struct CustomComponent(pub(super) Arc<dyn CustomExtension>);
+
+impl CustomComponent {
+    pub fn name(&self) -> &'static str {
+        self.0.name()
+    }
+}
+
+impl std::fmt::Debug for CustomComponent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("CustomComponent")
+            .field(&self.name())
+            .finish()
+    }
+}
+
+impl PartialEq for CustomComponent {
+    fn eq(&self, other: &Self) -> bool {
+        self.name() == other.name()
+    }
+}
+
+impl Eq for CustomComponent {}
+
+impl Hash for CustomComponent {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name().hash(state)
+    }
+}
@@ -42,6 +42,13 @@ impl BitRotateAccumulator {
             *self.counter.entry(offset).or_default() += 1;
         }
     }
+
+    /// Adds the counters of `other`.
+    pub fn merge(&mut self, other: Self) {
+        for (offset, count) in other.counter {
+            *self.counter.entry(offset).or_default() += count;
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -68,6 +68,15 @@ impl<const ELEM_BITS: u32, const EXPAND_BITS: u32> BitwiseAccumulator<ELEM_BITS,
             self.mults[*column_idx as usize].as_mut_slice()[*offset as usize].0 += 1;
         }
     }
+
+    /// Adds the multiplicities of `other`.
+    pub fn merge(&mut self, other: &Self) {
+        for (mults, other) in self.mults.iter_mut().zip(&other.mults) {
+            for (mult, other) in mults.as_mut_slice().iter_mut().zip(other.as_slice()) {
+                *mult += *other;
+            }
+        }
+    }
 }
 
 /// Generates the interaction trace for the bitwise table.
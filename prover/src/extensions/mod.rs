@@ -5,11 +5,9 @@
 //! each component can have a smaller log size or higher constraint degree bound. Each component is expected to emit
 //! a logup sum that matches with the one from the main trace, enforcing the total sum to equal to zero.
 //!
-//! There's no support for external our-of-crate extensions, mainly precompiles, yet. All components are considered
-//! to be built-in.
-//!
 //! To define a new built-in component, a struct implementing [`BuiltInExtension`] must be added to [`ExtensionComponent`]
-//! enum.
+//! enum. Components defined outside of this crate, mainly precompiles, implement [`CustomExtension`] instead and are
+//! added with [`ExtensionComponent::custom`].
 //!
 //! Some components must always be present, for example [`final_reg::FinalReg`]. They should only be accessible within
 //! the crate to avoid misuse.
@@ -18,6 +16,7 @@ use ram_init_final::RamInitFinal;
 use stwo::{
     core::{
         air::Component,
+        channel::Blake2sChannel,
         fields::{m31::BaseField, qm31::SecureField},
         pcs::TreeVec,
         ColumnVec,
@@ -40,6 +39,8 @@ use crate::{
 pub(crate) mod bit_op;
 pub(crate) mod final_reg;
 
+mod custom;
+
 mod multiplicity;
 mod multiplicity8;
 mod ram_init_final;
@@ -52,7 +53,8 @@ pub use config::ExtensionsConfig;
 
 pub(crate) mod keccak;
 
-pub(crate) use trace::ComponentTrace;
+pub use custom::{CustomComponent, CustomExtension};
+pub use trace::ComponentTrace;
 
 use bit_op::BitOpMultiplicity;
 use final_reg::FinalReg;
@@ -124,6 +126,12 @@ trait BuiltInExtension {
 
     fn compute_log_size(&self, side_note: &SideNote) -> u32;
 
+    /// Returns the log_size of the component padded to at least `min_log_size`, so that its shape does not depend
+    /// on the execution. Components with a fixed size ignore `min_log_size`.
+    fn compute_padded_log_size(&self, side_note: &SideNote, _min_log_size: u32) -> u32 {
+        self.compute_log_size(side_note)
+    }
+
     fn trace_sizes(&self, log_size: u32) -> TreeVec<Vec<u32>> {
         <Self as BuiltInExtension>::Eval::dummy(log_size)
             .evaluate(InfoEvaluator::empty())
@@ -183,19 +191,25 @@ impl ExtensionComponent {
     pub const fn keccak_extensions() -> &'static [Self] {
         keccak::keccak_extensions()
     }
+
+    /// Returns an extension component defined outside of this crate.
+    pub fn custom(extension: impl CustomExtension) -> Self {
+        Self::Custom(CustomComponent(std::sync::Arc::new(extension)))
+    }
 }
 
 // A macro mimicking enum_dispatch, but with less flexibility and therefore without shared state managing.
 //
 // To avoid repetitive implementations of components, the main trait [`BuiltInExtension`] features associated
-// type with bound which makes it non object safe, or non dyn-compatible. External precompiles, once introduced,
-// will require type-erased version of this trait since the prover crate cannot know details of implementation.
-// Such precompiles can be implemented as a separate `Custom(Box<dyn ...>)` variant.
+// type with bound which makes it non object safe, or non dyn-compatible. External precompiles require type-erased
+// version of this trait since the prover crate cannot know details of implementation, they are implemented as
+// a separate `Custom` variant dispatching to [`CustomExtension`].
 macro_rules! extension_dispatch {
     ($vis:vis enum $_enum:ident { $( $name:ident ),* $(,)? }) => {
         #[derive(Debug, Clone, PartialEq, Eq, Hash)]
         $vis enum $_enum {
             $($name($name),)*
+            Custom(CustomComponent),
         }
 
         $(
@@ -216,6 +230,7 @@ macro_rules! extension_dispatch {
             ) -> ColumnVec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>> {
                 match self {
                     $( $_enum::$name(inner) => <$name as BuiltInExtension>::generate_preprocessed_trace(inner, log_size, program_trace_ref), )*
+                    $_enum::Custom(inner) => inner.0.generate_preprocessed_trace(log_size, program_trace_ref),
                 }
             }
 
@@ -227,6 +242,7 @@ macro_rules! extension_dispatch {
             ) -> ComponentTrace {
                 match self {
                     $( $_enum::$name(inner) => <$name as BuiltInExtension>::generate_component_trace(inner, log_size, program_trace_ref, side_note), )*
+                    $_enum::Custom(inner) => inner.0.generate_component_trace(log_size, program_trace_ref, side_note),
                 }
             }
 
@@ -241,6 +257,7 @@ macro_rules! extension_dispatch {
             ) {
                 match self {
                     $( $_enum::$name(inner) => <$name as BuiltInExtension>::generate_interaction_trace(inner, component_trace, side_note, lookup_elements), )*
+                    $_enum::Custom(inner) => inner.0.generate_interaction_trace(component_trace, side_note, lookup_elements),
                 }
             }
 
@@ -253,6 +270,7 @@ macro_rules! extension_dispatch {
             ) -> Box<dyn ComponentProver<SimdBackend>> {
                 match self {
                     $( $_enum::$name(inner) => <$name as BuiltInExtension>::to_component_prover(inner, tree_span_provider, lookup_elements, log_size, claimed_sum), )*
+                    $_enum::Custom(inner) => inner.0.to_component_prover(tree_span_provider, lookup_elements, log_size, claimed_sum),
                 }
             }
 
@@ -265,24 +283,45 @@ macro_rules! extension_dispatch {
             ) -> Box<dyn Component> {
                 match self {
                     $( $_enum::$name(inner) => <$name as BuiltInExtension>::to_component(inner, tree_span_provider, lookup_elements, log_size, claimed_sum), )*
+                    $_enum::Custom(inner) => inner.0.to_component(tree_span_provider, lookup_elements, log_size, claimed_sum),
+                }
+            }
+
+            pub(crate) fn draw_lookup_elements(
+                &self,
+                lookup_elements: &mut AllLookupElements,
+                channel: &mut Blake2sChannel,
+            ) {
+                if let $_enum::Custom(inner) = self {
+                    inner.0.draw_lookup_elements(lookup_elements, channel);
                 }
             }
 
             pub(crate) fn compute_log_size(&self, side_note: &SideNote) -> u32 {
                 match self {
                     $( $_enum::$name(inner) => <$name as BuiltInExtension>::compute_log_size(inner, side_note), )*
+                    $_enum::Custom(inner) => inner.0.compute_log_size(side_note),
+                }
+            }
+
+            pub(crate) fn compute_padded_log_size(&self, side_note: &SideNote, min_log_size: u32) -> u32 {
+                match self {
+                    $( $_enum::$name(inner) => <$name as BuiltInExtension>::compute_padded_log_size(inner, side_note, min_log_size), )*
+                    $_enum::Custom(inner) => inner.0.compute_padded_log_size(side_note, min_log_size),
                 }
             }
 
             pub(crate) fn trace_sizes(&self, log_size: u32) -> TreeVec<Vec<u32>> {
                 match self {
                     $( $_enum::$name(inner) => <$name as BuiltInExtension>::trace_sizes(inner, log_size), )*
+                    $_enum::Custom(inner) => inner.0.trace_sizes(log_size),
                 }
             }
 
             pub(crate) fn preprocessed_trace_sizes(&self, log_size: u32) -> Vec<u32> {
                 match self {
                     $( $_enum::$name(inner) => <$name as BuiltInExtension>::preprocessed_trace_sizes(log_size), )*
+                    $_enum::Custom(inner) => inner.0.preprocessed_trace_sizes(log_size),
                 }
             }
         }
@@ -341,6 +341,10 @@ impl BuiltInExtension for RamInitFinal {
         let log_size = num_entries.next_power_of_two().trailing_zeros();
         log_size.max(LOG_N_LANES)
     }
+
+    fn compute_padded_log_size(&self, side_note: &SideNote, min_log_size: u32) -> u32 {
+        self.compute_log_size(side_note).max(min_log_size)
+    }
 }
 
 impl RamInitFinal {
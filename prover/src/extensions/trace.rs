@@ -8,6 +8,7 @@ use stwo::{
 use stwo_constraint_framework::{ORIGINAL_TRACE_IDX, PREPROCESSED_TRACE_IDX};
 
 /// Intermediate representation of the component trace.
+#[derive(Clone)]
 pub struct ComponentTrace {
     pub log_size: u32,
     pub preprocessed_trace: Vec<BaseColumn>,
@@ -2,8 +2,18 @@
 // Need this feature to use the `borrowing_sub` method
 #![feature(bigint_helper_methods)]
 
+/// Enters a [`tracing`](https://docs.rs/tracing) span at the given level until the end of the enclosing scope. Expands
+/// to nothing unless the `tracing` feature is enabled.
+macro_rules! phase_span {
+    ($level:ident, $($args:tt)*) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::span!(tracing::Level::$level, $($args)*).entered();
+    };
+}
+
 pub mod chips;
 pub mod components;
+pub mod diagnostics;
 pub mod extensions;
 pub mod trace;
 
@@ -19,22 +29,185 @@ mod test_utils;
 use nexus_vm::emulator::InternalView;
 pub(crate) use nexus_vm::WORD_SIZE;
 
-pub use machine::Proof;
+pub use machine::{
+    DeadlineVerificationError, Proof, ProveError, ProveMonitor, ProvePhase, ProverConfig,
+};
 
 pub use stwo::{core::verifier::VerificationError, prover::ProvingError};
 
 pub fn prove(
     trace: &impl nexus_vm::trace::Trace,
     view: &nexus_vm::emulator::View,
-) -> Result<Proof, ProvingError> {
+) -> Result<Proof, ProveError> {
     machine::Machine::<machine::BaseComponent>::prove(trace, view)
 }
 
+/// Prove and check that every lookup relation balances, see
+/// [`Machine::prove_checked`](machine::Machine::prove_checked).
+pub fn prove_checked(
+    trace: &impl nexus_vm::trace::Trace,
+    view: &nexus_vm::emulator::View,
+) -> Result<Proof, ProveError> {
+    machine::Machine::<machine::BaseComponent>::prove_checked(trace, view)
+}
+
+/// Prove with the execution-dependent parts of the proof padded to at least `min_log_size`, see
+/// [`Machine::prove_with_min_log_size`](machine::Machine::prove_with_min_log_size).
+pub fn prove_with_min_log_size(
+    trace: &impl nexus_vm::trace::Trace,
+    view: &nexus_vm::emulator::View,
+    min_log_size: u32,
+) -> Result<Proof, ProveError> {
+    machine::Machine::<machine::BaseComponent>::prove_with_min_log_size(
+        &[],
+        trace,
+        view,
+        min_log_size,
+    )
+}
+
+/// Prove with the execution-dependent parts of the proof padded to at least `min_log_size`, giving up once `deadline`
+/// passes, see [`Machine::prove_with_deadline`](machine::Machine::prove_with_deadline).
+pub fn prove_with_deadline(
+    trace: &impl nexus_vm::trace::Trace,
+    view: &nexus_vm::emulator::View,
+    min_log_size: u32,
+    deadline: std::time::Instant,
+) -> Result<Proof, ProveError> {
+    machine::Machine::<machine::BaseComponent>::prove_with_deadline(
+        &[],
+        trace,
+        view,
+        min_log_size,
+        deadline,
+    )
+}
+
+/// Prove under the commitment scheme parameters in `config`, optionally giving up once `deadline` passes, see
+/// [`Machine::prove_with_config`](machine::Machine::prove_with_config).
+pub fn prove_with_config(
+    trace: &impl nexus_vm::trace::Trace,
+    view: &nexus_vm::emulator::View,
+    min_log_size: u32,
+    config: &ProverConfig,
+    deadline: Option<std::time::Instant>,
+) -> Result<Proof, ProveError> {
+    machine::Machine::<machine::BaseComponent>::prove_with_config(
+        &[],
+        trace,
+        view,
+        min_log_size,
+        config,
+        deadline,
+    )
+}
+
+/// Prove under the commitment scheme parameters in `config`, reporting every phase to `monitor`, see
+/// [`Machine::prove_monitored`](machine::Machine::prove_monitored).
+pub fn prove_monitored(
+    trace: &impl nexus_vm::trace::Trace,
+    view: &nexus_vm::emulator::View,
+    min_log_size: u32,
+    config: &ProverConfig,
+    deadline: Option<std::time::Instant>,
+    monitor: &dyn ProveMonitor,
+) -> Result<Proof, ProveError> {
+    machine::Machine::<machine::BaseComponent>::prove_monitored(
+        &[],
+        trace,
+        view,
+        min_log_size,
+        config,
+        deadline,
+        monitor,
+    )
+}
+
+/// Prove an execution whose steps are pulled lazily from `steps`, see
+/// [`Machine::prove_from_steps`](machine::Machine::prove_from_steps).
+pub fn prove_from_steps(
+    steps: impl Iterator<Item = trace::ProgramStep>,
+    view: &nexus_vm::emulator::View,
+    min_log_size: u32,
+    config: &ProverConfig,
+) -> Result<Proof, ProveError> {
+    machine::Machine::<machine::BaseComponent>::prove_from_steps(
+        &[],
+        steps,
+        view,
+        min_log_size,
+        config,
+    )
+}
+
+/// Prove reusing a previously generated preprocessed trace, see
+/// [`Machine::prove_with_preprocessed_trace`](machine::Machine::prove_with_preprocessed_trace).
+pub fn prove_with_preprocessed_trace(
+    trace: &impl nexus_vm::trace::Trace,
+    view: &nexus_vm::emulator::View,
+    preprocessed_trace: &trace::PreprocessedTraces,
+    config: &ProverConfig,
+) -> Result<Proof, ProveError> {
+    machine::Machine::<machine::BaseComponent>::prove_with_preprocessed_trace(
+        &[],
+        trace,
+        view,
+        preprocessed_trace,
+        config,
+    )
+}
+
+/// Verify the proof, under whichever valid configuration it was made with.
+///
+/// The configuration is chosen by the prover, see [`verify_with_min_security`] to require a minimum security.
 pub fn verify(proof: Proof, view: &nexus_vm::emulator::View) -> Result<(), VerificationError> {
-    machine::Machine::<machine::BaseComponent>::verify(
+    verify_with_min_security(proof, view, 0)
+}
+
+/// Verify the proof, rejecting it if it has fewer than `min_security_bits` bits of conjectured security, see
+/// [`Proof::check_security`].
+pub fn verify_with_min_security(
+    proof: Proof,
+    view: &nexus_vm::emulator::View,
+    min_security_bits: u32,
+) -> Result<(), VerificationError> {
+    machine::Machine::<machine::BaseComponent>::verify_with_deadline(
+        &[],
+        proof,
+        view.get_program_memory(),
+        view.view_associated_data().as_deref().unwrap_or_default(),
+        view.view_statement_context(),
+        &[
+            // preprocessed trace is sensitive to this ordering
+            view.get_ro_initial_memory(),
+            view.get_rw_initial_memory(),
+            view.get_public_input(),
+        ]
+        .concat(),
+        view.get_exit_code(),
+        view.get_public_output(),
+        min_security_bits,
+        None,
+    )
+    .map_err(|err| match err {
+        DeadlineVerificationError::Verification(err) => err,
+        DeadlineVerificationError::DeadlineExceeded => unreachable!("no deadline was set"),
+    })
+}
+
+/// Verify, giving up once `deadline` passes, see
+/// [`Machine::verify_with_deadline`](machine::Machine::verify_with_deadline).
+pub fn verify_with_deadline(
+    proof: Proof,
+    view: &nexus_vm::emulator::View,
+    deadline: std::time::Instant,
+) -> Result<(), DeadlineVerificationError> {
+    machine::Machine::<machine::BaseComponent>::verify_with_deadline(
+        &[],
         proof,
         view.get_program_memory(),
         view.view_associated_data().as_deref().unwrap_or_default(),
+        view.view_statement_context(),
         &[
             // preprocessed trace is sensitive to this ordering
             view.get_ro_initial_memory(),
@@ -44,5 +217,7 @@ pub fn verify(proof: Proof, view: &nexus_vm::emulator::View) -> Result<(), Verif
         .concat(),
         view.get_exit_code(),
         view.get_public_output(),
+        0,
+        Some(deadline),
     )
 }
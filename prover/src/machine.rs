@@ -1,11 +1,12 @@
-use std::marker::PhantomData;
+use std::{fmt, marker::PhantomData, time::Instant};
 
 use num_traits::Zero;
 use stwo::{
     core::{
         air::Component,
         channel::{Blake2sChannel, Channel},
-        fields::qm31::SecureField,
+        fields::{m31::BaseField, qm31::SecureField},
+        fri::FriConfig,
         pcs::{CommitmentSchemeVerifier, PcsConfig, TreeVec},
         poly::circle::CanonicCoset,
         proof::StarkProof,
@@ -21,8 +22,10 @@ use stwo_constraint_framework::TraceLocationAllocator;
 
 use super::trace::eval::{INTERACTION_TRACE_IDX, ORIGINAL_TRACE_IDX, PREPROCESSED_TRACE_IDX};
 use super::trace::{
-    program::iter_program_steps, program_trace::ProgramTracesBuilder, sidenote::SideNote,
-    PreprocessedTraces, TracesBuilder,
+    program::{iter_program_steps, program_steps},
+    program_trace::{ProgramTraces, ProgramTracesBuilder},
+    sidenote::SideNote,
+    PreprocessedTraces, ProgramStep, TracesBuilder,
 };
 use nexus_vm::{
     emulator::{InternalView, MemoryInitializationEntry, ProgramInfo, PublicOutputEntry, View},
@@ -35,11 +38,12 @@ use crate::{
     chips::{
         AddChip, AuipcChip, BeqChip, BgeChip, BgeuChip, BitOpChip, BltChip, BltuChip, BneChip,
         CpuChip, CustomInstructionChip, DecodingCheckChip, JalChip, JalrChip, LoadStoreChip,
-        LuiChip, MExtensionChips, ProgramMemCheckChip, RangeCheckChip, RegisterMemCheckChip,
+        LuiChip, MExtensionChips, ProgramMemCheckChip, RangeCheckChips, RegisterMemCheckChip,
         SllChip, SltChip, SltuChip, SraChip, SrlChip, SubChip, SyscallChip, TimestampChip,
     },
-    column::{PreprocessedColumn, ProgramColumn},
+    column::{Column, PreprocessedColumn, ProgramColumn},
     components::{self, AllLookupElements},
+    diagnostics::{self, ChipMemory, ConstraintViolation, MemoryReport},
     extensions::{ComponentTrace, ExtensionComponent, ExtensionsConfig},
     trace::program_trace::ProgramTraceRef,
     traits::generate_interaction_trace,
@@ -75,11 +79,11 @@ pub type BaseComponent = (
     RegisterMemCheckChip,
     TimestampChip,
     // Range checks must be positioned at the end. They use values filled by instruction chips.
-    RangeCheckChip,
+    RangeCheckChips,
 );
 /// Base extensions used in conjunction with [`BaseComponent`]. These components are always enabled and are not accessible
 /// to downstream crates. ram_init_final() modifies multiplicities for multiplicity256(), so the ordering between these is important.
-const BASE_EXTENSIONS: &[ExtensionComponent] = &[
+pub(crate) const BASE_EXTENSIONS: &[ExtensionComponent] = &[
     ExtensionComponent::final_reg(),
     ExtensionComponent::bit_op_multiplicity(),
     ExtensionComponent::ram_init_final(),
@@ -90,11 +94,144 @@ const BASE_EXTENSIONS: &[ExtensionComponent] = &[
     ExtensionComponent::multiplicity256(),
 ];
 
+/// Errors that occur during [`Machine::verify_with_deadline`].
+#[derive(Debug)]
+pub enum DeadlineVerificationError {
+    /// The proof is invalid.
+    Verification(VerificationError),
+    /// Verification did not complete before the deadline.
+    DeadlineExceeded,
+}
+
+impl From<VerificationError> for DeadlineVerificationError {
+    fn from(err: VerificationError) -> Self {
+        Self::Verification(err)
+    }
+}
+
+impl fmt::Display for DeadlineVerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Verification(err) => write!(f, "{err}"),
+            Self::DeadlineExceeded => write!(f, "verification deadline exceeded"),
+        }
+    }
+}
+
+impl std::error::Error for DeadlineVerificationError {}
+
+/// Phases of proving an execution, in order, see [`ProveError::DeadlineExceeded`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProvePhase {
+    /// Executing the program to obtain its trace, which precedes proving.
+    Execution,
+    /// Filling the main trace from the execution trace.
+    WitnessGeneration,
+    /// Committing to the preprocessed and main traces.
+    Commitment,
+    /// Generating and committing to the interaction trace.
+    InteractionTrace,
+    /// Computing the composition polynomial, out-of-domain sampling and FRI.
+    StarkProof,
+}
+
+impl fmt::Display for ProvePhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Execution => "execution",
+            Self::WitnessGeneration => "witness generation",
+            Self::Commitment => "commitment",
+            Self::InteractionTrace => "interaction trace",
+            Self::StarkProof => "stark proof",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Errors that occur during [`Machine::prove`] and its variants.
+#[derive(Debug)]
+pub enum ProveError {
+    /// Proving the trace failed.
+    Proving(ProvingError),
+    /// The execution needs a trace larger than allowed, which is [`PreprocessedTraces::MAX_LOG_SIZE`] unless the size
+    /// was fixed beforehand, as by [`Machine::prove_with_preprocessed_trace`] or [`Machine::prove_from_steps`].
+    ExecutionTooLong { needed_log_size: u32, max: u32 },
+    /// A lookup relation doesn't balance, so the proof would be rejected. Only returned by [`Machine::prove_checked`].
+    ///
+    /// `sum` is the total logup sum of the proof, which equals the relation's own sum when it is the only one that
+    /// doesn't balance.
+    UnbalancedRelation {
+        name: &'static str,
+        sum: SecureField,
+    },
+    /// The deadline passed before `phase` started. Only returned by [`Machine::prove_with_deadline`].
+    DeadlineExceeded { phase: ProvePhase },
+    /// The [`ProveMonitor`] cancelled proving before `phase` started. Only returned by [`Machine::prove_monitored`].
+    Cancelled { phase: ProvePhase },
+    /// A constraint of a chip doesn't hold on some row of the trace, so the proof would be rejected. Only returned by
+    /// [`Machine::prove_checked`], or when [`ProverConfig::debug_constraints`] is set.
+    ConstraintViolation(ConstraintViolation),
+    /// The [`ProverConfig`] is not supported by the commitment scheme, for the given reason.
+    InvalidConfig(String),
+}
+
+impl From<ProvingError> for ProveError {
+    fn from(err: ProvingError) -> Self {
+        Self::Proving(err)
+    }
+}
+
+impl fmt::Display for ProveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Proving(err) => write!(f, "{err}"),
+            Self::ExecutionTooLong {
+                needed_log_size,
+                max,
+            } => write!(
+                f,
+                "execution needs a trace of log size {needed_log_size}, above the maximum of {max}; \
+                 consider splitting it into segments and proving them separately"
+            ),
+            Self::UnbalancedRelation { name, sum } => {
+                write!(f, "lookup relation {name} is unbalanced, logup sum is {sum}")
+            }
+            Self::DeadlineExceeded { phase } => {
+                write!(f, "proving deadline exceeded before {phase}")
+            }
+            Self::Cancelled { phase } => write!(f, "proving cancelled before {phase}"),
+            Self::InvalidConfig(reason) => write!(f, "invalid prover configuration: {reason}"),
+            Self::ConstraintViolation(violation) => {
+                write!(
+                    f,
+                    "constraint {} of {} doesn't hold on row {}, evaluating to {}",
+                    violation.constraint_index, violation.chip, violation.row, violation.value
+                )?;
+                for (i, cell) in violation.cells.iter().enumerate() {
+                    let sep = if i == 0 { " from " } else { ", " };
+                    write!(f, "{sep}{cell}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ProveError {}
+
+/// Observes a proof in progress, and may cancel it between phases, see [`Machine::prove_monitored`].
+pub trait ProveMonitor: Sync {
+    /// Called before `phase` starts, with the log size of the main trace. Returning `false` cancels proving with
+    /// [`ProveError::Cancelled`].
+    fn enter_phase(&self, phase: ProvePhase, log_size: u32) -> bool;
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Proof {
     pub stark_proof: StarkProof<Blake2sMerkleHasher>,
     pub claimed_sum: Vec<SecureField>, // one per component
     pub log_size: Vec<u32>,            // one per component
+    pub config: ProverConfig,
 }
 
 impl Proof {
@@ -104,10 +241,170 @@ impl Proof {
             stark_proof,
             claimed_sum,
             log_size,
+            config,
         } = self;
         stark_proof.size_estimate()
             + claimed_sum.len() * std::mem::size_of::<SecureField>()
             + log_size.len() * std::mem::size_of::<u32>()
+            + std::mem::size_of_val(config)
+    }
+
+    /// Check that the proof was made under a valid configuration with at least `min_security_bits` bits of conjectured
+    /// security, see [`ProverConfig::security_bits`].
+    ///
+    /// The configuration is chosen by the prover, so verifiers accepting proofs from untrusted provers should check it
+    /// against a minimum, such as [`ProverConfig::RECOMMENDED_MIN_SECURITY_BITS`]. A minimum of zero only checks that
+    /// the configuration is valid, which verification always does.
+    pub fn check_security(&self, min_security_bits: u32) -> Result<(), VerificationError> {
+        self.config
+            .validate()
+            .map_err(VerificationError::InvalidStructure)?;
+        let security_bits = self.config.security_bits();
+        if security_bits < min_security_bits {
+            return Err(VerificationError::InvalidStructure(format!(
+                "proof has {security_bits} bits of security, below the minimum of {min_security_bits}"
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Parameters of the polynomial commitment scheme, trading proving time and proof size for security.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProverConfig {
+    /// Log2 of the FRI blowup factor, between [`Self::MIN_LOG_BLOWUP_FACTOR`] and [`Self::MAX_LOG_BLOWUP_FACTOR`].
+    pub log_blowup_factor: u32,
+    /// Number of FRI queries, between one and [`Self::MAX_N_QUERIES`].
+    pub n_queries: usize,
+    /// Number of proof-of-work bits required before drawing the queries, at most [`Self::MAX_POW_BITS`].
+    pub pow_bits: u32,
+    /// Run the checks of [`Machine::prove_checked`] while proving, failing with [`ProveError::ConstraintViolation`]
+    /// or [`ProveError::UnbalancedRelation`] instead of producing a proof the verifier rejects.
+    ///
+    /// This only affects the prover: it is neither serialized nor recorded in the proof.
+    #[serde(skip)]
+    pub debug_constraints: bool,
+}
+
+impl ProverConfig {
+    /// The smallest supported log2 of the FRI blowup factor.
+    pub const MIN_LOG_BLOWUP_FACTOR: u32 = 1;
+    /// The largest supported log2 of the FRI blowup factor.
+    pub const MAX_LOG_BLOWUP_FACTOR: u32 = 16;
+    /// The largest supported number of FRI queries, far beyond what any level of security needs.
+    pub const MAX_N_QUERIES: usize = 1 << 10;
+    /// The largest supported number of proof-of-work bits.
+    pub const MAX_POW_BITS: u32 = 64;
+    /// The conjectured security, in bits, that verifiers accepting proofs from untrusted provers are advised to require
+    /// with [`verify_with_min_security`](crate::verify_with_min_security).
+    pub const RECOMMENDED_MIN_SECURITY_BITS: u32 = 80;
+
+    /// Returns the configuration reaching `security_bits` bits of conjectured security with the given blowup factor
+    /// and proof-of-work, using as few queries as possible.
+    ///
+    /// The parameters are clamped to the supported ranges.
+    pub fn with_security_bits(security_bits: u32, log_blowup_factor: u32, pow_bits: u32) -> Self {
+        let log_blowup_factor =
+            log_blowup_factor.clamp(Self::MIN_LOG_BLOWUP_FACTOR, Self::MAX_LOG_BLOWUP_FACTOR);
+        let pow_bits = pow_bits.min(Self::MAX_POW_BITS);
+        let query_bits = security_bits.saturating_sub(pow_bits);
+        Self {
+            log_blowup_factor,
+            n_queries: (query_bits.div_ceil(log_blowup_factor) as usize)
+                .clamp(1, Self::MAX_N_QUERIES),
+            pow_bits,
+            debug_constraints: false,
+        }
+    }
+
+    /// 96 bits of conjectured security with the smallest blowup factor, which keeps proving fast at the cost of larger
+    /// proofs.
+    ///
+    /// Unlike the default configuration, this passes a check against [`Self::RECOMMENDED_MIN_SECURITY_BITS`].
+    pub fn recommended() -> Self {
+        Self::with_security_bits(96, Self::MIN_LOG_BLOWUP_FACTOR, 10)
+    }
+
+    /// Check that the parameters are supported by the commitment scheme, returning the reason otherwise.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(Self::MIN_LOG_BLOWUP_FACTOR..=Self::MAX_LOG_BLOWUP_FACTOR)
+            .contains(&self.log_blowup_factor)
+        {
+            return Err(format!(
+                "log blowup factor {} is outside of {}..={}",
+                self.log_blowup_factor,
+                Self::MIN_LOG_BLOWUP_FACTOR,
+                Self::MAX_LOG_BLOWUP_FACTOR
+            ));
+        }
+        if !(1..=Self::MAX_N_QUERIES).contains(&self.n_queries) {
+            return Err(format!(
+                "number of queries {} is outside of 1..={}",
+                self.n_queries,
+                Self::MAX_N_QUERIES
+            ));
+        }
+        if self.pow_bits > Self::MAX_POW_BITS {
+            return Err(format!(
+                "{} proof-of-work bits is above the maximum of {}",
+                self.pow_bits,
+                Self::MAX_POW_BITS
+            ));
+        }
+        Ok(())
+    }
+
+    /// The conjectured security of proofs made with this configuration, in bits: each query contributes the log of the
+    /// blowup factor, and the proof-of-work adds its bits.
+    pub fn security_bits(&self) -> u32 {
+        let n_queries = u32::try_from(self.n_queries).unwrap_or(u32::MAX);
+        self.log_blowup_factor
+            .saturating_mul(n_queries)
+            .saturating_add(self.pow_bits)
+    }
+
+    /// Estimate the memory taken by the main, preprocessed and program trace columns of a trace of `2^log_size` rows,
+    /// once evaluated over the blowup domain to be committed.
+    ///
+    /// The interaction trace and the extension components are not included, so this is a lower bound on the memory
+    /// needed for proving.
+    ///
+    /// Saturates at `usize::MAX` if the size doesn't fit.
+    pub fn committed_trace_bytes(&self, log_size: u32) -> usize {
+        let columns =
+            Column::COLUMNS_NUM + PreprocessedColumn::COLUMNS_NUM + ProgramColumn::COLUMNS_NUM;
+        let rows = 1usize
+            .checked_shl(log_size.saturating_add(self.log_blowup_factor))
+            .unwrap_or(usize::MAX);
+        columns
+            .saturating_mul(rows)
+            .saturating_mul(std::mem::size_of::<BaseField>())
+    }
+
+    /// The parameters of the commitment scheme, which must have been checked with [`Self::validate`].
+    fn pcs_config(&self) -> PcsConfig {
+        let mut config = PcsConfig::default();
+        config.pow_bits = self.pow_bits;
+        config.fri_config = FriConfig::new(
+            config.fri_config.log_last_layer_degree_bound,
+            self.log_blowup_factor,
+            self.n_queries,
+        );
+        config
+    }
+}
+
+impl Default for ProverConfig {
+    /// The default parameters of the commitment scheme, [`PcsConfig::default`], which favor proving time over
+    /// security. See [`Self::recommended`] for a configuration suited to untrusted verifiers.
+    fn default() -> Self {
+        let config = PcsConfig::default();
+        Self {
+            log_blowup_factor: config.fri_config.log_blowup_factor,
+            n_queries: config.fri_config.n_queries,
+            pow_bits: config.pow_bits,
+            debug_constraints: false,
+        }
     }
 }
 
@@ -123,7 +420,7 @@ pub struct Machine<C = BaseComponent> {
 }
 
 impl<C: MachineChip + Sync> Machine<C> {
-    pub fn prove(trace: &impl Trace, view: &View) -> Result<Proof, ProvingError> {
+    pub fn prove(trace: &impl Trace, view: &View) -> Result<Proof, ProveError> {
         Self::prove_with_extensions(&[], trace, view)
     }
 
@@ -131,57 +428,515 @@ impl<C: MachineChip + Sync> Machine<C> {
         extensions: &[ExtensionComponent],
         trace: &impl Trace,
         view: &View,
-    ) -> Result<Proof, ProvingError> {
-        let num_steps = trace.get_num_steps();
+    ) -> Result<Proof, ProveError> {
+        Self::prove_with_min_log_size(extensions, trace, view, PreprocessedTraces::MIN_LOG_SIZE)
+    }
+
+    /// Proves the execution with the main trace, and every component whose size depends on the execution, padded
+    /// to at least `min_log_size`.
+    ///
+    /// As long as the execution fits within `min_log_size`, the shape (and so the size) of the proof does not
+    /// depend on the execution.
+    pub fn prove_with_min_log_size(
+        extensions: &[ExtensionComponent],
+        trace: &impl Trace,
+        view: &View,
+        min_log_size: u32,
+    ) -> Result<Proof, ProveError> {
+        Self::prove_inner(
+            extensions,
+            trace,
+            view,
+            min_log_size,
+            None,
+            &ProverConfig::default(),
+            false,
+            None,
+            None,
+        )
+    }
+
+    /// Proves the execution as [`Self::prove_with_min_log_size`] under the commitment scheme parameters in `config`,
+    /// giving up with [`ProveError::DeadlineExceeded`] if `deadline` passes, as [`Self::prove_with_deadline`].
+    pub fn prove_with_config(
+        extensions: &[ExtensionComponent],
+        trace: &impl Trace,
+        view: &View,
+        min_log_size: u32,
+        config: &ProverConfig,
+        deadline: Option<Instant>,
+    ) -> Result<Proof, ProveError> {
+        Self::prove_inner(
+            extensions,
+            trace,
+            view,
+            min_log_size,
+            None,
+            config,
+            false,
+            deadline,
+            None,
+        )
+    }
+
+    /// Proves the execution as [`Self::prove_with_config`], reporting every phase to `monitor` before it starts, and
+    /// giving up with [`ProveError::Cancelled`] if the monitor cancels it.
+    ///
+    /// Like the deadline, cancellation is checked cooperatively between proving phases.
+    pub fn prove_monitored(
+        extensions: &[ExtensionComponent],
+        trace: &impl Trace,
+        view: &View,
+        min_log_size: u32,
+        config: &ProverConfig,
+        deadline: Option<Instant>,
+        monitor: &dyn ProveMonitor,
+    ) -> Result<Proof, ProveError> {
+        Self::prove_inner(
+            extensions,
+            trace,
+            view,
+            min_log_size,
+            None,
+            config,
+            false,
+            deadline,
+            Some(monitor),
+        )
+    }
+
+    /// Proves the execution as [`Self::prove_with_min_log_size`], giving up with [`ProveError::DeadlineExceeded`] if
+    /// `deadline` passes.
+    ///
+    /// The deadline is checked cooperatively between proving phases, so a phase that has already started always runs
+    /// to completion.
+    pub fn prove_with_deadline(
+        extensions: &[ExtensionComponent],
+        trace: &impl Trace,
+        view: &View,
+        min_log_size: u32,
+        deadline: Instant,
+    ) -> Result<Proof, ProveError> {
+        Self::prove_inner(
+            extensions,
+            trace,
+            view,
+            min_log_size,
+            None,
+            &ProverConfig::default(),
+            false,
+            Some(deadline),
+            None,
+        )
+    }
+
+    /// Proves the execution whose steps are pulled from `steps` while filling the main trace, so that they can be
+    /// produced lazily (e.g. by an emulator running alongside) instead of being held in memory all at once.
+    ///
+    /// The main trace is allocated before any step is pulled, so its size is fixed by `min_log_size` (raised to fit
+    /// the program) as in [`Self::prove_with_min_log_size`], rather than by the number of steps. The resulting proof
+    /// is identical to proving the same steps from a buffered trace with the same `min_log_size`.
+    ///
+    /// Fails with [`ProveError::ExecutionTooLong`] if `steps` yields more steps than fit within the main trace.
+    pub fn prove_from_steps(
+        extensions: &[ExtensionComponent],
+        steps: impl Iterator<Item = ProgramStep>,
+        view: &View,
+        min_log_size: u32,
+        config: &ProverConfig,
+    ) -> Result<Proof, ProveError> {
+        let log_size = Self::log_size_for_steps(0, view, min_log_size)?;
+        Self::prove_steps(
+            extensions,
+            steps,
+            view,
+            log_size,
+            min_log_size,
+            None,
+            config,
+            false,
+            None,
+            None,
+        )
+    }
+
+    /// Proves the execution reusing a previously generated preprocessed trace, which also fixes the shape of the
+    /// proof as in [`Self::prove_with_min_log_size`].
+    ///
+    /// Fails with [`ProveError::ExecutionTooLong`] if the execution or the program doesn't fit within the preprocessed
+    /// trace.
+    pub fn prove_with_preprocessed_trace(
+        extensions: &[ExtensionComponent],
+        trace: &impl Trace,
+        view: &View,
+        preprocessed_trace: &PreprocessedTraces,
+        config: &ProverConfig,
+    ) -> Result<Proof, ProveError> {
+        Self::prove_inner(
+            extensions,
+            trace,
+            view,
+            preprocessed_trace.log_size(),
+            Some(preprocessed_trace),
+            config,
+            false,
+            None,
+            None,
+        )
+    }
+
+    /// Proves the execution as [`Self::prove`], checking that the constraints of every chip hold and that every lookup
+    /// relation balances.
+    ///
+    /// A constraint that doesn't hold or a relation that doesn't balance, usually because of a bug in a chip, results
+    /// in a proof that the verifier rejects. Instead of returning such a proof, this fails with
+    /// [`ProveError::ConstraintViolation`] pinpointing the first failing constraint, as
+    /// [`Self::find_constraint_violation`], or with [`ProveError::UnbalancedRelation`] naming the relation. The checks
+    /// evaluate the constraints row by row and recompute the logup sums once per relation, so they are considerably
+    /// slower than proving.
+    ///
+    /// The same checks run under any other proving entry point when [`ProverConfig::debug_constraints`] is set.
+    pub fn prove_checked(trace: &impl Trace, view: &View) -> Result<Proof, ProveError> {
+        Self::prove_inner(
+            &[],
+            trace,
+            view,
+            PreprocessedTraces::MIN_LOG_SIZE,
+            None,
+            &ProverConfig::default(),
+            true,
+            None,
+            None,
+        )
+    }
+
+    /// Returns the log size of the main trace needed to prove the execution, padded to at least `min_log_size`.
+    ///
+    /// Fails with [`ProveError::ExecutionTooLong`] if it exceeds [`PreprocessedTraces::MAX_LOG_SIZE`].
+    pub fn required_log_size(
+        trace: &impl Trace,
+        view: &View,
+        min_log_size: u32,
+    ) -> Result<u32, ProveError> {
+        Self::log_size_for_steps(trace.get_num_steps(), view, min_log_size)
+    }
+
+    fn log_size_for_steps(
+        num_steps: usize,
+        view: &View,
+        min_log_size: u32,
+    ) -> Result<u32, ProveError> {
         let program_len = view.get_program_memory().program.len();
-        let log_size =
-            Self::max_log_size(&[num_steps, program_len]).max(PreprocessedTraces::MIN_LOG_SIZE);
+        let log_size = Self::max_log_size(&[num_steps, program_len])
+            .max(PreprocessedTraces::MIN_LOG_SIZE)
+            .max(min_log_size);
 
-        let extensions_config = ExtensionsConfig::from(extensions);
-        let extensions_iter = BASE_EXTENSIONS.iter().chain(extensions);
+        if log_size > PreprocessedTraces::MAX_LOG_SIZE {
+            return Err(ProveError::ExecutionTooLong {
+                needed_log_size: log_size,
+                max: PreprocessedTraces::MAX_LOG_SIZE,
+            });
+        }
+        Ok(log_size)
+    }
+
+    /// Evaluates the constraints of every chip of the machine on each row of the execution trace, and returns the
+    /// first one that doesn't hold, see [`diagnostics`](crate::diagnostics).
+    ///
+    /// This is a debugging aid for building new chips: unlike proving, which only reports that the composition
+    /// polynomial is not low-degree, it pinpoints the failing chip, constraint and row. Lookup relations and the
+    /// constraints of extension components are not checked, use [`Machine::prove_checked`] for the former.
+    pub fn find_constraint_violation(
+        trace: &impl Trace,
+        view: &View,
+    ) -> Result<Option<ConstraintViolation>, ProveError> {
+        let log_size = Self::required_log_size(trace, view, PreprocessedTraces::MIN_LOG_SIZE)?;
+        let extensions_config = ExtensionsConfig::default();
 
-        // Fill columns of the preprocessed trace.
         let preprocessed_trace = PreprocessedTraces::new(log_size);
+        let init_memory = Self::init_memory(view);
+        let program_trace_ref = Self::program_trace_ref(view, &init_memory);
+        let (prover_traces, finalized_program_trace, _) = Self::fill_traces(
+            program_steps(trace),
+            view,
+            log_size,
+            program_trace_ref,
+            &extensions_config,
+        )?;
+        let finalized_trace = prover_traces.finalize();
 
-        // Fill columns of the original trace.
+        // The values of lookup elements don't matter, relations are not evaluated.
+        let mut lookup_elements = AllLookupElements::default();
+        C::draw_lookup_elements(
+            &mut lookup_elements,
+            &mut Blake2sChannel::default(),
+            &extensions_config,
+        );
+
+        Ok(diagnostics::find_constraint_violation::<C>(
+            &finalized_trace,
+            &preprocessed_trace,
+            &finalized_program_trace,
+            &lookup_elements,
+            &extensions_config,
+        ))
+    }
+
+    /// Proves the execution as [`Self::prove`], and reports the memory taken by the main and interaction trace
+    /// columns of each chip, see [`MemoryReport`].
+    ///
+    /// The report is computed by filling the traces once more before proving, so this is meant for diagnosing
+    /// out-of-memory failures rather than for regular use.
+    pub fn prove_with_memory_report(
+        trace: &impl Trace,
+        view: &View,
+    ) -> Result<(Proof, Vec<ChipMemory>), ProveError> {
+        let report = Self::memory_report(trace, view)?;
+        let proof = Self::prove(trace, view)?;
+        Ok((proof, report))
+    }
+
+    fn memory_report(trace: &impl Trace, view: &View) -> Result<Vec<ChipMemory>, ProveError> {
+        let log_size = Self::required_log_size(trace, view, PreprocessedTraces::MIN_LOG_SIZE)?;
+        let extensions_config = ExtensionsConfig::default();
+        let mut report = MemoryReport::default();
+
+        let preprocessed_trace = PreprocessedTraces::new(log_size);
+        let init_memory = Self::init_memory(view);
+        let program_trace_ref = Self::program_trace_ref(view, &init_memory);
         let mut prover_traces = TracesBuilder::new(log_size);
-        let program_trace_ref = ProgramTraceRef {
+        let program_traces = ProgramTracesBuilder::new(log_size, program_trace_ref);
+        let mut prover_side_note = SideNote::new(&program_traces, view);
+
+        // Observe the first row of each instruction kind (opcode, funct3 and funct7), and the first padding row.
+        let mut observed = std::collections::HashSet::new();
+        let program_steps = iter_program_steps(trace, prover_traces.num_rows());
+        for (row_idx, program_step) in program_steps.enumerate() {
+            let kind = program_step
+                .as_ref()
+                .map(|step| step.step.raw_instruction & 0xfe00_707f);
+            report.observe_row(observed.insert(kind));
+            C::fill_reported_main_trace(
+                &mut prover_traces,
+                row_idx,
+                &program_step,
+                &mut prover_side_note,
+                &extensions_config,
+                &mut report,
+            );
+        }
+        C::finalize_main_trace(
+            &mut prover_traces,
+            &mut prover_side_note,
+            &extensions_config,
+        );
+
+        // The values of lookup elements don't matter, only the number of columns is reported.
+        let mut lookup_elements = AllLookupElements::default();
+        C::draw_lookup_elements(
+            &mut lookup_elements,
+            &mut Blake2sChannel::default(),
+            &extensions_config,
+        );
+        C::fill_reported_interaction_trace(
+            &prover_traces.finalize(),
+            &preprocessed_trace,
+            &program_traces.finalize(),
+            &lookup_elements,
+            &mut report,
+        );
+
+        Ok(report.finish(log_size))
+    }
+
+    fn init_memory(view: &View) -> Vec<MemoryInitializationEntry> {
+        [
+            // preprocessed trace is sensitive to this ordering
+            view.get_ro_initial_memory(),
+            view.get_rw_initial_memory(),
+            view.get_public_input(),
+        ]
+        .concat()
+    }
+
+    fn program_trace_ref<'a>(
+        view: &'a View,
+        init_memory: &'a [MemoryInitializationEntry],
+    ) -> ProgramTraceRef<'a> {
+        ProgramTraceRef {
             program_memory: view.get_program_memory(),
-            init_memory: &[
-                // preprocessed trace is sensitive to this ordering
-                view.get_ro_initial_memory(),
-                view.get_rw_initial_memory(),
-                view.get_public_input(),
-            ]
-            .concat(),
+            init_memory,
             exit_code: view.get_exit_code(),
             public_output: view.get_public_output(),
-        };
+        }
+    }
+
+    /// Fills the main and program traces of the execution, pulling one step from `steps` per row and padding once
+    /// they run out, and returns them along with the side note.
+    ///
+    /// Fails with [`ProveError::ExecutionTooLong`] if `steps` yields more steps than there are rows.
+    fn fill_traces(
+        steps: impl Iterator<Item = ProgramStep>,
+        view: &View,
+        log_size: u32,
+        program_trace_ref: ProgramTraceRef,
+        extensions_config: &ExtensionsConfig,
+    ) -> Result<(TracesBuilder, ProgramTraces, SideNote), ProveError> {
+        let mut prover_traces = TracesBuilder::new(log_size);
         let program_traces = ProgramTracesBuilder::new(log_size, program_trace_ref);
         let mut prover_side_note = SideNote::new(&program_traces, view);
-        let program_steps = iter_program_steps(trace, prover_traces.num_rows());
-        for (row_idx, program_step) in program_steps.enumerate() {
+        let mut steps = steps.fuse();
+        for row_idx in 0..prover_traces.num_rows() {
+            let program_step = steps.next();
             C::fill_main_trace(
                 &mut prover_traces,
                 row_idx,
                 &program_step,
                 &mut prover_side_note,
-                &extensions_config,
+                extensions_config,
             );
         }
+        let remaining = steps.count();
+        if remaining > 0 {
+            return Err(ProveError::ExecutionTooLong {
+                needed_log_size: Self::max_log_size(&[prover_traces.num_rows() + remaining]),
+                max: log_size,
+            });
+        }
+        C::finalize_main_trace(&mut prover_traces, &mut prover_side_note, extensions_config);
 
-        let finalized_trace = prover_traces.finalize();
-        let finalized_program_trace = program_traces.finalize();
+        Ok((prover_traces, program_traces.finalize(), prover_side_note))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn prove_inner(
+        extensions: &[ExtensionComponent],
+        trace: &impl Trace,
+        view: &View,
+        min_log_size: u32,
+        preprocessed_trace: Option<&PreprocessedTraces>,
+        config: &ProverConfig,
+        checked: bool,
+        deadline: Option<Instant>,
+        monitor: Option<&dyn ProveMonitor>,
+    ) -> Result<Proof, ProveError> {
+        let log_size = Self::required_log_size(trace, view, min_log_size)?;
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            log_size,
+            num_steps = trace.get_num_steps(),
+            "proving execution"
+        );
+
+        Self::prove_steps(
+            extensions,
+            program_steps(trace),
+            view,
+            log_size,
+            min_log_size,
+            preprocessed_trace,
+            config,
+            checked,
+            deadline,
+            monitor,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn prove_steps(
+        extensions: &[ExtensionComponent],
+        steps: impl Iterator<Item = ProgramStep>,
+        view: &View,
+        log_size: u32,
+        min_log_size: u32,
+        preprocessed_trace: Option<&PreprocessedTraces>,
+        prover_config: &ProverConfig,
+        checked: bool,
+        deadline: Option<Instant>,
+        monitor: Option<&dyn ProveMonitor>,
+    ) -> Result<Proof, ProveError> {
+        phase_span!(INFO, "prove");
+        prover_config
+            .validate()
+            .map_err(ProveError::InvalidConfig)?;
+        let checked = checked || prover_config.debug_constraints;
+        let checkpoint = |phase| {
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                Err(ProveError::DeadlineExceeded { phase })
+            } else if monitor.is_some_and(|monitor| !monitor.enter_phase(phase, log_size)) {
+                Err(ProveError::Cancelled { phase })
+            } else {
+                Ok(())
+            }
+        };
+
+        let extensions_config = ExtensionsConfig::from(extensions);
+        let extensions_iter = BASE_EXTENSIONS.iter().chain(extensions);
+
+        checkpoint(ProvePhase::WitnessGeneration)?;
+
+        // Fill columns of the preprocessed trace, unless they were provided.
+        let generated_trace;
+        let preprocessed_trace = match preprocessed_trace {
+            Some(preprocessed_trace) => {
+                if log_size > preprocessed_trace.log_size() {
+                    return Err(ProveError::ExecutionTooLong {
+                        needed_log_size: log_size,
+                        max: preprocessed_trace.log_size(),
+                    });
+                }
+                preprocessed_trace
+            }
+            None => {
+                generated_trace = PreprocessedTraces::new(log_size);
+                &generated_trace
+            }
+        };
+
+        // Fill columns of the original trace.
+        let init_memory = Self::init_memory(view);
+        let program_trace_ref = Self::program_trace_ref(view, &init_memory);
+        let (finalized_trace, finalized_program_trace, mut prover_side_note) = {
+            phase_span!(DEBUG, "witness_generation");
+            let (prover_traces, finalized_program_trace, prover_side_note) =
+                Self::fill_traces(steps, view, log_size, program_trace_ref, &extensions_config)?;
+            (
+                prover_traces.finalize(),
+                finalized_program_trace,
+                prover_side_note,
+            )
+        };
+
+        if checked {
+            // The values of lookup elements don't matter, relations are not evaluated.
+            let mut lookup_elements = AllLookupElements::default();
+            C::draw_lookup_elements(
+                &mut lookup_elements,
+                &mut Blake2sChannel::default(),
+                &extensions_config,
+            );
+            if let Some(violation) = diagnostics::find_constraint_violation::<C>(
+                &finalized_trace,
+                preprocessed_trace,
+                &finalized_program_trace,
+                &lookup_elements,
+                &extensions_config,
+            ) {
+                return Err(ProveError::ConstraintViolation(violation));
+            }
+        }
 
         let all_log_sizes: Vec<u32> = std::iter::once(log_size)
             .chain(
                 extensions_iter
                     .clone()
-                    .map(|ext| ext.compute_log_size(&prover_side_note)),
+                    .map(|ext| ext.compute_padded_log_size(&prover_side_note, min_log_size)),
             )
             .collect();
 
-        let config = PcsConfig::default();
+        checkpoint(ProvePhase::Commitment)?;
+
+        let config = prover_config.pcs_config();
         // Precompute twiddles.
         let twiddles = SimdBackend::precompute_twiddles(
             CanonicCoset::new(
@@ -195,9 +950,11 @@ impl<C: MachineChip + Sync> Machine<C> {
 
         // Setup protocol.
         let prover_channel = &mut Blake2sChannel::default();
-        for byte in view.view_associated_data().unwrap_or_default() {
-            prover_channel.mix_u64(byte.into());
-        }
+        mix_statement(
+            prover_channel,
+            &view.view_associated_data().unwrap_or_default(),
+            view.view_statement_context(),
+        );
 
         let mut commitment_scheme =
             CommitmentSchemeProver::<SimdBackend, Blake2sMerkleChannel>::new(config, &twiddles);
@@ -225,7 +982,10 @@ impl<C: MachineChip + Sync> Machine<C> {
         for extension_trace in &extension_traces {
             tree_builder.extend_evals(extension_trace.to_circle_evaluation(PREPROCESSED_TRACE_IDX));
         }
-        tree_builder.commit(prover_channel);
+        {
+            phase_span!(DEBUG, "commit", tree = "preprocessed");
+            tree_builder.commit(prover_channel);
+        }
 
         let mut tree_builder = commitment_scheme.tree_builder();
         let _main_trace_location =
@@ -234,17 +994,29 @@ impl<C: MachineChip + Sync> Machine<C> {
         for extension_trace in &extension_traces {
             tree_builder.extend_evals(extension_trace.to_circle_evaluation(ORIGINAL_TRACE_IDX));
         }
-        tree_builder.commit(prover_channel);
+        {
+            phase_span!(DEBUG, "commit", tree = "main");
+            tree_builder.commit(prover_channel);
+        }
+        let checked_extension_traces = checked.then(|| extension_traces.clone());
+
+        checkpoint(ProvePhase::InteractionTrace)?;
 
         let mut lookup_elements = AllLookupElements::default();
         C::draw_lookup_elements(&mut lookup_elements, prover_channel, &extensions_config);
+        for ext in extensions_iter.clone() {
+            ext.draw_lookup_elements(&mut lookup_elements, prover_channel);
+        }
 
-        let (interaction_trace, claimed_sum) = generate_interaction_trace::<C>(
-            &finalized_trace,
-            &preprocessed_trace,
-            &finalized_program_trace,
-            &lookup_elements,
-        );
+        let (interaction_trace, claimed_sum) = {
+            phase_span!(DEBUG, "interaction_trace");
+            generate_interaction_trace::<C>(
+                &finalized_trace,
+                preprocessed_trace,
+                &finalized_program_trace,
+                &lookup_elements,
+            )
+        };
 
         let mut tree_builder = commitment_scheme.tree_builder();
         let _interaction_trace_location = tree_builder.extend_evals(interaction_trace);
@@ -259,7 +1031,42 @@ impl<C: MachineChip + Sync> Machine<C> {
             all_claimed_sum.push(claimed_sum);
             tree_builder.extend_evals(interaction_trace);
         }
-        tree_builder.commit(prover_channel);
+        // Check the relations before committing to the interaction trace, so that an unbalanced one fails fast.
+        let sum = all_claimed_sum.iter().sum::<SecureField>();
+        if let Some(extension_traces) = checked_extension_traces.filter(|_| !sum.is_zero()) {
+            // Redrawing the lookup elements of a balanced relation doesn't change the total sum.
+            let redraw_channel = &mut Blake2sChannel::default();
+            for (name, redrawn_elements) in lookup_elements.redraw_each(redraw_channel) {
+                let (_, redrawn_sum) = generate_interaction_trace::<C>(
+                    &finalized_trace,
+                    preprocessed_trace,
+                    &finalized_program_trace,
+                    &redrawn_elements,
+                );
+                let redrawn_sum = redrawn_sum
+                    + BASE_EXTENSIONS
+                        .iter()
+                        .chain(extensions)
+                        .zip(extension_traces.iter().cloned())
+                        .map(|(ext, extension_trace)| {
+                            ext.generate_interaction_trace(
+                                extension_trace,
+                                &prover_side_note,
+                                &redrawn_elements,
+                            )
+                            .1
+                        })
+                        .sum::<SecureField>();
+                if redrawn_sum != sum {
+                    return Err(ProveError::UnbalancedRelation { name, sum });
+                }
+            }
+        }
+
+        {
+            phase_span!(DEBUG, "commit", tree = "interaction");
+            tree_builder.commit(prover_channel);
+        }
 
         let tree_span_provider = &mut TraceLocationAllocator::default();
         let main_component = MachineComponent::new(
@@ -282,16 +1089,26 @@ impl<C: MachineChip + Sync> Machine<C> {
         let mut components_ref: Vec<&dyn ComponentProver<SimdBackend>> =
             ext_components.iter().map(|c| &**c).collect();
         components_ref.insert(0, &main_component);
-        let proof = prove::<SimdBackend, Blake2sMerkleChannel>(
-            &components_ref,
-            prover_channel,
-            commitment_scheme,
-        )?;
+        checkpoint(ProvePhase::StarkProof)?;
+
+        // Composition polynomial, out-of-domain sampling and FRI.
+        let proof = {
+            phase_span!(DEBUG, "stark_proof");
+            prove::<SimdBackend, Blake2sMerkleChannel>(
+                &components_ref,
+                prover_channel,
+                commitment_scheme,
+            )?
+        };
 
         Ok(Proof {
             stark_proof: proof,
             claimed_sum: all_claimed_sum,
             log_size: all_log_sizes,
+            config: ProverConfig {
+                debug_constraints: false,
+                ..*prover_config
+            },
         })
     }
 
@@ -314,45 +1131,96 @@ impl<C: MachineChip + Sync> Machine<C> {
         )
     }
 
-    pub fn verify_with_extensions(
+    pub fn verify_with_extensions(
+        extensions: &[ExtensionComponent],
+        proof: Proof,
+        program_info: &ProgramInfo,
+        ad: &[u8],
+        init_memory: &[MemoryInitializationEntry],
+        exit_code: &[PublicOutputEntry],
+        output_memory: &[PublicOutputEntry],
+    ) -> Result<(), VerificationError> {
+        Self::verify_with_deadline(
+            extensions,
+            proof,
+            program_info,
+            ad,
+            &[],
+            init_memory,
+            exit_code,
+            output_memory,
+            0,
+            None,
+        )
+        .map_err(|err| match err {
+            DeadlineVerificationError::Verification(err) => err,
+            DeadlineVerificationError::DeadlineExceeded => unreachable!("no deadline was set"),
+        })
+    }
+
+    /// Verify the proof, rejecting it unless it was made with at least `min_security_bits` bits of conjectured
+    /// security (see [`Proof::check_security`], zero for no minimum), and giving up with [`DeadlineVerificationError::DeadlineExceeded`]
+    /// if `deadline` passes.
+    ///
+    /// The proof is bound to `ad` and to `context`, see
+    /// [`View::set_statement_context`](nexus_vm::emulator::View::set_statement_context).
+    ///
+    /// The deadline is checked cooperatively between verification phases, so a phase that has already started
+    /// always runs to completion.
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_with_deadline(
         extensions: &[ExtensionComponent],
         proof: Proof,
         program_info: &ProgramInfo,
         ad: &[u8],
+        context: &[u8],
         init_memory: &[MemoryInitializationEntry],
         exit_code: &[PublicOutputEntry],
         output_memory: &[PublicOutputEntry],
-    ) -> Result<(), VerificationError> {
+        min_security_bits: u32,
+        deadline: Option<Instant>,
+    ) -> Result<(), DeadlineVerificationError> {
+        let checkpoint = || {
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                Err(DeadlineVerificationError::DeadlineExceeded)
+            } else {
+                Ok(())
+            }
+        };
+
+        proof.check_security(min_security_bits)?;
         let Proof {
             stark_proof: proof,
             claimed_sum,
             log_size: all_log_sizes,
+            config: prover_config,
         } = proof;
 
         if claimed_sum.len() != extensions.len() + BASE_EXTENSIONS.len() + 1 {
             return Err(VerificationError::InvalidStructure(
                 "claimed sum len mismatch".to_string(),
-            ));
+            )
+            .into());
         }
         if all_log_sizes.len() != extensions.len() + BASE_EXTENSIONS.len() + 1 {
-            return Err(VerificationError::InvalidStructure(
-                "log size len mismatch".to_string(),
-            ));
+            return Err(
+                VerificationError::InvalidStructure("log size len mismatch".to_string()).into(),
+            );
         }
         if claimed_sum.iter().sum::<SecureField>() != SecureField::zero() {
             return Err(VerificationError::InvalidStructure(
                 "claimed logup sum is not zero".to_string(),
-            ));
+            )
+            .into());
         }
+        checkpoint()?;
 
         let extensions_config = ExtensionsConfig::from(extensions);
         let extensions_iter = BASE_EXTENSIONS.iter().chain(extensions);
 
-        let config = PcsConfig::default();
+        let config = prover_config.pcs_config();
         let verifier_channel = &mut Blake2sChannel::default();
-        for &byte in ad {
-            verifier_channel.mix_u64(byte.into());
-        }
+        mix_statement(verifier_channel, ad, context);
         all_log_sizes.iter().for_each(|log_size| {
             verifier_channel.mix_u64(*log_size as u64);
         });
@@ -361,7 +1229,7 @@ impl<C: MachineChip + Sync> Machine<C> {
 
         // simulate the prover and compute expected commitment to preprocessed trace
         {
-            let config = PcsConfig::default();
+            let config = prover_config.pcs_config();
             let verifier_channel = &mut verifier_channel.clone();
             let twiddles = SimdBackend::precompute_twiddles(
                 CanonicCoset::new(
@@ -402,6 +1270,7 @@ impl<C: MachineChip + Sync> Machine<C> {
                 .clone()
                 .zip(all_log_sizes.get(1..).unwrap_or_default())
             {
+                checkpoint()?;
                 tree_builder
                     .extend_evals(ext.generate_preprocessed_trace(*log_size, program_trace_ref));
             }
@@ -411,9 +1280,10 @@ impl<C: MachineChip + Sync> Machine<C> {
             let preprocessed = proof.commitments[PREPROCESSED_TRACE_IDX];
             if preprocessed_expected != preprocessed {
                 return Err(VerificationError::InvalidStructure(format!("invalid commitment to preprocessed trace: \
-                                                                        expected {preprocessed_expected}, got {preprocessed}")));
+                                                                        expected {preprocessed_expected}, got {preprocessed}")).into());
             }
         }
+        checkpoint()?;
 
         // Retrieve the expected column sizes in each commitment interaction, from the AIR.
 
@@ -450,6 +1320,9 @@ impl<C: MachineChip + Sync> Machine<C> {
 
         let mut lookup_elements = AllLookupElements::default();
         C::draw_lookup_elements(&mut lookup_elements, verifier_channel, &extensions_config);
+        for ext in extensions_iter.clone() {
+            ext.draw_lookup_elements(&mut lookup_elements, verifier_channel);
+        }
 
         let tree_span_provider = &mut TraceLocationAllocator::default();
         let main_component = MachineComponent::new(
@@ -478,8 +1351,10 @@ impl<C: MachineChip + Sync> Machine<C> {
             &log_sizes[INTERACTION_TRACE_IDX],
             verifier_channel,
         );
+        checkpoint()?;
 
-        verify(&components_ref, verifier_channel, commitment_scheme, proof)
+        verify(&components_ref, verifier_channel, commitment_scheme, proof)?;
+        Ok(())
     }
 
     /// Computes minimum allowed log_size from a slice of lengths.
@@ -492,13 +1367,261 @@ impl<C: MachineChip + Sync> Machine<C> {
     }
 }
 
+/// Mix the statement the proof is bound to into the channel: the associated data, followed by the context.
+fn mix_statement(channel: &mut Blake2sChannel, ad: &[u8], context: &[u8]) {
+    for &byte in ad {
+        channel.mix_u64(byte.into());
+    }
+    // Bytes of the associated data never exceed `u8::MAX`, so the length separates them from the context.
+    if !context.is_empty() {
+        channel.mix_u64(u64::from(u8::MAX) + 1 + context.len() as u64);
+        for &byte in context {
+            channel.mix_u64(byte.into());
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        chips::Range256Chip,
+        components::CustomLookupElements,
+        extensions::CustomExtension,
+        trace::{eval::TraceEval, sidenote::CustomSideNote, ProgramStep},
+    };
     use nexus_vm::{
         riscv::{BasicBlock, BuiltinOpcode, Instruction, Opcode},
         trace::k_trace_direct,
     };
+    use stwo::{
+        core::{fields::qm31::SECURE_EXTENSION_DEGREE, ColumnVec},
+        prover::{
+            backend::simd::{column::BaseColumn, m31::LOG_N_LANES},
+            poly::{circle::CircleEvaluation, BitReversedOrder},
+        },
+    };
+    use stwo_constraint_framework::{EvalAtRow, FrameworkComponent, FrameworkEval, InfoEvaluator};
+
+    /// A chip that counts a range check of 0 that never takes place.
+    struct OvercountRange256;
+
+    impl MachineChip for OvercountRange256 {
+        fn fill_main_trace(
+            _traces: &mut TracesBuilder,
+            row_idx: usize,
+            _vm_step: &Option<ProgramStep>,
+            side_note: &mut SideNote,
+            _config: &ExtensionsConfig,
+        ) {
+            if row_idx == 0 {
+                side_note.range256.multiplicity[0] += 1;
+            }
+        }
+
+        fn add_constraints<E: EvalAtRow>(
+            _eval: &mut E,
+            _trace_eval: &TraceEval<E>,
+            _lookup_elements: &AllLookupElements,
+            _config: &ExtensionsConfig,
+        ) {
+        }
+    }
+
+    /// A chip whose second constraint requires the clock to be at most 5, failing on row 5.
+    struct ClkAtMostFive;
+
+    impl MachineChip for ClkAtMostFive {
+        fn fill_main_trace(
+            _traces: &mut TracesBuilder,
+            _row_idx: usize,
+            _vm_step: &Option<ProgramStep>,
+            _side_note: &mut SideNote,
+            _config: &ExtensionsConfig,
+        ) {
+        }
+
+        fn add_constraints<E: EvalAtRow>(
+            eval: &mut E,
+            trace_eval: &TraceEval<E>,
+            _lookup_elements: &AllLookupElements,
+            _config: &ExtensionsConfig,
+        ) {
+            let [is_first] = trace_eval.preprocessed_column_eval(PreprocessedColumn::IsFirst);
+            let [clk, _, _, _] = trace_eval.preprocessed_column_eval(PreprocessedColumn::Clk);
+
+            eval.add_constraint(is_first * (clk.clone() - E::F::from(BaseField::from(1))));
+            eval.add_constraint((1..=5u32).fold(E::F::from(BaseField::from(1)), |acc, i| {
+                acc * (clk.clone() - E::F::from(BaseField::from(i)))
+            }));
+        }
+    }
+
+    stwo_constraint_framework::relation!(StepsLookupElements, 1);
+
+    impl CustomLookupElements for StepsLookupElements {
+        fn draw(channel: &mut Blake2sChannel) -> Self {
+            Self::draw(channel)
+        }
+    }
+
+    /// The number of executed steps, counted by [`CountSteps`].
+    #[derive(Default)]
+    struct StepCount(u32);
+
+    impl CustomSideNote for StepCount {
+        fn merge(&mut self, other: Self) {
+            self.0 += other.0;
+        }
+    }
+
+    /// A chip counting executed steps for [`StepsExtension`].
+    struct CountSteps;
+
+    impl MachineChip for CountSteps {
+        fn fill_main_trace(
+            _traces: &mut TracesBuilder,
+            _row_idx: usize,
+            vm_step: &Option<ProgramStep>,
+            side_note: &mut SideNote,
+            config: &ExtensionsConfig,
+        ) {
+            if vm_step.is_some() && config.is_custom_enabled(StepsExtension::NAME) {
+                side_note.custom_mut::<StepCount>().0 += 1;
+            }
+        }
+
+        fn add_constraints<E: EvalAtRow>(
+            _eval: &mut E,
+            _trace_eval: &TraceEval<E>,
+            _lookup_elements: &AllLookupElements,
+            _config: &ExtensionsConfig,
+        ) {
+        }
+    }
+
+    /// An extension defined outside of the built-in ones, with a boolean column set on one row per executed step.
+    struct StepsExtension;
+
+    impl StepsExtension {
+        const NAME: &'static str = "steps";
+    }
+
+    struct StepsEval;
+
+    impl FrameworkEval for StepsEval {
+        fn log_size(&self) -> u32 {
+            LOG_N_LANES
+        }
+
+        fn max_constraint_log_degree_bound(&self) -> u32 {
+            LOG_N_LANES + 1
+        }
+
+        fn evaluate<E: EvalAtRow>(&self, mut eval: E) -> E {
+            let is_step = eval.next_trace_mask();
+            eval.add_constraint(is_step.clone() * (is_step - E::F::from(BaseField::from(1))));
+            eval
+        }
+    }
+
+    impl CustomExtension for StepsExtension {
+        fn name(&self) -> &'static str {
+            Self::NAME
+        }
+
+        fn draw_lookup_elements(
+            &self,
+            lookup_elements: &mut AllLookupElements,
+            channel: &mut Blake2sChannel,
+        ) {
+            lookup_elements.insert_custom(StepsLookupElements::draw(channel));
+        }
+
+        fn generate_preprocessed_trace(
+            &self,
+            _log_size: u32,
+            _program_trace_ref: ProgramTraceRef,
+        ) -> ColumnVec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>> {
+            vec![]
+        }
+
+        fn generate_component_trace(
+            &self,
+            log_size: u32,
+            _program_trace_ref: ProgramTraceRef,
+            side_note: &mut SideNote,
+        ) -> ComponentTrace {
+            let steps = side_note.custom::<StepCount>().map_or(0, |count| count.0);
+            assert_eq!(steps, 6);
+            let is_step = BaseColumn::from_iter(
+                (0..1u32 << log_size).map(|row| BaseField::from(u32::from(row < steps))),
+            );
+            ComponentTrace {
+                log_size,
+                preprocessed_trace: vec![],
+                original_trace: vec![is_step],
+            }
+        }
+
+        fn generate_interaction_trace(
+            &self,
+            _component_trace: ComponentTrace,
+            _side_note: &SideNote,
+            lookup_elements: &AllLookupElements,
+        ) -> (
+            ColumnVec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>>,
+            SecureField,
+        ) {
+            // The relation is drawn along with the built-in ones, although no value is looked up.
+            let _: &StepsLookupElements = lookup_elements.custom();
+            (vec![], SecureField::zero())
+        }
+
+        fn to_component_prover(
+            &self,
+            tree_span_provider: &mut TraceLocationAllocator,
+            _lookup_elements: &AllLookupElements,
+            _log_size: u32,
+            claimed_sum: SecureField,
+        ) -> Box<dyn ComponentProver<SimdBackend>> {
+            Box::new(FrameworkComponent::new(
+                tree_span_provider,
+                StepsEval,
+                claimed_sum,
+            ))
+        }
+
+        fn to_component(
+            &self,
+            tree_span_provider: &mut TraceLocationAllocator,
+            _lookup_elements: &AllLookupElements,
+            _log_size: u32,
+            claimed_sum: SecureField,
+        ) -> Box<dyn Component> {
+            Box::new(FrameworkComponent::new(
+                tree_span_provider,
+                StepsEval,
+                claimed_sum,
+            ))
+        }
+
+        fn compute_log_size(&self, _side_note: &SideNote) -> u32 {
+            LOG_N_LANES
+        }
+
+        fn trace_sizes(&self, log_size: u32) -> TreeVec<Vec<u32>> {
+            StepsEval
+                .evaluate(InfoEvaluator::empty())
+                .mask_offsets
+                .as_cols_ref()
+                .map_cols(|_| log_size)
+        }
+
+        fn preprocessed_trace_sizes(&self, _log_size: u32) -> Vec<u32> {
+            vec![]
+        }
+    }
 
     #[test]
     fn prove_verify() {
@@ -529,4 +1652,488 @@ mod tests {
         )
         .unwrap();
     }
+
+    #[test]
+    fn verify_binds_statement_context() {
+        let basic_block = vec![BasicBlock::new(vec![Instruction::new_ir(
+            Opcode::from(BuiltinOpcode::ADDI),
+            1,
+            0,
+            1,
+        )])];
+        let (mut view, program_trace) =
+            k_trace_direct(&basic_block, 1).expect("error generating trace");
+        view.set_statement_context(b"context".to_vec());
+
+        let proof = Machine::<BaseComponent>::prove(&program_trace, &view).unwrap();
+        crate::verify(proof.clone(), &view).unwrap();
+
+        for context in [&b""[..], b"other"] {
+            view.set_statement_context(context.to_vec());
+            assert!(crate::verify(proof.clone(), &view).is_err());
+        }
+    }
+
+    #[test]
+    fn prove_execution_too_long() {
+        let basic_block = vec![BasicBlock::new(vec![Instruction::new_ir(
+            Opcode::from(BuiltinOpcode::ADDI),
+            1,
+            0,
+            1,
+        )])];
+        let (view, program_trace) =
+            k_trace_direct(&basic_block, 1).expect("error generating trace");
+
+        let max = PreprocessedTraces::MAX_LOG_SIZE;
+        let err =
+            Machine::<BaseComponent>::prove_with_min_log_size(&[], &program_trace, &view, max + 1)
+                .unwrap_err();
+
+        assert!(matches!(
+            err,
+            ProveError::ExecutionTooLong { needed_log_size, max: m } if needed_log_size == max + 1 && m == max
+        ));
+        assert!(err.to_string().contains("segments"));
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn prove_emits_phase_spans() {
+        use std::sync::{Arc, Mutex};
+        use tracing_subscriber::{layer::Context, prelude::*, Layer, Registry};
+
+        /// Records the names of created spans.
+        #[derive(Clone, Default)]
+        struct SpanNames(Arc<Mutex<Vec<&'static str>>>);
+
+        impl<S: tracing::Subscriber> Layer<S> for SpanNames {
+            fn on_new_span(
+                &self,
+                attrs: &tracing::span::Attributes<'_>,
+                _id: &tracing::span::Id,
+                _ctx: Context<'_, S>,
+            ) {
+                self.0.lock().unwrap().push(attrs.metadata().name());
+            }
+        }
+
+        let basic_block = vec![BasicBlock::new(vec![
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 1, 0, 1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADD), 2, 1, 0),
+        ])];
+        let (view, program_trace) =
+            k_trace_direct(&basic_block, 1).expect("error generating trace");
+
+        let span_names = SpanNames::default();
+        let subscriber = Registry::default().with(span_names.clone());
+        tracing::subscriber::with_default(subscriber, || {
+            Machine::<BaseComponent>::prove(&program_trace, &view).unwrap()
+        });
+
+        let span_names = span_names.0.lock().unwrap();
+        for name in [
+            "prove",
+            "witness_generation",
+            "commit",
+            "interaction_trace",
+            "stark_proof",
+        ] {
+            assert!(span_names.contains(&name), "span {name} was not emitted");
+        }
+        assert_eq!(span_names.iter().filter(|&&n| n == "commit").count(), 3);
+    }
+
+    #[test]
+    fn verify_rejects_weak_config() {
+        let basic_block = vec![BasicBlock::new(vec![
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 1, 0, 1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADD), 2, 1, 0),
+        ])];
+        let (view, program_trace) =
+            k_trace_direct(&basic_block, 1).expect("error generating trace");
+
+        // An honest proof under a weak configuration.
+        let weak = ProverConfig {
+            log_blowup_factor: 1,
+            n_queries: 3,
+            pow_bits: 5,
+            debug_constraints: false,
+        };
+        let proof = Machine::<BaseComponent>::prove_with_config(
+            &[],
+            &program_trace,
+            &view,
+            PreprocessedTraces::MIN_LOG_SIZE,
+            &weak,
+            None,
+        )
+        .unwrap();
+        crate::verify(proof.clone(), &view).unwrap();
+        assert!(crate::verify_with_min_security(
+            proof.clone(),
+            &view,
+            ProverConfig::RECOMMENDED_MIN_SECURITY_BITS
+        )
+        .is_err());
+        crate::verify_with_min_security(proof, &view, weak.security_bits()).unwrap();
+
+        // A proof whose declared configuration was rewritten, to a weaker or an invalid one.
+        let proof = Machine::<BaseComponent>::prove(&program_trace, &view).unwrap();
+        crate::verify(proof.clone(), &view).unwrap();
+        for config in [
+            ProverConfig {
+                n_queries: 1,
+                pow_bits: 0,
+                ..proof.config
+            },
+            ProverConfig {
+                log_blowup_factor: 0,
+                ..proof.config
+            },
+            ProverConfig {
+                log_blowup_factor: 17,
+                ..proof.config
+            },
+            ProverConfig {
+                n_queries: usize::MAX,
+                pow_bits: u32::MAX,
+                ..proof.config
+            },
+        ] {
+            let forged = Proof {
+                config,
+                ..proof.clone()
+            };
+            assert!(
+                crate::verify_with_min_security(forged, &view, proof.config.security_bits())
+                    .is_err()
+            );
+        }
+
+        assert!(matches!(
+            Machine::<BaseComponent>::prove_with_config(
+                &[],
+                &program_trace,
+                &view,
+                PreprocessedTraces::MIN_LOG_SIZE,
+                &ProverConfig {
+                    log_blowup_factor: 0,
+                    ..weak
+                },
+                None,
+            ),
+            Err(ProveError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn prove_with_too_small_preprocessed_trace() {
+        let basic_block = vec![BasicBlock::new(
+            (0..300)
+                .map(|_| Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 1, 1, 1))
+                .collect(),
+        )];
+        let (view, program_trace) =
+            k_trace_direct(&basic_block, 1).expect("error generating trace");
+
+        let preprocessed_trace = PreprocessedTraces::new(PreprocessedTraces::MIN_LOG_SIZE);
+        let err = Machine::<BaseComponent>::prove_with_preprocessed_trace(
+            &[],
+            &program_trace,
+            &view,
+            &preprocessed_trace,
+            &ProverConfig::default(),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ProveError::ExecutionTooLong {
+                needed_log_size,
+                max: PreprocessedTraces::MIN_LOG_SIZE,
+            } if needed_log_size == PreprocessedTraces::MIN_LOG_SIZE + 1
+        ));
+    }
+
+    #[test]
+    fn prove_checked_unbalanced_relation() {
+        let basic_block = vec![BasicBlock::new(vec![
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 1, 0, 1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADD), 2, 1, 0),
+        ])];
+        let (view, program_trace) =
+            k_trace_direct(&basic_block, 1).expect("error generating trace");
+
+        Machine::<BaseComponent>::prove_checked(&program_trace, &view).unwrap();
+
+        let err =
+            Machine::<(BaseComponent, OvercountRange256)>::prove_checked(&program_trace, &view)
+                .unwrap_err();
+        assert!(matches!(
+            err,
+            ProveError::UnbalancedRelation { name: "Range256LookupElements", sum } if !sum.is_zero()
+        ));
+    }
+
+    #[test]
+    fn find_constraint_violation_at_row() {
+        let basic_block = vec![BasicBlock::new(vec![
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 1, 0, 1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADD), 2, 1, 0),
+        ])];
+        let (view, program_trace) =
+            k_trace_direct(&basic_block, 1).expect("error generating trace");
+
+        let violation =
+            Machine::<BaseComponent>::find_constraint_violation(&program_trace, &view).unwrap();
+        assert_eq!(violation, None);
+
+        let violation = Machine::<(BaseComponent, ClkAtMostFive)>::find_constraint_violation(
+            &program_trace,
+            &view,
+        )
+        .unwrap();
+        assert_eq!(
+            violation,
+            Some(ConstraintViolation {
+                chip: std::any::type_name::<ClkAtMostFive>(),
+                constraint_index: 1,
+                row: 5,
+                // The clock is 6 on row 5.
+                value: SecureField::from(BaseField::from(120)),
+                // Only the low limb of the clock is constrained.
+                cells: vec![diagnostics::TraceCell {
+                    interaction: PREPROCESSED_TRACE_IDX,
+                    column: PreprocessedColumn::Clk.offset(),
+                    row: 5,
+                    value: BaseField::from(6),
+                }],
+            })
+        );
+
+        let err = Machine::<(BaseComponent, ClkAtMostFive)>::prove_checked(&program_trace, &view)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ProveError::ConstraintViolation(ConstraintViolation { row: 5, .. })
+        ));
+
+        // The same checks run under a config asking for them.
+        let err = Machine::<(BaseComponent, ClkAtMostFive)>::prove_with_config(
+            &[],
+            &program_trace,
+            &view,
+            PreprocessedTraces::MIN_LOG_SIZE,
+            &ProverConfig {
+                debug_constraints: true,
+                ..ProverConfig::default()
+            },
+            None,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ProveError::ConstraintViolation(ConstraintViolation { row: 5, ref cells, .. })
+                if cells.len() == 1
+        ));
+    }
+
+    #[test]
+    fn prove_verify_custom_extension() {
+        let basic_block = vec![BasicBlock::new(vec![
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 1, 0, 1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADD), 2, 1, 0),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADD), 3, 2, 1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADD), 4, 3, 2),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADD), 5, 4, 3),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADD), 6, 5, 4),
+        ])];
+        let (view, program_trace) =
+            k_trace_direct(&basic_block, 1).expect("error generating trace");
+
+        let extensions = [ExtensionComponent::custom(StepsExtension)];
+        let proof = Machine::<(BaseComponent, CountSteps)>::prove_with_extensions(
+            &extensions,
+            &program_trace,
+            &view,
+        )
+        .unwrap();
+        assert_eq!(proof.claimed_sum.len(), BASE_EXTENSIONS.len() + 2);
+
+        Machine::<(BaseComponent, CountSteps)>::verify_with_extensions(
+            &extensions,
+            proof,
+            view.get_program_memory(),
+            &[],
+            &[
+                view.get_public_input(),
+                view.get_ro_initial_memory(),
+                view.get_rw_initial_memory(),
+            ]
+            .concat(),
+            view.get_exit_code(),
+            view.get_public_output(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn prove_with_memory_report() {
+        let basic_block = vec![BasicBlock::new(vec![
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 1, 0, 1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADD), 2, 1, 0),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SLT), 3, 2, 1),
+        ])];
+        let (view, program_trace) =
+            k_trace_direct(&basic_block, 1).expect("error generating trace");
+
+        let (proof, report) =
+            Machine::<BaseComponent>::prove_with_memory_report(&program_trace, &view).unwrap();
+
+        // Range256Chip checks dozens of columns, sharing an interaction column between every three limbs.
+        let range256 = report
+            .iter()
+            .find(|chip| chip.name == std::any::type_name::<Range256Chip>())
+            .expect("Range256Chip must be reported");
+        let max_interaction_bytes = report.iter().map(|chip| chip.interaction_bytes).max();
+        assert_eq!(Some(range256.interaction_bytes), max_interaction_bytes);
+
+        // Every main trace column is accounted for.
+        let log_size = *proof.log_size.first().unwrap();
+        let main_bytes: usize = report.iter().map(|chip| chip.main_bytes).sum();
+        assert_eq!(
+            main_bytes,
+            Column::COLUMNS_NUM * (1 << log_size) * std::mem::size_of::<BaseField>()
+        );
+
+        // Sizes are counted in whole columns, and interaction columns hold secure field values.
+        let column_bytes = (1 << log_size) * std::mem::size_of::<BaseField>();
+        for chip in &report {
+            assert_eq!(chip.main_bytes % column_bytes, 0, "{}", chip.name);
+            assert_eq!(
+                chip.interaction_bytes % (SECURE_EXTENSION_DEGREE * column_bytes),
+                0,
+                "{}",
+                chip.name
+            );
+        }
+        assert!(range256.interaction_bytes > 0);
+    }
+
+    /// A program exercising most instruction chips, used to tamper with the columns they write.
+    fn tamper_program() -> Vec<BasicBlock> {
+        vec![BasicBlock::new(vec![
+            // x2 = 0x81008, a usable address
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 1, 0, 1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SLLI), 1, 1, 19),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADD), 2, 1, 2),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 3, 0, 128),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SW), 2, 3, 20),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::LW), 6, 2, 20),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SB), 2, 3, 0),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::LBU), 6, 2, 0),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SUB), 4, 3, 1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SLTU), 5, 1, 3),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SLT), 5, 4, 3),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::AND), 5, 3, 4),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::OR), 5, 3, 4),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::XOR), 5, 3, 4),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SRL), 5, 4, 3),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SRA), 5, 4, 3),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::LUI), 7, 0, 0x1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::AUIPC), 7, 0, 0x1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::MUL), 8, 3, 3),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::DIVU), 8, 1, 3),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::REMU), 8, 4, 3),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::BNE), 3, 0, 8),
+            Instruction::unimpl(),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::BEQ), 0, 0, 8),
+            Instruction::unimpl(),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::JAL), 9, 0, 8),
+            Instruction::unimpl(),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 10, 9, 1),
+        ])]
+    }
+
+    #[test]
+    fn tamper_written_columns() {
+        let (view, program_trace) =
+            k_trace_direct(&tamper_program(), 1).expect("error generating trace");
+
+        let log_size = Machine::<BaseComponent>::required_log_size(
+            &program_trace,
+            &view,
+            PreprocessedTraces::MIN_LOG_SIZE,
+        )
+        .unwrap();
+        let config = ExtensionsConfig::default();
+        let preprocessed_trace = PreprocessedTraces::new(log_size);
+        let init_memory = Machine::<BaseComponent>::init_memory(&view);
+        let program_trace_ref = Machine::<BaseComponent>::program_trace_ref(&view, &init_memory);
+        let (traces, program_traces, _) = Machine::<BaseComponent>::fill_traces(
+            program_steps(&program_trace),
+            &view,
+            log_size,
+            program_trace_ref,
+            &config,
+        );
+        let mut lookup_elements = AllLookupElements::default();
+        BaseComponent::draw_lookup_elements(
+            &mut lookup_elements,
+            &mut Blake2sChannel::default(),
+            &config,
+        );
+
+        // Returns the claimed sum of the main component, or `None` if a constraint doesn't hold. The sums of
+        // extensions only depend on the side note, so a proof is rejected unless this matches the untampered sum.
+        let check = |traces: TracesBuilder| {
+            let finalized_trace = traces.finalize();
+            let violation = diagnostics::find_constraint_violation::<BaseComponent>(
+                &finalized_trace,
+                &preprocessed_trace,
+                &program_traces,
+                &lookup_elements,
+                &config,
+            );
+            violation.is_none().then(|| {
+                generate_interaction_trace::<BaseComponent>(
+                    &finalized_trace,
+                    &preprocessed_trace,
+                    &program_traces,
+                    &lookup_elements,
+                )
+                .1
+            })
+        };
+        let claimed_sum = check(traces.clone()).expect("untampered trace must satisfy constraints");
+
+        let mut undetected = Vec::new();
+        for &col in Column::ALL_VARIANTS {
+            // Flip the lowest bit of the first nonzero value written to the column, skipping columns this program
+            // leaves empty.
+            let Some((row, limb)) = (0..traces.num_rows())
+                .flat_map(|row| (0..col.size()).map(move |limb| (row, limb)))
+                .find(|&(row, limb)| !traces.cols[col.offset() + limb][row].is_zero())
+            else {
+                continue;
+            };
+            let mut tampered = traces.clone();
+            let value = &mut tampered.cols[col.offset() + limb][row];
+            *value = BaseField::from(value.0 ^ 1);
+
+            // Trace generation rejecting the value is as good as a failing proof.
+            let detected = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                check(tampered) != Some(claimed_sum)
+            }))
+            .unwrap_or(true);
+            if !detected {
+                undetected.push((col, row));
+            }
+        }
+
+        assert!(
+            undetected.is_empty(),
+            "tampering with these columns went undetected, potential soundness gaps: {undetected:?}"
+        );
+    }
 }
@@ -57,6 +57,12 @@ impl<E: EvalAtRow> TraceEval<E> {
         array::from_fn(|i| self.evals[offset + i][0].clone())
     }
 
+    /// Returns the evaluation of `limb` of `col` at the current row.
+    pub(crate) fn limb_eval(&self, col: Column, limb: usize) -> E::F {
+        assert!(limb < col.size(), "limb out of range");
+        self.evals[col.offset() + limb][0].clone()
+    }
+
     #[doc(hidden)]
     pub fn column_eval_next_row<const N: usize>(&self, col: Column) -> [E::F; N] {
         assert_eq!(col.size(), N, "column size mismatch");
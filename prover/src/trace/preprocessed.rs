@@ -119,6 +119,10 @@ pub struct PreprocessedTraces {
 impl PreprocessedTraces {
     pub const MIN_LOG_SIZE: u32 = PreprocessedBuilder::MIN_LOG_SIZE;
 
+    /// Max supported log size of the trace. Executions that need a larger trace should be split into segments that
+    /// are proven separately.
+    pub const MAX_LOG_SIZE: u32 = 26;
+
     pub fn new(log_size: u32) -> Self {
         PreprocessedBuilder::new(log_size).finalize()
     }
@@ -26,6 +26,11 @@ pub type BoolWord = [bool; WORD_SIZE];
 pub type WordWithEffectiveBits = (Word, usize);
 
 impl ProgramStep {
+    /// Creates the step of an execution, from the registers before it and the executed instruction.
+    pub fn new(regs: RegisterFile, step: Step) -> Self {
+        Self { regs, step }
+    }
+
     /// Returns the value of the first operand (rd or rs1) as bytes.
     /// Always a register value in range u32.
     pub(crate) fn get_value_a(&self) -> Word {
@@ -145,15 +150,19 @@ pub fn iter_program_steps<TR: Trace>(
     num_rows: usize,
 ) -> impl Iterator<Item = Option<ProgramStep>> + '_ {
     assert!(trace.get_num_steps() <= num_rows, "Too many ProgramSteps");
-    trace
-        .get_blocks_iter()
-        .map(|block| {
-            assert_eq!(block.steps.len(), 1, "Only k = 1 traces are supported.");
-            Some(ProgramStep {
-                step: block.steps[0].clone(),
-                regs: block.regs,
-            })
-        })
+    program_steps(trace)
+        .map(Some)
         .chain(std::iter::repeat(None))
         .take(num_rows)
 }
+
+/// Iterates over the program steps in `trace`, without padding
+pub fn program_steps<TR: Trace>(trace: &TR) -> impl Iterator<Item = ProgramStep> + '_ {
+    trace.get_blocks_iter().map(|block| {
+        assert_eq!(block.steps.len(), 1, "Only k = 1 traces are supported.");
+        ProgramStep {
+            step: block.steps[0].clone(),
+            regs: block.regs,
+        }
+    })
+}
@@ -24,6 +24,27 @@ pub struct KeccakSideNote {
     pub(crate) round_lookups: Vec<RoundLookups>,
 }
 
+impl KeccakSideNote {
+    /// Appends the inputs of `other`, and adds its multiplicities, see [`SideNote::merge`](super::SideNote::merge).
+    pub(crate) fn merge(&mut self, other: Self) {
+        self.inputs.extend(other.inputs);
+        self.timestamps.extend(other.timestamps);
+        self.addresses.extend(other.addresses);
+        for (accum, other) in [
+            (&mut self.xor_accum, other.xor_accum),
+            (&mut self.bit_not_and_accum, other.bit_not_and_accum),
+        ] {
+            match (accum.as_mut(), other) {
+                (Some(accum), Some(other)) => accum.merge(&other),
+                (None, other) => *accum = other,
+                (Some(_), None) => {}
+            }
+        }
+        self.bit_rotate_accum.merge(other.bit_rotate_accum);
+        self.round_lookups.extend(other.round_lookups);
+    }
+}
+
 #[derive(Default)]
 pub struct RoundLookups {
     pub(crate) bitwise_lookups: Vec<([usize; 3], BitOp)>,
@@ -1,6 +1,9 @@
 // This file defines the side note structures for main trace filling
 
-use std::collections::BTreeMap;
+use std::{
+    any::{Any, TypeId},
+    collections::{BTreeMap, HashMap},
+};
 
 use nexus_vm::{
     emulator::{InternalView, MemoryInitializationEntry, PublicOutputEntry, View},
@@ -11,6 +14,7 @@ use super::{program_trace::ProgramTracesBuilder, regs::RegisterMemCheckSideNote}
 
 pub(crate) mod keccak;
 
+#[derive(Clone)]
 pub struct ProgramMemCheckSideNote {
     /// For each Pc, the number of accesses to that Pc so far (None if never)
     pub(crate) last_access_counter: BTreeMap<u32, u32>,
@@ -21,7 +25,7 @@ pub struct ProgramMemCheckSideNote {
 }
 
 /// Side note for committing to the final RW memory content and for computing the final read digest
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct ReadWriteMemCheckSideNote {
     /// u32 is the access counter, u8 is the value of the byte
     pub(crate) last_access: BTreeMap<u32, (u32, u8)>,
@@ -83,6 +87,14 @@ pub struct RangeCheckSideNote<const LEN: usize> {
     pub(crate) multiplicity: [u32; LEN],
 }
 
+impl<const LEN: usize> RangeCheckSideNote<LEN> {
+    fn merge(&mut self, other: &Self) {
+        for (multiplicity, other) in self.multiplicity.iter_mut().zip(other.multiplicity) {
+            *multiplicity += other;
+        }
+    }
+}
+
 impl<const LEN: usize> Default for RangeCheckSideNote<LEN> {
     fn default() -> Self {
         Self {
@@ -99,6 +111,58 @@ pub struct BitOpSideNote {
     pub(crate) multiplicity_xor: BTreeMap<u8, u32>,
 }
 
+impl BitOpSideNote {
+    fn merge(&mut self, other: Self) {
+        for (multiplicity, other) in [
+            (&mut self.multiplicity_and, other.multiplicity_and),
+            (&mut self.multiplicity_or, other.multiplicity_or),
+            (&mut self.multiplicity_xor, other.multiplicity_xor),
+        ] {
+            for (key, count) in other {
+                *multiplicity.entry(key).or_default() += count;
+            }
+        }
+    }
+}
+
+/// Side note data of chips and extensions defined outside of this crate, see [`SideNote::custom_mut`].
+pub trait CustomSideNote: Default + Send + Sync + 'static {
+    /// Merges the data of a side note [forked](SideNote::fork) from this one, as [`SideNote::merge`] does.
+    fn merge(&mut self, other: Self);
+}
+
+trait ErasedSideNote: Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+
+    fn merge_boxed(&mut self, other: Box<dyn ErasedSideNote>);
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+impl<T: CustomSideNote> ErasedSideNote for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn merge_boxed(&mut self, other: Box<dyn ErasedSideNote>) {
+        let other = other
+            .into_any()
+            .downcast::<T>()
+            .expect("side notes of the same type");
+        CustomSideNote::merge(self, *other);
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
 pub struct SideNote {
     pub program_mem_check: ProgramMemCheckSideNote,
     pub(crate) register_mem_check: RegisterMemCheckSideNote,
@@ -110,6 +174,7 @@ pub struct SideNote {
     pub(crate) range128: RangeCheckSideNote<{ 1 << 7 }>,
     pub(crate) range256: RangeCheckSideNote<{ 1 << 8 }>,
     pub(crate) keccak: keccak::KeccakSideNote,
+    custom: HashMap<TypeId, Box<dyn ErasedSideNote>>,
 }
 
 impl SideNote {
@@ -139,8 +204,87 @@ impl SideNote {
             range128: RangeCheckSideNote::<{ 1 << 7 }>::default(),
             range256: RangeCheckSideNote::<{ 1 << 8 }>::default(),
             keccak: keccak::KeccakSideNote::default(),
+            custom: HashMap::new(),
+        }
+    }
+}
+
+impl SideNote {
+    /// Returns a side note for filling the rows following those filled so far, independently of this one.
+    ///
+    /// The returned side note carries the current memory checking state, which depends on every preceding row, while
+    /// multiplicities, keccak inputs and [custom](Self::custom_mut) data start empty. It can then be [merged](Self::merge) back once its rows are
+    /// filled, so that row ranges can be filled in parallel given the memory checking state at the start of each.
+    pub fn fork(&self) -> Self {
+        Self {
+            program_mem_check: self.program_mem_check.clone(),
+            register_mem_check: self.register_mem_check,
+            rw_mem_check: self.rw_mem_check.clone(),
+            bit_op: BitOpSideNote::default(),
+            range8: RangeCheckSideNote::default(),
+            range16: RangeCheckSideNote::default(),
+            range32: RangeCheckSideNote::default(),
+            range128: RangeCheckSideNote::default(),
+            range256: RangeCheckSideNote::default(),
+            keccak: keccak::KeccakSideNote::default(),
+            custom: HashMap::new(),
         }
     }
+
+    /// Merges a side note [forked](Self::fork) from this one, after it filled the rows following those filled by
+    /// this one.
+    ///
+    /// Multiplicities are added and keccak inputs appended, while the memory checking state of `other` replaces this
+    /// one, as it continues it. The result is the side note of filling all rows serially, which the main trace is then
+    /// [finalized](crate::traits::MachineChip::finalize_main_trace) with.
+    pub fn merge(&mut self, other: SideNote) {
+        self.program_mem_check = other.program_mem_check;
+        self.register_mem_check = other.register_mem_check;
+        self.rw_mem_check = other.rw_mem_check;
+        self.bit_op.merge(other.bit_op);
+        self.range8.merge(&other.range8);
+        self.range16.merge(&other.range16);
+        self.range32.merge(&other.range32);
+        self.range128.merge(&other.range128);
+        self.range256.merge(&other.range256);
+        self.keccak.merge(other.keccak);
+        for (type_id, data) in other.custom {
+            match self.custom.get_mut(&type_id) {
+                Some(custom) => custom.merge_boxed(data),
+                None => {
+                    self.custom.insert(type_id, data);
+                }
+            }
+        }
+    }
+
+    /// Returns the data of a chip or extension defined outside of this crate, inserting its default on first access.
+    ///
+    /// A chip emitting lookups to a custom extension records here what the extension needs to generate its trace,
+    /// much like built-in chips count multiplicities of range checks.
+    pub fn custom_mut<T: CustomSideNote>(&mut self) -> &mut T {
+        self.custom
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(T::default()))
+            .as_any_mut()
+            .downcast_mut()
+            .expect("side note is keyed by its type")
+    }
+
+    /// Returns the data of a chip or extension defined outside of this crate, if any was recorded.
+    pub fn custom<T: CustomSideNote>(&self) -> Option<&T> {
+        self.custom
+            .get(&TypeId::of::<T>())
+            .and_then(|data| data.as_any().downcast_ref())
+    }
+
+    /// Count an occurrence of `byte` in the shared 0..=255 range check table.
+    ///
+    /// Chips that contribute a range-check fraction with [`Range256Chip::add_checked_value`](crate::chips::Range256Chip::add_checked_value)
+    /// must call this once for every checked value, otherwise the logup sum won't match.
+    pub fn range256_add(&mut self, byte: u8) {
+        self.range256.multiplicity[byte as usize] += 1;
+    }
 }
 
 pub(crate) trait RangeCheckSideNoteGetter<const LEN: usize> {
@@ -170,3 +314,110 @@ impl RangeCheckSideNoteGetter<{ 1 << 8 }> for SideNote {
         &self.range256
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        extensions::ExtensionsConfig,
+        machine::BaseComponent,
+        trace::{
+            program::iter_program_steps, program_trace::ProgramTraceRef, PreprocessedTraces,
+            TracesBuilder,
+        },
+        traits::MachineChip,
+    };
+    use nexus_vm::{
+        riscv::{BasicBlock, BuiltinOpcode, Instruction, Opcode},
+        trace::k_trace_direct,
+    };
+
+    #[test]
+    fn merge_matches_serial_fill() {
+        let basic_block = vec![BasicBlock::new(vec![
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 1, 0, 200),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 2, 0, 1000),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::XOR), 3, 1, 2),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::AND), 4, 3, 1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SLT), 5, 4, 2),
+            // x8 = 0x80000, an address in RAM
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 8, 0, 1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SLLI), 8, 8, 19),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SW), 8, 3, 8),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::LW), 6, 8, 8),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADD), 7, 6, 5),
+        ])];
+        let (view, program_trace) =
+            k_trace_direct(&basic_block, 1).expect("error generating trace");
+
+        let log_size = PreprocessedTraces::MIN_LOG_SIZE;
+        let config = ExtensionsConfig::default();
+        let init_memory = [
+            view.get_ro_initial_memory(),
+            view.get_rw_initial_memory(),
+            view.get_public_input(),
+        ]
+        .concat();
+        let program_traces = ProgramTracesBuilder::new(
+            log_size,
+            ProgramTraceRef {
+                program_memory: view.get_program_memory(),
+                init_memory: &init_memory,
+                exit_code: view.get_exit_code(),
+                public_output: view.get_public_output(),
+            },
+        );
+
+        // Fills every row, switching to a forked side note from row `split`, and merges it back at the end.
+        let fill = |split: usize| {
+            let mut traces = TracesBuilder::new(log_size);
+            let mut side_note = SideNote::new(&program_traces, &view);
+            let mut forked = None;
+            let program_steps = iter_program_steps(&program_trace, traces.num_rows());
+            for (row_idx, program_step) in program_steps.enumerate() {
+                if row_idx == split {
+                    forked = Some(side_note.fork());
+                }
+                BaseComponent::fill_main_trace(
+                    &mut traces,
+                    row_idx,
+                    &program_step,
+                    forked.as_mut().unwrap_or(&mut side_note),
+                    &config,
+                );
+            }
+            if let Some(forked) = forked {
+                side_note.merge(forked);
+            }
+            BaseComponent::finalize_main_trace(&mut traces, &mut side_note, &config);
+            side_note
+        };
+
+        let serial = fill(usize::MAX);
+        // Split within the program, so that both side notes record multiplicities and memory accesses.
+        let merged = fill(6);
+
+        assert_eq!(merged.range8.multiplicity, serial.range8.multiplicity);
+        assert_eq!(merged.range16.multiplicity, serial.range16.multiplicity);
+        assert_eq!(merged.range32.multiplicity, serial.range32.multiplicity);
+        assert_eq!(merged.range128.multiplicity, serial.range128.multiplicity);
+        assert_eq!(merged.range256.multiplicity, serial.range256.multiplicity);
+        assert_eq!(
+            merged.bit_op.multiplicity_and,
+            serial.bit_op.multiplicity_and
+        );
+        assert_eq!(
+            merged.bit_op.multiplicity_xor,
+            serial.bit_op.multiplicity_xor
+        );
+        assert_eq!(merged.register_mem_check, serial.register_mem_check);
+        assert_eq!(
+            merged.rw_mem_check.last_access,
+            serial.rw_mem_check.last_access
+        );
+        assert_eq!(
+            merged.program_mem_check.last_access_counter,
+            serial.program_mem_check.last_access_counter
+        );
+    }
+}
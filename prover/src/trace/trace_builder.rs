@@ -153,6 +153,29 @@ impl FinalizedTraces {
         std::array::from_fn(|i| &self.cols[col.offset() + i])
     }
 
+    /// Returns the raw column holding `limb` of `col`.
+    pub(crate) fn get_limb(&self, col: Column, limb: usize) -> &BaseColumn {
+        assert!(limb < col.size(), "limb out of range");
+        &self.cols[col.offset() + limb]
+    }
+
+    /// Returns the columns that are zero in every row, which usually means a chip forgot to fill them.
+    ///
+    /// This is a heuristic for debugging: columns for which [`Column::may_be_all_zero`] holds are
+    /// not reported, but other columns can still be legitimately zero for some programs.
+    pub fn unfilled_columns(&self) -> Vec<Column> {
+        Column::ALL_VARIANTS
+            .iter()
+            .copied()
+            .filter(|col| !col.may_be_all_zero())
+            .filter(|col| {
+                self.cols[col.offset()..col.offset() + col.size()]
+                    .iter()
+                    .all(|limb| limb.as_slice().iter().all(BaseField::is_zero))
+            })
+            .collect()
+    }
+
     pub fn into_circle_evaluation(
         self,
     ) -> ColumnVec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>> {
@@ -163,3 +186,29 @@ impl FinalizedTraces {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unfilled_columns() {
+        let log_size = LOG_N_LANES;
+        let mut traces = TracesBuilder::new(log_size);
+        for col in traces.cols.iter_mut() {
+            col.fill(BaseField::from(1));
+        }
+
+        // Leave one limb of a multi-limb column set, so the column counts as filled.
+        for row in 0..traces.num_rows() {
+            *traces.column_mut::<WORD_SIZE>(row, Column::ValueA)[0] = BaseField::zero();
+        }
+        for row in 0..traces.num_rows() {
+            traces.fill_columns(row, 0u32, Column::ValueB);
+            traces.fill_columns(row, false, Column::IsMul);
+        }
+
+        let unfilled = traces.finalize().unfilled_columns();
+        assert_eq!(unfilled, vec![Column::ValueB]);
+    }
+}
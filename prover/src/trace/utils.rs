@@ -1,3 +1,4 @@
+#[cfg(feature = "parallel")]
 use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
 use stwo::{
     core::fields::m31::BaseField,
@@ -91,20 +92,44 @@ impl FromBaseFields<WORD_SIZE> for u32 {
     }
 }
 
+/// Converts raw columns stored in coset order into bit-reversed [`BaseColumn`]s.
+///
+/// With the `parallel` feature enabled this is [`finalize_columns_parallel`], otherwise
+/// [`finalize_columns_serial`]. Both produce the same output.
 pub fn finalize_columns(columns: Vec<Vec<BaseField>>) -> Vec<BaseColumn> {
+    #[cfg(feature = "parallel")]
+    {
+        finalize_columns_parallel(columns)
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        finalize_columns_serial(columns)
+    }
+}
+
+/// Version of [`finalize_columns`] converting columns concurrently on the rayon thread pool.
+#[cfg(feature = "parallel")]
+pub fn finalize_columns_parallel(columns: Vec<Vec<BaseField>>) -> Vec<BaseColumn> {
     let mut ret = Vec::with_capacity(columns.len());
     columns
         .into_par_iter()
-        .map(|col| {
-            let eval = coset_order_to_circle_domain_order(col.as_slice());
-            let mut base_column = BaseColumn::from_iter(eval);
-            <SimdBackend as ColumnOps<BaseField>>::bit_reverse_column(&mut base_column);
-            base_column
-        })
+        .map(finalize_column)
         .collect_into_vec(&mut ret);
     ret
 }
 
+/// Single-threaded version of [`finalize_columns`].
+pub fn finalize_columns_serial(columns: Vec<Vec<BaseField>>) -> Vec<BaseColumn> {
+    columns.into_iter().map(finalize_column).collect()
+}
+
+fn finalize_column(col: Vec<BaseField>) -> BaseColumn {
+    let eval = coset_order_to_circle_domain_order(col.as_slice());
+    let mut base_column = BaseColumn::from_iter(eval);
+    <SimdBackend as ColumnOps<BaseField>>::bit_reverse_column(&mut base_column);
+    base_column
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -113,6 +138,33 @@ mod tests {
         utils::{bit_reverse_index, coset_index_to_circle_domain_index},
     };
 
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_finalize_matches_serial() {
+        use crate::column::Column;
+
+        const LOG_SIZE: u32 = 8;
+        let columns: Vec<Vec<BaseField>> = (0..Column::COLUMNS_NUM)
+            .map(|col_idx| {
+                (0..1u32 << LOG_SIZE)
+                    .map(|row_idx| M31::from(row_idx.wrapping_mul(31).wrapping_add(col_idx as u32)))
+                    .collect()
+            })
+            .collect();
+
+        let parallel = finalize_columns_parallel(columns.clone());
+        let serial = finalize_columns_serial(columns);
+
+        assert_eq!(parallel.len(), serial.len());
+        for (col_idx, (lhs, rhs)) in parallel.iter().zip(&serial).enumerate() {
+            assert_eq!(
+                lhs.as_slice(),
+                rhs.as_slice(),
+                "column {col_idx} differs between parallel and serial finalization"
+            );
+        }
+    }
+
     #[test]
     fn test_order() {
         let log_size = 3;
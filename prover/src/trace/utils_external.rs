@@ -17,6 +17,7 @@
 // https://github.com/starkware-libs/stwo/blob/f7871979e6ea8e606dc4674301b7d8b28b5838ed/crates/prover/src/core/utils.rs#L108
 // and since then modified.
 
+#[cfg(feature = "parallel")]
 use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
 use stwo::core::fields::Field;
 
@@ -26,6 +27,7 @@ pub fn coset_order_to_circle_domain_order<F: Field>(values: &[F]) -> Vec<F> {
     let n = values.len();
     let half_len = n / 2;
 
+    #[cfg(feature = "parallel")]
     (0..half_len)
         .into_par_iter()
         .map(|i| values[i << 1])
@@ -35,5 +37,11 @@ pub fn coset_order_to_circle_domain_order<F: Field>(values: &[F]) -> Vec<F> {
                 .map(|i| values[n - 1 - (i << 1)]),
         )
         .collect_into_vec(&mut ret);
+    #[cfg(not(feature = "parallel"))]
+    ret.extend(
+        (0..half_len)
+            .map(|i| values[i << 1])
+            .chain((0..half_len).map(|i| values[n - 1 - (i << 1)])),
+    );
     ret
 }
@@ -16,6 +16,7 @@ use stwo_constraint_framework::{EvalAtRow, LogupTraceGenerator};
 
 use crate::{
     components::AllLookupElements,
+    diagnostics::{DiagnosticEvaluator, MemoryReport},
     extensions::ExtensionsConfig,
     trace::{
         eval::TraceEval, preprocessed::PreprocessedTraces, program_trace::ProgramTraces,
@@ -39,6 +40,17 @@ pub trait MachineChip {
         config: &ExtensionsConfig,
     );
 
+    /// Called once after [`Self::fill_main_trace`] has been called on every row.
+    ///
+    /// Chips reading values that other chips write on arbitrary rows, like range checks counting multiplicities, do
+    /// so here instead of on the last row.
+    fn finalize_main_trace(
+        _traces: &mut TracesBuilder,
+        _side_note: &mut SideNote,
+        _config: &ExtensionsConfig,
+    ) {
+    }
+
     /// Called on each row during constraint evaluation.
     ///
     /// This method **should not** read masks from `eval`.
@@ -49,6 +61,62 @@ pub trait MachineChip {
         config: &ExtensionsConfig,
     );
 
+    /// Called on each row by [`find_constraint_violation`](crate::diagnostics::find_constraint_violation).
+    ///
+    /// Attributes the constraints to this chip, tuples forward to their members instead.
+    fn add_diagnosed_constraints(
+        eval: &mut DiagnosticEvaluator,
+        trace_eval: &TraceEval<DiagnosticEvaluator>,
+        lookup_elements: &AllLookupElements,
+        config: &ExtensionsConfig,
+    ) {
+        eval.set_chip(std::any::type_name::<Self>());
+        Self::add_constraints(eval, trace_eval, lookup_elements, config);
+    }
+
+    /// Called on each row by [`Machine::prove_with_memory_report`](crate::machine::Machine::prove_with_memory_report)
+    /// instead of [`Self::fill_main_trace`].
+    ///
+    /// Attributes the columns written to this chip, tuples forward to their members instead.
+    fn fill_reported_main_trace(
+        traces: &mut TracesBuilder,
+        row_idx: usize,
+        vm_step: &Option<ProgramStep>,
+        side_note: &mut SideNote,
+        config: &ExtensionsConfig,
+        report: &mut MemoryReport,
+    ) {
+        report.fill_main_trace(std::any::type_name::<Self>(), traces, row_idx, |traces| {
+            Self::fill_main_trace(traces, row_idx, vm_step, side_note, config)
+        });
+    }
+
+    /// Called once by [`Machine::prove_with_memory_report`](crate::machine::Machine::prove_with_memory_report)
+    /// instead of [`Self::fill_interaction_trace`].
+    ///
+    /// Attributes the columns generated to this chip, tuples forward to their members instead.
+    fn fill_reported_interaction_trace(
+        original_traces: &FinalizedTraces,
+        preprocessed_trace: &PreprocessedTraces,
+        program_traces: &ProgramTraces,
+        lookup_elements: &AllLookupElements,
+        report: &mut MemoryReport,
+    ) {
+        report.fill_interaction_trace(
+            std::any::type_name::<Self>(),
+            original_traces.log_size(),
+            |logup_trace_gen| {
+                Self::fill_interaction_trace(
+                    logup_trace_gen,
+                    original_traces,
+                    preprocessed_trace,
+                    program_traces,
+                    lookup_elements,
+                )
+            },
+        );
+    }
+
     /// Called just once for generating the interaction trace.
     ///
     /// The signature of this method is intentionally similar to `gen_interaction_trace()` in stwo examples.
@@ -93,6 +161,14 @@ impl MachineChip for Tuple {
         for_tuples!( #( Tuple::fill_main_trace(traces, row_idx, vm_step, side_note, config); )* );
     }
 
+    fn finalize_main_trace(
+        traces: &mut TracesBuilder,
+        side_note: &mut SideNote,
+        config: &ExtensionsConfig,
+    ) {
+        for_tuples!( #( Tuple::finalize_main_trace(traces, side_note, config); )* );
+    }
+
     fn add_constraints<E: EvalAtRow>(
         eval: &mut E,
         trace_eval: &TraceEval<E>,
@@ -102,6 +178,36 @@ impl MachineChip for Tuple {
         for_tuples!( #( Tuple::add_constraints(eval, trace_eval, lookup_elements, config); )* );
     }
 
+    fn add_diagnosed_constraints(
+        eval: &mut DiagnosticEvaluator,
+        trace_eval: &TraceEval<DiagnosticEvaluator>,
+        lookup_elements: &AllLookupElements,
+        config: &ExtensionsConfig,
+    ) {
+        for_tuples!( #( Tuple::add_diagnosed_constraints(eval, trace_eval, lookup_elements, config); )* );
+    }
+
+    fn fill_reported_main_trace(
+        traces: &mut TracesBuilder,
+        row_idx: usize,
+        vm_step: &Option<ProgramStep>,
+        side_note: &mut SideNote,
+        config: &ExtensionsConfig,
+        report: &mut MemoryReport,
+    ) {
+        for_tuples!( #( Tuple::fill_reported_main_trace(traces, row_idx, vm_step, side_note, config, report); )* );
+    }
+
+    fn fill_reported_interaction_trace(
+        original_traces: &FinalizedTraces,
+        preprocessed_traces: &PreprocessedTraces,
+        program_traces: &ProgramTraces,
+        lookup_elements: &AllLookupElements,
+        report: &mut MemoryReport,
+    ) {
+        for_tuples!( #( Tuple::fill_reported_interaction_trace(original_traces, preprocessed_traces, program_traces, lookup_elements, report); )* );
+    }
+
     fn fill_interaction_trace(
         logup_trace_gen: &mut LogupTraceGenerator,
         original_traces: &FinalizedTraces,
@@ -106,6 +106,17 @@ impl VirtualColumn<1> for IsTypeR {
     }
 }
 
+/// Whether the row is padding, i.e. doesn't correspond to an execution step.
+///
+/// Chips that check values on every row use it to skip padding rows uniformly.
+pub(crate) struct IsPadding;
+
+impl VirtualColumnForSum for IsPadding {
+    fn columns() -> &'static [Column] {
+        &[Column::IsPadding]
+    }
+}
+
 pub(crate) struct IsTypeU;
 
 impl VirtualColumnForSum for IsTypeU {
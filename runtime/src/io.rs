@@ -1,13 +1,38 @@
 pub use core::fmt::Write;
 extern crate alloc;
 
+/// The severity of a structured log record, see [`log!`](crate::log).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Error = 1,
+    Warn = 2,
+    Info = 3,
+    Debug = 4,
+    Trace = 5,
+}
+
+impl core::fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            LogLevel::Error => "ERROR",
+            LogLevel::Warn => "WARN",
+            LogLevel::Info => "INFO",
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Trace => "TRACE",
+        })
+    }
+}
+
 #[cfg(target_arch = "riscv32")]
 mod riscv32 {
     extern crate alloc;
     use crate::{
-        ecall, read_input, write_output, NexusRTError, SYS_CYCLE_COUNT, SYS_EXIT, SYS_LOG,
-        SYS_READ_PRIVATE_INPUT, WORD_SIZE,
+        ecall, read_input, write_output, LogLevel, NexusRTError, EXIT_ASSERTION_FAILED,
+        SYS_CYCLE_COUNT, SYS_EXIT, SYS_LOG, SYS_READ_PRIVATE_INPUT, WORD_SIZE,
     };
+
+    /// The file descriptor of the structured log of the VM.
+    const LOG_RECORD_FD: u32 = 3;
     use serde::{de::DeserializeOwned, Serialize};
 
     /// Write a string to the output console (if any).
@@ -23,6 +48,43 @@ mod riscv32 {
         }
     }
 
+    /// Write a record to the structured log of the VM (if any), stamped by the VM with the current cycle.
+    pub fn write_log_record(level: LogLevel, s: &str) -> Option<u32> {
+        let buf_ptr = s.as_ptr();
+        let buf_len = s.len();
+        let out = ecall!(
+            SYS_LOG,
+            LOG_RECORD_FD,
+            ("a1", buf_ptr),
+            ("a2", buf_len),
+            ("a3", level as u32)
+        );
+        if out == u32::MAX {
+            None
+        } else {
+            Some(out)
+        }
+    }
+
+    /// Format and write a record to the structured log of the VM, see [`log!`](crate::log).
+    pub fn write_log_record_fmt(level: LogLevel, args: core::fmt::Arguments) -> Option<u32> {
+        write_log_record(level, &alloc::fmt::format(args))
+    }
+
+    /// Call the host function registered for the syscall `code`, which must be in `0x1000..=0x1FFF`, with arguments
+    /// placed in `a0` through `a3`, and return its result.
+    ///
+    /// The result is advice provided by the host, and the guest must not trust it.
+    pub fn host_call(code: u32, args: [u32; 4]) -> u32 {
+        ecall!(
+            code,
+            args[0],
+            ("a1", args[1]),
+            ("a2", args[2]),
+            ("a3", args[3])
+        )
+    }
+
     /// Exit the program with the given exit code.
     pub fn exit(exit_code: i32) -> ! {
         // Write the exit code to the output.
@@ -35,6 +97,17 @@ mod riscv32 {
         }
     }
 
+    /// Assert that `cond` holds, or otherwise halt with exit code 2 and `id` as the public output.
+    ///
+    /// Unlike a panic, a failed assertion is attested to by the proof: the verifier can check that
+    /// assertion `id` failed by expecting exit code 2 and public output `id`.
+    pub fn nexus_assert(cond: bool, id: u32) {
+        if !cond {
+            let _ = write_public_output(&id);
+            exit(EXIT_ASSERTION_FAILED as i32);
+        }
+    }
+
     /// Read an object off the private input tape
     ///
     /// exhausts the private input tape, so can only be used once
@@ -43,6 +116,25 @@ mod riscv32 {
         Ok(postcard::from_bytes_cobs::<T>(bytes.as_mut_slice())?)
     }
 
+    /// Read the next object off the private input tape
+    ///
+    /// Unlike [`read_private_input`], only the bytes of a single object are read, so that objects provided on
+    /// demand by the host can be read one at a time
+    pub fn read_private<T: DeserializeOwned>() -> Result<T, NexusRTError> {
+        let mut bytes = alloc::vec::Vec::new();
+        // Skip any padding before the object, then read up to and including the cobs delimiter.
+        while let Some(byte) = read_from_private_input() {
+            if byte == 0 && bytes.is_empty() {
+                continue;
+            }
+            bytes.push(byte);
+            if byte == 0 {
+                break;
+            }
+        }
+        Ok(postcard::from_bytes_cobs::<T>(bytes.as_mut_slice())?)
+    }
+
     /// Read a byte from the private input tape
     fn read_from_private_input() -> Option<u8> {
         let out = ecall!(SYS_READ_PRIVATE_INPUT);
@@ -142,6 +234,27 @@ macro_rules! println {
     }
 }
 
+/// Writes a structured record to the VM log at the given [`LogLevel`]
+///
+/// The VM stamps each record with the cycle at which it was written. The records can be filtered by level on the
+/// host, and are also included in the plain VM terminal output.
+#[cfg(target_arch = "riscv32")]
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($as:tt)*) => {
+        nexus_rt::write_log_record_fmt($level, core::format_args!($($as)*))
+    }
+}
+
+/// Writes a structured record to the terminal at the given [`LogLevel`]
+#[cfg(not(target_arch = "riscv32"))]
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($as:tt)*) => {
+        std::println!("[{}] {}", $level, std::format_args!($($as)*))
+    }
+}
+
 #[allow(private_bounds)]
 #[cfg(not(target_arch = "riscv32"))]
 mod native {
@@ -162,6 +275,15 @@ mod native {
         unimplemented!()
     }
 
+    pub fn host_call<UNUSABLE: RequiresRV32Target>(_code: u32, _args: [u32; 4]) -> u32 {
+        unimplemented!()
+    }
+
+    pub fn read_private<UNUSABLE: RequiresRV32Target, T: DeserializeOwned>(
+    ) -> Result<T, NexusRTError> {
+        unimplemented!()
+    }
+
     pub fn read_public_input<UNUSABLE: RequiresRV32Target, T: DeserializeOwned>(
     ) -> Result<T, NexusRTError> {
         unimplemented!()
@@ -170,6 +292,10 @@ mod native {
     pub fn write_public_output<UNUSABLE: RequiresRV32Target, T: Serialize + ?Sized>(_val: &T) {
         unimplemented!()
     }
+
+    pub fn nexus_assert<UNUSABLE: RequiresRV32Target>(_cond: bool, _id: u32) {
+        unimplemented!()
+    }
 }
 #[cfg(not(target_arch = "riscv32"))]
 pub use native::*;
@@ -20,7 +20,8 @@ mod error;
 pub use error::*;
 pub use postcard;
 
-pub mod keccak;
+pub use nexus_common::{keccak, keccak256, merkle, rand};
+pub mod secp256k1;
 
 // Ecall codes. Allow dead code here because these are only used in the RISC-V runtime, not when
 // compiling for the host.
@@ -44,6 +45,8 @@ pub(crate) const SYS_PERFORM_HEAP_ALLOCATION: u32 = 0x405;
 pub(crate) const EXIT_SUCCESS: u32 = 0;
 #[cfg(target_arch = "riscv32")]
 pub(crate) const EXIT_PANIC: u32 = 1;
+#[cfg(target_arch = "riscv32")]
+pub(crate) const EXIT_ASSERTION_FAILED: u32 = 2;
 // Constants.
 #[cfg(target_arch = "riscv32")]
 pub(crate) const WORD_SIZE: usize = 4;
@@ -0,0 +1,333 @@
+//! Verification of secp256k1 ECDSA signatures, see [`verify`].
+//!
+//! Field and scalar arithmetic is done in software over 32-bit words, so that a verification runs on RV32IM
+//! instructions alone and is proven like the rest of the guest. It takes a few million cycles, dominated by the
+//! multiplications of the two scalar multiplications.
+
+/// A 256-bit integer, as eight little-endian words.
+type U256 = [u32; 8];
+
+const ZERO: U256 = [0; 8];
+const ONE: U256 = [1, 0, 0, 0, 0, 0, 0, 0];
+
+/// A modulus `2^256 - c`, with `c` small enough to reduce by folding the high half of a product onto the low half.
+struct Modulus {
+    m: U256,
+    c: [u32; 5],
+}
+
+/// The order of the base field, `2^256 - 2^32 - 977`.
+const P: Modulus = Modulus {
+    m: [
+        0xfffffc2f, 0xfffffffe, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff,
+        0xffffffff,
+    ],
+    c: [0x000003d1, 0x00000001, 0, 0, 0],
+};
+
+/// The order of the group.
+const N: Modulus = Modulus {
+    m: [
+        0xd0364141, 0xbfd25e8c, 0xaf48a03b, 0xbaaedce6, 0xfffffffe, 0xffffffff, 0xffffffff,
+        0xffffffff,
+    ],
+    c: [0x2fc9bebf, 0x402da173, 0x50b75fc4, 0x45512319, 0x00000001],
+};
+
+/// The generator of the group.
+const G: Affine = Affine {
+    x: [
+        0x16f81798, 0x59f2815b, 0x2dce28d9, 0x029bfcdb, 0xce870b07, 0x55a06295, 0xf9dcbbac,
+        0x79be667e,
+    ],
+    y: [
+        0xfb10d4b8, 0x9c47d08f, 0xa6855419, 0xfd17b448, 0x0e1108a8, 0x5da4fbfc, 0x26a3c465,
+        0x483ada77,
+    ],
+};
+
+fn from_be_bytes(bytes: &[u8]) -> U256 {
+    core::array::from_fn(|i| {
+        let end = bytes.len() - 4 * i;
+        u32::from_be_bytes([
+            bytes[end - 4],
+            bytes[end - 3],
+            bytes[end - 2],
+            bytes[end - 1],
+        ])
+    })
+}
+
+fn less_than(a: &U256, b: &U256) -> bool {
+    a.iter().rev().lt(b.iter().rev())
+}
+
+/// Sets `a` to `a + b` modulo 2^(32 * a.len()), returning the carry.
+fn add_assign(a: &mut [u32], b: &[u32]) -> bool {
+    let mut carry = false;
+    for (a, b) in a.iter_mut().zip(b) {
+        let (sum, c1) = a.overflowing_add(*b);
+        let (sum, c2) = sum.overflowing_add(carry as u32);
+        *a = sum;
+        carry = c1 || c2;
+    }
+    carry
+}
+
+/// Sets `a` to `a - b` modulo 2^(32 * a.len()), returning the borrow.
+fn sub_assign(a: &mut [u32], b: &[u32]) -> bool {
+    let mut borrow = false;
+    for (a, b) in a.iter_mut().zip(b) {
+        let (diff, b1) = a.overflowing_sub(*b);
+        let (diff, b2) = diff.overflowing_sub(borrow as u32);
+        *a = diff;
+        borrow = b1 || b2;
+    }
+    borrow
+}
+
+/// Adds `b * c` to `acc`, which must be large enough to hold the result.
+fn mul_add_assign(acc: &mut [u32], b: &[u32], c: &[u32]) {
+    for (i, b) in b.iter().enumerate() {
+        if *b == 0 {
+            continue;
+        }
+        let mut carry = 0u64;
+        for (j, c) in c.iter().enumerate() {
+            let sum = u64::from(*b) * u64::from(*c) + u64::from(acc[i + j]) + carry;
+            acc[i + j] = sum as u32;
+            carry = sum >> 32;
+        }
+        for word in acc[i + c.len()..].iter_mut() {
+            if carry == 0 {
+                break;
+            }
+            let sum = u64::from(*word) + carry;
+            *word = sum as u32;
+            carry = sum >> 32;
+        }
+    }
+}
+
+impl Modulus {
+    fn add(&self, a: &U256, b: &U256) -> U256 {
+        let mut sum = *a;
+        // Both operands are below the modulus, so subtracting it once is enough, even after wrapping around.
+        if add_assign(&mut sum, b) || !less_than(&sum, &self.m) {
+            sub_assign(&mut sum, &self.m);
+        }
+        sum
+    }
+
+    fn sub(&self, a: &U256, b: &U256) -> U256 {
+        let mut diff = *a;
+        if sub_assign(&mut diff, b) {
+            add_assign(&mut diff, &self.m);
+        }
+        diff
+    }
+
+    fn mul(&self, a: &U256, b: &U256) -> U256 {
+        let mut product = [0u32; 16];
+        mul_add_assign(&mut product, a, b);
+
+        // 2^256 is congruent to c, so the high half folds onto the low half as `low + high * c`, until it is zero.
+        while product[8..].iter().any(|word| *word != 0) {
+            let mut folded = [0u32; 16];
+            folded[..8].copy_from_slice(&product[..8]);
+            mul_add_assign(&mut folded, &product[8..], &self.c);
+            product = folded;
+        }
+
+        // The modulus is above 2^255, so a value below 2^256 is reduced by subtracting it at most once.
+        let mut result: U256 = product[..8].try_into().expect("low half has eight words");
+        if !less_than(&result, &self.m) {
+            sub_assign(&mut result, &self.m);
+        }
+        result
+    }
+
+    /// Inverts a nonzero `a` modulo the (prime) modulus, as `a^(m - 2)`.
+    fn invert(&self, a: &U256) -> U256 {
+        let mut exponent = self.m;
+        sub_assign(&mut exponent, &[2]);
+
+        let mut result = ONE;
+        for bit in (0..256).rev() {
+            result = self.mul(&result, &result);
+            if (exponent[bit / 32] >> (bit % 32)) & 1 == 1 {
+                result = self.mul(&result, a);
+            }
+        }
+        result
+    }
+}
+
+/// A point in affine coordinates, other than the point at infinity.
+#[derive(Clone, Copy)]
+struct Affine {
+    x: U256,
+    y: U256,
+}
+
+/// A point in Jacobian coordinates, `(x / z^2, y / z^3)`, which is the point at infinity if `z` is zero.
+#[derive(Clone, Copy)]
+struct Point {
+    x: U256,
+    y: U256,
+    z: U256,
+}
+
+impl Point {
+    const INFINITY: Self = Self {
+        x: ONE,
+        y: ONE,
+        z: ZERO,
+    };
+
+    fn is_infinity(&self) -> bool {
+        self.z == ZERO
+    }
+
+    fn double(&self) -> Self {
+        if self.is_infinity() || self.y == ZERO {
+            return Self::INFINITY;
+        }
+        // dbl-2009-l, for curves with a = 0.
+        let a = P.mul(&self.x, &self.x);
+        let b = P.mul(&self.y, &self.y);
+        let c = P.mul(&b, &b);
+        let x_plus_b = P.add(&self.x, &b);
+        let d = P.sub(&P.sub(&P.mul(&x_plus_b, &x_plus_b), &a), &c);
+        let d = P.add(&d, &d);
+        let e = P.add(&P.add(&a, &a), &a);
+        let f = P.mul(&e, &e);
+
+        let x = P.sub(&f, &P.add(&d, &d));
+        let c2 = P.add(&c, &c);
+        let c4 = P.add(&c2, &c2);
+        let c8 = P.add(&c4, &c4);
+        let y = P.sub(&P.mul(&e, &P.sub(&d, &x)), &c8);
+        let z = P.mul(&P.add(&self.y, &self.y), &self.z);
+        Self { x, y, z }
+    }
+
+    /// Adds a point given in affine coordinates.
+    fn add_affine(&self, other: &Affine) -> Self {
+        if self.is_infinity() {
+            return Self {
+                x: other.x,
+                y: other.y,
+                z: ONE,
+            };
+        }
+        // madd-2007-bl.
+        let z1z1 = P.mul(&self.z, &self.z);
+        let u2 = P.mul(&other.x, &z1z1);
+        let s2 = P.mul(&P.mul(&other.y, &self.z), &z1z1);
+        let h = P.sub(&u2, &self.x);
+        let s2_minus_y1 = P.sub(&s2, &self.y);
+        if h == ZERO {
+            return if s2_minus_y1 == ZERO {
+                self.double()
+            } else {
+                Self::INFINITY
+            };
+        }
+
+        let hh = P.mul(&h, &h);
+        let hh2 = P.add(&hh, &hh);
+        let i = P.add(&hh2, &hh2);
+        let j = P.mul(&h, &i);
+        let r = P.add(&s2_minus_y1, &s2_minus_y1);
+        let v = P.mul(&self.x, &i);
+
+        let x = P.sub(&P.sub(&P.mul(&r, &r), &j), &P.add(&v, &v));
+        let y1_j = P.mul(&self.y, &j);
+        let y = P.sub(&P.mul(&r, &P.sub(&v, &x)), &P.add(&y1_j, &y1_j));
+        let z1_plus_h = P.add(&self.z, &h);
+        let z = P.sub(&P.sub(&P.mul(&z1_plus_h, &z1_plus_h), &z1z1), &hh);
+        Self { x, y, z }
+    }
+
+    /// Returns the point in affine coordinates, or `None` for the point at infinity.
+    fn to_affine(self) -> Option<Affine> {
+        if self.is_infinity() {
+            return None;
+        }
+        let z_inv = P.invert(&self.z);
+        let z_inv2 = P.mul(&z_inv, &z_inv);
+        Some(Affine {
+            x: P.mul(&self.x, &z_inv2),
+            y: P.mul(&self.y, &P.mul(&z_inv2, &z_inv)),
+        })
+    }
+}
+
+/// Verifies the ECDSA `signature` (`r` followed by `s`) of `message_hash` under the uncompressed `public_key` (`x`
+/// followed by `y`, without the `0x04` prefix). All values are big-endian.
+///
+/// High `s` values are accepted, as in the original ECDSA specification.
+pub fn verify(message_hash: &[u8; 32], signature: &[u8; 64], public_key: &[u8; 64]) -> bool {
+    let (r, s) = (
+        from_be_bytes(&signature[..32]),
+        from_be_bytes(&signature[32..]),
+    );
+    if r == ZERO || s == ZERO || !less_than(&r, &N.m) || !less_than(&s, &N.m) {
+        return false;
+    }
+
+    let public_key = Affine {
+        x: from_be_bytes(&public_key[..32]),
+        y: from_be_bytes(&public_key[32..]),
+    };
+    if !less_than(&public_key.x, &P.m) || !less_than(&public_key.y, &P.m) {
+        return false;
+    }
+    // y^2 = x^3 + 7
+    let x3 = P.mul(&P.mul(&public_key.x, &public_key.x), &public_key.x);
+    if P.mul(&public_key.y, &public_key.y) != P.add(&x3, &[7, 0, 0, 0, 0, 0, 0, 0]) {
+        return false;
+    }
+
+    // The hash is below 2^256, which is less than twice N.
+    let mut z = from_be_bytes(message_hash);
+    if !less_than(&z, &N.m) {
+        sub_assign(&mut z, &N.m);
+    }
+    let w = N.invert(&s);
+    let u1 = N.mul(&z, &w);
+    let u2 = N.mul(&r, &w);
+
+    // u1·G + u2·Q, with both multiplications sharing the doublings. G + Q is made affine once, so that every
+    // addition of the loop is a mixed one.
+    let g_plus_q = Point::INFINITY
+        .add_affine(&G)
+        .add_affine(&public_key)
+        .to_affine();
+    let mut point = Point::INFINITY;
+    for bit in (0..256).rev() {
+        point = point.double();
+        let bits = (
+            (u1[bit / 32] >> (bit % 32)) & 1,
+            (u2[bit / 32] >> (bit % 32)) & 1,
+        );
+        point = match (bits, &g_plus_q) {
+            // G + Q is infinity if Q = -G, and adding it leaves the point unchanged.
+            ((1, 1), Some(g_plus_q)) => point.add_affine(g_plus_q),
+            ((1, 0), _) => point.add_affine(&G),
+            ((0, 1), _) => point.add_affine(&public_key),
+            _ => point,
+        };
+    }
+    let Some(point) = point.to_affine() else {
+        return false;
+    };
+
+    // The x-coordinate is below P, which is less than twice N.
+    let mut x = point.x;
+    if !less_than(&x, &N.m) {
+        sub_assign(&mut x, &N.m);
+    }
+    x == r
+}
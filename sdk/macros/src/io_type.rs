@@ -0,0 +1,84 @@
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use proc_macro_crate::{crate_name, FoundCrate};
+use quote::quote;
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Fields, Ident};
+
+pub fn derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let found_crate = crate_name("nexus-sdk").expect("nexus-sdk is not in `Cargo.toml`");
+    let sdk = match found_crate {
+        FoundCrate::Itself => quote!(crate),
+        FoundCrate::Name(name) => {
+            let ident = Ident::new(&name, Span::call_site());
+            quote!( ::#ident )
+        }
+    };
+
+    let name = &input.ident;
+    let name_str = name.to_string();
+    let description = match &input.data {
+        Data::Struct(data) => describe_fields(&sdk, &name_str, &data.fields),
+        Data::Enum(data) => {
+            let variants = data
+                .variants
+                .iter()
+                .map(|variant| describe_fields(&sdk, &variant.ident.to_string(), &variant.fields));
+            quote! {
+                ::std::format!("{} {{ {} }}", #name_str, <[::std::string::String]>::join(&[#(#variants),*], ", "))
+            }
+        }
+        Data::Union(_) => {
+            return syn::Error::new_spanned(name, "`IoType` cannot be derived for unions")
+                .to_compile_error()
+                .into()
+        }
+    };
+
+    let mut generics = input.generics.clone();
+    for param in generics.type_params_mut() {
+        param.bounds.push(parse_quote!(#sdk::IoType));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let output: proc_macro2::TokenStream = quote! {
+        impl #impl_generics #sdk::IoType for #name #ty_generics #where_clause {
+            fn schema_id() -> ::std::string::String {
+                #description
+            }
+        }
+    };
+
+    output.into()
+}
+
+/// Describe a struct or enum variant named `name` by the identifiers of its fields, e.g. `Point { x: i32, y: i32 }`.
+fn describe_fields(
+    sdk: &proc_macro2::TokenStream,
+    name: &str,
+    fields: &Fields,
+) -> proc_macro2::TokenStream {
+    match fields {
+        Fields::Named(fields) => {
+            let fields = fields.named.iter().map(|field| {
+                let ident = field.ident.as_ref().unwrap().to_string();
+                let ty = &field.ty;
+                quote!(::std::format!("{}: {}", #ident, <#ty as #sdk::IoType>::schema_id()))
+            });
+            quote!(
+                ::std::format!("{} {{ {} }}", #name, <[::std::string::String]>::join(&[#(#fields),*], ", "))
+            )
+        }
+        Fields::Unnamed(fields) => {
+            let fields = fields.unnamed.iter().map(|field| {
+                let ty = &field.ty;
+                quote!(<#ty as #sdk::IoType>::schema_id())
+            });
+            quote!(
+                ::std::format!("{}({})", #name, <[::std::string::String]>::join(&[#(#fields),*], ", "))
+            )
+        }
+        Fields::Unit => quote!(::std::string::String::from(#name)),
+    }
+}
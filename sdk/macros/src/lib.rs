@@ -1,7 +1,14 @@
 use proc_macro::TokenStream;
+mod io_type;
 mod pprof;
 
 #[proc_macro_attribute]
 pub fn profile(attr: TokenStream, input: TokenStream) -> TokenStream {
     pprof::derive(attr, input)
 }
+
+/// Derive `nexus_sdk::IoType`, describing the type by its name and the schema identifiers of its fields.
+#[proc_macro_derive(IoType)]
+pub fn io_type(input: TokenStream) -> TokenStream {
+    io_type::derive(input)
+}
@@ -0,0 +1,93 @@
+use std::collections::BTreeMap;
+
+/// Associated data built from named fields, with a canonical encoding.
+///
+/// Fields are encoded in key order regardless of the order in which they were added, so a prover and a
+/// verifier that add the same fields always agree on the bytes bound into the proof. Each key and value is
+/// prefixed with its length as a little-endian `u32`, and the whole encoding with the number of fields, so
+/// distinct sets of fields never share an encoding.
+///
+/// ```rust
+/// use nexus_sdk::AssociatedData;
+///
+/// let ad = AssociatedData::new()
+///     .field("chain", b"mainnet")
+///     .field("nonce", 7u64.to_le_bytes());
+///
+/// let same = AssociatedData::new()
+///     .field("nonce", 7u64.to_le_bytes())
+///     .field("chain", b"mainnet");
+///
+/// assert_eq!(ad.encode(), same.encode());
+///
+/// let decoded = AssociatedData::decode(&ad.encode()).unwrap();
+/// assert_eq!(decoded.get("nonce"), Some(&7u64.to_le_bytes()[..]));
+/// ```
+///
+/// The associated data is mixed into the Fiat-Shamir channel of the proof, but is not readable by the guest.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AssociatedData {
+    fields: BTreeMap<String, Vec<u8>>,
+}
+
+impl AssociatedData {
+    /// Start building associated data with no fields.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the field `key` with value `bytes`, replacing any prior value for the same key.
+    pub fn field(mut self, key: &str, bytes: impl AsRef<[u8]>) -> Self {
+        self.fields.insert(key.to_string(), bytes.as_ref().to_vec());
+        self
+    }
+
+    /// The value of the field `key`, if any.
+    pub fn get(&self, key: &str) -> Option<&[u8]> {
+        self.fields.get(key).map(Vec::as_slice)
+    }
+
+    /// Decode associated data from its canonical encoding, such as the associated data of a view.
+    ///
+    /// Returns `None` if `bytes` is not the canonical encoding of any set of fields.
+    pub fn decode(bytes: &[u8]) -> Option<Self> {
+        fn take<'a>(bytes: &mut &'a [u8], len: usize) -> Option<&'a [u8]> {
+            let (head, tail) = bytes.split_at_checked(len)?;
+            *bytes = tail;
+            Some(head)
+        }
+        fn take_len(bytes: &mut &[u8]) -> Option<usize> {
+            let len = u32::from_le_bytes(take(bytes, 4)?.try_into().ok()?);
+            Some(len as usize)
+        }
+
+        let mut rest = bytes;
+        let count = take_len(&mut rest)?;
+
+        let mut ad = Self::new();
+        for _ in 0..count {
+            let key_len = take_len(&mut rest)?;
+            let key = std::str::from_utf8(take(&mut rest, key_len)?).ok()?;
+            let value_len = take_len(&mut rest)?;
+            let value = take(&mut rest, value_len)?;
+            ad = ad.field(key, value);
+        }
+
+        // Out of order or duplicate keys, and trailing bytes, are not canonical.
+        (ad.encode() == bytes).then_some(ad)
+    }
+
+    /// The canonical encoding, to pass as the associated data when proving and when verifying.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut encoded = (self.fields.len() as u32).to_le_bytes().to_vec();
+
+        for (key, value) in &self.fields {
+            encoded.extend_from_slice(&(key.len() as u32).to_le_bytes());
+            encoded.extend_from_slice(key.as_bytes());
+            encoded.extend_from_slice(&(value.len() as u32).to_le_bytes());
+            encoded.extend_from_slice(value);
+        }
+
+        encoded
+    }
+}
@@ -1,8 +1,10 @@
 use crypto_common::generic_array::typenum::{ToInt, U32};
+use nexus_core::nvm::{ExecutionLimits, SyscallCode};
+use serde::Deserialize;
 use std::io;
 use std::io::Write;
 use std::marker::PhantomData;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::str::FromStr;
 use uuid::Uuid;
@@ -22,6 +24,167 @@ impl Packager for CargoPackager {
     }
 }
 
+/// The guest resource limits declared in a `[package.metadata.nexus]` manifest section.
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NexusMetadata {
+    cycle_limit: Option<usize>,
+    max_memory: Option<u32>,
+    syscalls: Option<Vec<String>>,
+}
+
+impl NexusMetadata {
+    fn into_limits(self) -> Result<ExecutionLimits, BuildError> {
+        let syscalls = self
+            .syscalls
+            .map(|names| {
+                names
+                    .iter()
+                    .map(|name| {
+                        SyscallCode::from_name(name).map(u32::from).ok_or_else(|| {
+                            BuildError::InvalidManifest(format!("unknown syscall `{name}`"))
+                        })
+                    })
+                    .collect::<Result<Vec<u32>, BuildError>>()
+            })
+            .transpose()?;
+
+        Ok(ExecutionLimits {
+            cycle_limit: self.cycle_limit,
+            max_memory: self.max_memory,
+            syscalls,
+        })
+    }
+}
+
+/// Read the resource limits for `package` from the output of `cargo metadata`.
+pub(super) fn read_manifest_limits(
+    metadata: &serde_json::Value,
+    package: &str,
+) -> Result<ExecutionLimits, BuildError> {
+    let nexus = metadata["packages"]
+        .as_array()
+        .and_then(|packages| packages.iter().find(|p| p["name"] == package))
+        .map(|p| &p["metadata"]["nexus"])
+        .filter(|nexus| !nexus.is_null());
+
+    match nexus {
+        Some(nexus) => serde_json::from_value::<NexusMetadata>(nexus.clone())
+            .map_err(|e| BuildError::InvalidManifest(e.to_string()))?
+            .into_limits(),
+        None => Ok(ExecutionLimits::default()),
+    }
+}
+
+/// Rustc flags remapping the workspace and Cargo home directories to fixed paths, read from the output of
+/// `cargo metadata`.
+fn remap_path_prefixes(metadata: &serde_json::Value) -> Vec<String> {
+    let cargo_home = std::env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cargo")));
+
+    let workspace_root = metadata["workspace_root"].as_str().map(PathBuf::from);
+
+    [(workspace_root, "/nexus-guest"), (cargo_home, "/cargo")]
+        .into_iter()
+        .filter_map(|(from, to)| from.map(|from| format!("{}={to}", from.display())))
+        .flat_map(|remap| ["--remap-path-prefix".to_string(), remap])
+        .collect()
+}
+
+/// A guest program source pinned to a revision of a git repository.
+#[derive(Clone)]
+pub(crate) struct GitSource {
+    url: String,
+    rev: String,
+}
+
+impl GitSource {
+    /// The directory the repository is cloned into, under the temporary directory and keyed by the URL, the package
+    /// and the revision.
+    fn checkout_dir(&self, package: &str) -> PathBuf {
+        let url_digest = crate::merkle::hash_leaf(self.url.as_bytes());
+        let url_key: String = url_digest[..8].iter().map(|b| format!("{b:02x}")).collect();
+        let rev_key: String = self
+            .rev
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .collect();
+
+        std::env::temp_dir()
+            .join("nexus-guest-sources")
+            .join(format!("{package}-{url_key}-{rev_key}"))
+    }
+
+    /// Clone the repository (if not already done) and check out the revision, returning the checkout directory.
+    ///
+    /// A full commit hash is only fetched if it isn't known yet, so that rebuilding a pinned commit works offline.
+    /// Any other revision, such as a branch, is fetched on every checkout so that it doesn't go stale.
+    fn checkout(&self, package: &str) -> Result<PathBuf, BuildError> {
+        if self.rev.starts_with('-') {
+            return Err(BuildError::GitError(format!(
+                "invalid revision `{}`",
+                self.rev
+            )));
+        }
+        let dir = self.checkout_dir(package);
+
+        if !dir.join(".git").exists() {
+            let dir_str = dir.to_string_lossy();
+            git(None, &["clone", "--no-checkout", "--", &self.url, &dir_str])?;
+        }
+
+        let is_commit = self.rev.len() == 40 && self.rev.chars().all(|c| c.is_ascii_hexdigit());
+        if !is_commit
+            || git(
+                Some(&dir),
+                &["checkout", "--force", "--detach", &self.rev, "--"],
+            )
+            .is_err()
+        {
+            git(Some(&dir), &["fetch", "--", "origin", &self.rev])?;
+            git(
+                Some(&dir),
+                &["checkout", "--force", "--detach", "FETCH_HEAD", "--"],
+            )?;
+        }
+
+        Ok(dir)
+    }
+}
+
+/// Run git with `args`, from `dir` if provided.
+fn git(dir: Option<&Path>, args: &[&str]) -> Result<(), BuildError> {
+    let mut cmd = Command::new("git");
+    if let Some(dir) = dir {
+        cmd.current_dir(dir);
+    }
+
+    let res = cmd.args(args).output()?;
+    if !res.status.success() {
+        return Err(BuildError::GitError(
+            String::from_utf8_lossy(&res.stderr).trim().to_string(),
+        ));
+    }
+    Ok(())
+}
+
+impl Compiler<CargoPackager> {
+    /// Configure dynamic compilation of `package` from the git repository at `url`, checked out at `rev`.
+    ///
+    /// The repository is cloned under the temporary directory (see [`std::env::temp_dir`]) by [`build`](Compile::build),
+    /// and fetched again on every build unless `rev` is a full commit hash. Pinning `rev` to a commit makes the binary
+    /// reproducible, as build paths are remapped and the lockfile of the repository (if any) is respected.
+    pub fn from_git(url: &str, rev: &str, package: &str) -> Self {
+        let mut compiler = Self::new(package);
+        compiler.git = Some(GitSource {
+            url: url.to_string(),
+            rev: rev.to_string(),
+        });
+        compiler
+    }
+}
+
 impl Compile for Compiler<CargoPackager> {
     /// Configure dynamic compilation.
     fn new(package: &str) -> Self {
@@ -31,6 +194,10 @@ impl Compile for Compiler<CargoPackager> {
             debug: false,
             native: false,
             unique: false,
+            limits: ExecutionLimits::default(),
+            git: None,
+            image: None,
+            manifest: None,
             _packager: PhantomData,
         }
     }
@@ -43,6 +210,10 @@ impl Compile for Compiler<CargoPackager> {
             debug: false,
             native: false,
             unique: false,
+            limits: ExecutionLimits::default(),
+            git: None,
+            image: None,
+            manifest: None,
             _packager: PhantomData,
         }
     }
@@ -65,17 +236,54 @@ impl Compile for Compiler<CargoPackager> {
     }
 
     /// Compile and build the guest binary.
+    ///
+    /// Build paths are remapped, so that the binary (and so its [`program_digest`](crate::stwo::seq::program_digest))
+    /// does not depend on where the source or the toolchain is located.
     fn build(&mut self) -> Result<PathBuf, BuildError> {
-        let linker_path = Compiler::set_linker()?;
+        let linker_path = Self::set_linker()?;
+
+        let cargo_bin = std::env::var("CARGO").unwrap_or_else(|_err| "cargo".into());
+
+        // Build from the checked out repository for git sources, and from the current directory otherwise.
+        let source_dir = self
+            .git
+            .as_ref()
+            .map(|source| source.checkout(&self.package))
+            .transpose()?;
+        let cargo = || {
+            let mut cmd = Command::new(&cargo_bin);
+            if let Some(dir) = &source_dir {
+                cmd.current_dir(dir);
+            }
+            cmd
+        };
 
-        let rust_flags = [
+        // Read the workspace location, and any resource limits declared in the guest manifest.
+        let res = cargo()
+            .args(["metadata", "--format-version", "1", "--no-deps"])
+            .output()?;
+
+        if !res.status.success() {
+            io::stderr().write_all(&res.stderr)?;
+            return Err(BuildError::CompilerError);
+        }
+
+        let metadata: serde_json::Value = serde_json::from_slice(&res.stdout)
+            .map_err(|e| BuildError::InvalidManifest(e.to_string()))?;
+        self.limits = read_manifest_limits(&metadata, &self.package)?;
+
+        let remap_flags = remap_path_prefixes(&metadata);
+
+        let link_arg = format!("link-arg=-T{}", linker_path.display());
+        let mut rust_flags = vec![
             "-C",
             "relocation-model=pic",
             "-C",
-            &format!("link-arg=-T{}", linker_path.display()),
+            &link_arg,
             "-C",
             "panic=abort",
         ];
+        rust_flags.extend(remap_flags.iter().map(String::as_str));
 
         let target = if self.native {
             "native"
@@ -98,8 +306,7 @@ impl Compile for Compiler<CargoPackager> {
             dest = format!("{}-{}", dest, uuid);
         }
 
-        let cargo_bin = std::env::var("CARGO").unwrap_or_else(|_err| "cargo".into());
-        let mut cmd = Command::new(cargo_bin);
+        let mut cmd = cargo();
 
         // Base args
         cmd.envs(envs).args([
@@ -120,6 +327,14 @@ impl Compile for Compiler<CargoPackager> {
         // Profile selection
         cmd.args(["--profile", profile]);
 
+        // Pinned sources must build with the dependency versions they were committed with.
+        if source_dir
+            .as_ref()
+            .is_some_and(|dir| dir.join("Cargo.lock").exists())
+        {
+            cmd.arg("--locked");
+        }
+
         let res = cmd.output()?;
 
         if !res.status.success() {
@@ -136,4 +351,113 @@ impl Compile for Compiler<CargoPackager> {
 
         Ok(elf_path)
     }
+
+    /// The resource limits declared under `[package.metadata.nexus]` in the guest manifest, as read during the most recent build.
+    fn limits(&self) -> ExecutionLimits {
+        self.limits.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_manifest_limits() {
+        let metadata = serde_json::json!({
+            "packages": [
+                { "name": "other", "metadata": null },
+                {
+                    "name": "guest",
+                    "metadata": {
+                        "nexus": {
+                            "cycle_limit": 1000,
+                            "max_memory": 65536,
+                            "syscalls": ["write", "read_from_private_input"],
+                        }
+                    }
+                },
+            ]
+        });
+
+        let limits = read_manifest_limits(&metadata, "guest").unwrap();
+        assert_eq!(limits.cycle_limit, Some(1000));
+        assert_eq!(limits.max_memory, Some(65536));
+        assert_eq!(limits.syscalls, Some(vec![0x200, 0x400]));
+
+        assert_eq!(
+            read_manifest_limits(&metadata, "other").unwrap(),
+            ExecutionLimits::default()
+        );
+    }
+
+    #[test]
+    fn test_git_checkout() {
+        let repo = std::env::temp_dir().join(format!("nexus-git-source-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&repo).unwrap();
+        let commit = |contents: &str| {
+            std::fs::write(repo.join("guest.txt"), contents).unwrap();
+            for args in [
+                &["add", "-A"][..],
+                &[
+                    "-c",
+                    "user.name=nexus",
+                    "-c",
+                    "user.email=nexus@localhost",
+                    "commit",
+                    "-m",
+                    contents,
+                ],
+            ] {
+                git(Some(&repo), args).unwrap();
+            }
+        };
+        git(Some(&repo), &["init"]).unwrap();
+        git(Some(&repo), &["checkout", "-b", "guest"]).unwrap();
+        commit("v1");
+
+        let source = GitSource {
+            url: format!("file://{}", repo.display()),
+            rev: "guest".to_string(),
+        };
+        let read = |dir: &Path| std::fs::read_to_string(dir.join("guest.txt")).unwrap();
+        let dir = source.checkout("guest").unwrap();
+        assert_eq!(read(&dir), "v1");
+
+        // Branches are fetched again on checkout.
+        commit("v2");
+        assert_eq!(read(&source.checkout("guest").unwrap()), "v2");
+
+        // Sources are keyed by their URL, and revisions can't be passed as options.
+        let other = GitSource {
+            url: format!("{}/", source.url),
+            ..source.clone()
+        };
+        assert_ne!(other.checkout_dir("guest"), dir);
+        let option = GitSource {
+            rev: "--upload-pack=touch".to_string(),
+            ..source.clone()
+        };
+        assert!(matches!(
+            option.checkout("guest"),
+            Err(BuildError::GitError(_))
+        ));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        std::fs::remove_dir_all(&repo).unwrap();
+    }
+
+    #[test]
+    fn test_read_manifest_limits_unknown_syscall() {
+        let metadata = serde_json::json!({
+            "packages": [
+                { "name": "guest", "metadata": { "nexus": { "syscalls": ["fork"] } } },
+            ]
+        });
+
+        assert!(matches!(
+            read_manifest_limits(&metadata, "guest"),
+            Err(BuildError::InvalidManifest(_))
+        ));
+    }
 }
@@ -0,0 +1,336 @@
+use crypto_common::generic_array::typenum::{ToInt, U32};
+use nexus_core::nvm::ExecutionLimits;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::io::Write;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use uuid::Uuid;
+
+pub use crate::error::BuildError;
+
+use super::cargo::read_manifest_limits;
+use super::{Compile, Compiler, Packager};
+
+/// The directory the guest workspace is mounted at within the container.
+const CONTAINER_WORKSPACE: &str = "/nexus-guest";
+/// The path the linker script is mounted at within the container.
+const CONTAINER_LINKER: &str = "/nexus-linker.ld";
+/// The directory the build output is mounted at within the container.
+const CONTAINER_TARGET: &str = "/nexus-target";
+
+/// The guest program packager building Rust guests via Cargo within a pinned container image, for reproducible
+/// builds.
+pub enum DockerPackager {}
+impl Packager for DockerPackager {
+    type DigestSize = U32;
+
+    fn digest_len() -> usize {
+        let sz: u32 = Self::DigestSize::to_int();
+        sz as usize
+    }
+}
+
+/// A record of how a guest binary was built by [`DockerPackager`], so that verifiers can rebuild it bit-for-bit.
+///
+/// It is written as JSON next to the binary, at its path with a `.manifest.json` suffix.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildManifest {
+    /// The container image, as configured.
+    pub image: String,
+    /// The content digest of the container image the guest was built in.
+    pub image_digest: String,
+    /// The version of rustc within the container, as reported by `rustc --version`.
+    pub rustc_version: String,
+    /// The target triple, or `native` for native builds.
+    pub target: String,
+    /// The Cargo profile.
+    pub profile: String,
+    /// The flags passed to rustc.
+    pub rustflags: Vec<String>,
+    /// Whether dependencies were locked to the workspace lockfile.
+    pub locked: bool,
+}
+
+impl Compiler<DockerPackager> {
+    /// Set the container image to build in, such as `rust@sha256:...`.
+    ///
+    /// The image must provide Cargo and the toolchain used by the guest, with the `riscv32im-unknown-none-elf`
+    /// target installed. Pinning the image by digest, rather than by tag, makes the build reproducible.
+    pub fn with_image(mut self, image: &str) -> Self {
+        self.image = Some(image.to_string());
+        self
+    }
+
+    /// The manifest of the most recent build, if any.
+    pub fn manifest(&self) -> Option<&BuildManifest> {
+        self.manifest.as_ref()
+    }
+
+    /// Arguments of `docker run` executing `command` within the workspace at `workspace_root`, from `package_dir`.
+    fn run_args(
+        image: &str,
+        workspace_root: &Path,
+        package_dir: &Path,
+        linker_path: &Path,
+        dest: &str,
+        command: &[&str],
+    ) -> Vec<String> {
+        let workdir = Path::new(CONTAINER_WORKSPACE).join(
+            package_dir
+                .strip_prefix(workspace_root)
+                .unwrap_or(Path::new("")),
+        );
+
+        let mut args = vec![
+            "run".to_string(),
+            "--rm".to_string(),
+            "--volume".to_string(),
+            format!("{}:{CONTAINER_WORKSPACE}", workspace_root.display()),
+            "--volume".to_string(),
+            format!("{}:{CONTAINER_LINKER}:ro", linker_path.display()),
+            "--volume".to_string(),
+            format!("{dest}:{CONTAINER_TARGET}"),
+            "--workdir".to_string(),
+            workdir.display().to_string(),
+            image.to_string(),
+        ];
+        args.extend(command.iter().map(|arg| arg.to_string()));
+        args
+    }
+}
+
+/// Run docker with `args`, returning its trimmed standard output.
+fn docker(args: &[String]) -> Result<String, BuildError> {
+    let res = Command::new("docker").args(args).output()?;
+    if !res.status.success() {
+        return Err(BuildError::DockerError(
+            String::from_utf8_lossy(&res.stderr).trim().to_string(),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&res.stdout).trim().to_string())
+}
+
+impl Compile for Compiler<DockerPackager> {
+    /// Configure dynamic compilation.
+    fn new(package: &str) -> Self {
+        Self::new_with_custom_binary(package, package)
+    }
+
+    /// Configure dynamic compilation, using non-default binary name.
+    fn new_with_custom_binary(package: &str, binary: &str) -> Self {
+        Self {
+            package: package.to_string(),
+            binary: binary.to_string(),
+            debug: false,
+            native: false,
+            unique: false,
+            limits: ExecutionLimits::default(),
+            git: None,
+            image: None,
+            manifest: None,
+            _packager: PhantomData,
+        }
+    }
+
+    /// Set dynamic compilation to build the guest program in a debug profile.
+    fn set_debug_build(&mut self, debug: bool) {
+        self.debug = debug;
+    }
+
+    /// Set dynamic compilation to build for the native (host machine) target, rather than for the zkVM.
+    fn set_native_build(&mut self, native: bool) {
+        self.native = native;
+    }
+
+    /// Set dynamic compilation to run a unique build that neither overwrites prior builds nor will be overwritten by future builds. May be used to concurrently build different versions of the same binary.
+    ///
+    /// Note: the SDK does not automatically clean or otherwise manage the resultant builds in the output directory.
+    fn set_unique_build(&mut self, unique: bool) {
+        self.unique = unique;
+    }
+
+    /// Compile and build the guest binary within the configured container image, and write its
+    /// [`BuildManifest`] next to it.
+    ///
+    /// The workspace of the guest, read with the host Cargo, is mounted at a fixed path, so that the binary does
+    /// not depend on where the source is located.
+    fn build(&mut self) -> Result<PathBuf, BuildError> {
+        let image = self
+            .image
+            .clone()
+            .ok_or_else(|| BuildError::DockerError("no container image configured".into()))?;
+        let linker_path = Self::set_linker()?;
+
+        let cargo_bin = std::env::var("CARGO").unwrap_or_else(|_err| "cargo".into());
+
+        // Read the workspace location, and any resource limits declared in the guest manifest.
+        let res = Command::new(&cargo_bin)
+            .args(["metadata", "--format-version", "1", "--no-deps"])
+            .output()?;
+
+        if !res.status.success() {
+            io::stderr().write_all(&res.stderr)?;
+            return Err(BuildError::CompilerError);
+        }
+
+        let metadata: serde_json::Value = serde_json::from_slice(&res.stdout)
+            .map_err(|e| BuildError::InvalidManifest(e.to_string()))?;
+        self.limits = read_manifest_limits(&metadata, &self.package)?;
+
+        let workspace_root = metadata["workspace_root"]
+            .as_str()
+            .map(PathBuf::from)
+            .ok_or_else(|| BuildError::InvalidManifest("missing workspace root".into()))?;
+        let package_dir = std::env::current_dir()?;
+
+        let link_arg = format!("link-arg=-T{CONTAINER_LINKER}");
+        let rust_flags = vec![
+            "-C",
+            "relocation-model=pic",
+            "-C",
+            &link_arg,
+            "-C",
+            "panic=abort",
+        ];
+
+        let target = if self.native {
+            "native"
+        } else {
+            "riscv32im-unknown-none-elf"
+        };
+
+        let profile = if self.debug { "debug" } else { "release" };
+        let prog = self.binary.as_str();
+
+        let mut dest = match std::env::var_os("OUT_DIR") {
+            Some(path) => path.into_string().unwrap(),
+            None => "/tmp/nexus-target".into(),
+        };
+
+        if self.unique {
+            let uuid = Uuid::new_v4();
+            dest = format!("{}-{}", dest, uuid);
+        }
+        fs::create_dir_all(&dest)?;
+
+        let encoded_flags = format!("CARGO_ENCODED_RUSTFLAGS={}", rust_flags.join("\x1f"));
+        let mut command = vec![
+            "env",
+            &encoded_flags,
+            "cargo",
+            "build",
+            "--package",
+            self.package.as_str(),
+            "--bin",
+            prog,
+            "--target-dir",
+            CONTAINER_TARGET,
+            "--profile",
+            profile,
+        ];
+
+        // Only specify a --target for cross compilation; for native builds Cargo should infer host target.
+        if !self.native {
+            command.extend(["--target", target]);
+        }
+
+        // Builds must use the dependency versions the workspace was committed with.
+        let locked = workspace_root.join("Cargo.lock").exists();
+        if locked {
+            command.push("--locked");
+        }
+
+        let run_args = |command: &[&str]| {
+            Self::run_args(
+                &image,
+                &workspace_root,
+                &package_dir,
+                &linker_path,
+                &dest,
+                command,
+            )
+        };
+
+        if let Err(e) = docker(&run_args(&command)) {
+            io::stderr().write_all(e.to_string().as_bytes())?;
+            return Err(BuildError::CompilerError);
+        }
+
+        let manifest = BuildManifest {
+            image: image.clone(),
+            image_digest: docker(&[
+                "image".to_string(),
+                "inspect".to_string(),
+                "--format".to_string(),
+                "{{.Id}}".to_string(),
+                image.clone(),
+            ])?,
+            rustc_version: docker(&run_args(&["rustc", "--version"]))?,
+            target: target.to_string(),
+            profile: profile.to_string(),
+            rustflags: rust_flags.iter().map(|flag| flag.to_string()).collect(),
+            locked,
+        };
+
+        // Compute output artifact path differently for native vs cross builds
+        let elf_path = if self.native {
+            PathBuf::from(format!("{}/{}/{}", dest, profile, prog))
+        } else {
+            PathBuf::from(format!("{}/{}/{}/{}", dest, target, profile, prog))
+        };
+
+        let manifest_json = serde_json::to_vec_pretty(&manifest)
+            .map_err(|e| BuildError::InvalidManifest(e.to_string()))?;
+        fs::write(
+            format!("{}.manifest.json", elf_path.display()),
+            manifest_json,
+        )?;
+        self.manifest = Some(manifest);
+
+        Ok(elf_path)
+    }
+
+    /// The resource limits declared under `[package.metadata.nexus]` in the guest manifest, as read during the most recent build.
+    fn limits(&self) -> ExecutionLimits {
+        self.limits.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_args() {
+        let args = Compiler::<DockerPackager>::run_args(
+            "rust@sha256:abcd",
+            Path::new("/home/user/guests"),
+            Path::new("/home/user/guests/fib"),
+            Path::new("/tmp/nexus-guest-linkers/default.ld"),
+            "/tmp/nexus-target",
+            &["rustc", "--version"],
+        );
+
+        assert_eq!(
+            args,
+            [
+                "run",
+                "--rm",
+                "--volume",
+                "/home/user/guests:/nexus-guest",
+                "--volume",
+                "/tmp/nexus-guest-linkers/default.ld:/nexus-linker.ld:ro",
+                "--volume",
+                "/tmp/nexus-target:/nexus-target",
+                "--workdir",
+                "/nexus-guest/fib",
+                "rust@sha256:abcd",
+                "rustc",
+                "--version",
+            ]
+        );
+    }
+}
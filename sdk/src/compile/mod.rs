@@ -6,10 +6,14 @@ use std::path::PathBuf;
 use std::str::FromStr;
 
 use crate::error::BuildError;
+use nexus_core::nvm::ExecutionLimits;
 
 /// Compilation and packaging for Rust guests via Cargo.
 pub mod cargo;
 
+/// Reproducible compilation and packaging for Rust guests via Cargo within a container.
+pub mod docker;
+
 /// A guest program packager.
 pub trait Packager {
     type DigestSize: ArrayLength<u8>;
@@ -30,6 +34,10 @@ pub struct Compiler<P: Packager> {
     debug: bool,
     native: bool,
     unique: bool,
+    limits: ExecutionLimits,
+    git: Option<cargo::GitSource>,
+    image: Option<String>,
+    manifest: Option<docker::BuildManifest>,
     _packager: PhantomData<P>,
 }
 
@@ -70,4 +78,9 @@ pub trait Compile {
 
     /// Compile and build the guest binary.
     fn build(&mut self) -> Result<PathBuf, BuildError>;
+
+    /// The resource limits declared by the guest program, as read during the most recent build.
+    fn limits(&self) -> ExecutionLimits {
+        ExecutionLimits::default()
+    }
 }
@@ -10,6 +10,18 @@ pub enum ConfigurationError {
     /// The prover or verifier was invoked without yet having been configured.
     #[error("operation invoked without required configuration having been done")]
     NotYetConfigured,
+
+    /// The execution called host functions, whose results cannot yet be proven.
+    #[error("execution made {0} calls to host functions, which cannot yet be proven")]
+    UnprovableHostCalls(usize),
+
+    /// The prover is in emulation-only mode, whose executions cannot be proven.
+    #[error("prover is in emulation-only mode, see `Stwo::with_zbb_emulation`")]
+    EmulationOnly,
+
+    /// Proofs were asked to have a fixed shape, which needs a cycle limit to be sized by.
+    #[error("a fixed proof shape needs a cycle limit, see `Stwo::with_limits`")]
+    UnboundedShape,
 }
 
 /// Errors that occur during dynamic compilation of guest programs.
@@ -26,6 +38,18 @@ pub enum BuildError {
     /// The compilation process failed.
     #[error("unable to compile using the configured compiler (e.g., rustc via Cargo)")]
     CompilerError,
+
+    /// The `[package.metadata.nexus]` section of the guest manifest is invalid.
+    #[error("invalid guest manifest metadata: {0}")]
+    InvalidManifest(String),
+
+    /// The guest source could not be fetched or checked out from its git repository.
+    #[error("unable to fetch guest source from git: {0}")]
+    GitError(String),
+
+    /// The container the guest is built in could not be run or inspected.
+    #[error("unable to build guest in container: {0}")]
+    DockerError(String),
 }
 
 /// Errors that occur while reading from or writing to the input/output segments and tapes of the zkVM.
@@ -42,6 +66,18 @@ pub enum IOError {
     /// Error parsing the logging tape due to an encoding issue.
     #[error("encoding  error: {0}")]
     EncodingError(#[from] std::string::FromUtf8Error),
+
+    /// The public input or output types expected by the verifier differ from those the execution was proven with.
+    #[error("public i/o schema mismatch: proven with {proven:?}, but expected {expected:?}")]
+    IoSchemaMismatch {
+        proven: crate::traits::IoSchema,
+        expected: crate::traits::IoSchema,
+    },
+
+    /// The proof does not record the public input and output types it was proven with, so it cannot be checked
+    /// against expected values of known types.
+    #[error("proof does not record its public i/o schema")]
+    MissingIoSchema,
 }
 
 /// Errors that occur while manipulating host system file paths.
@@ -51,3 +87,140 @@ pub enum PathError {
     #[error("provided path has invalid encoding for use with filesystem")]
     EncodingError,
 }
+
+/// Errors that occur while checking a Merkle-committed public output.
+#[derive(Debug, Error)]
+pub enum OutputCommitmentError {
+    /// The proof was not generated with a committed output root.
+    #[error("proof does not carry a committed output root")]
+    NotCommitted,
+
+    /// The opened leaf does not match the committed output root.
+    #[error("leaf at index {0} does not open against the committed output root")]
+    InvalidOpening(usize),
+}
+
+/// Errors that occur while verifying a proof under a time budget, against an expected configuration, or of a fetched
+/// program.
+#[derive(Debug, Error)]
+pub enum VerifyError {
+    /// Verification did not complete within the given timeout.
+    #[error("verification did not complete within {0:?}")]
+    Timeout(std::time::Duration),
+
+    /// The proof was produced under a different proving configuration than expected.
+    #[error("proof was produced under a different proving configuration")]
+    ConfigMismatch,
+
+    /// The program could not be fetched by its digest.
+    #[error("unable to fetch program: {0}")]
+    FetchError(String),
+
+    /// The fetched program does not have the expected digest.
+    #[error("fetched program does not match the expected digest")]
+    ProgramDigestMismatch,
+
+    /// A chain of proofs to verify together was empty.
+    #[error("no proofs to verify")]
+    EmptyChain,
+
+    /// The public input of the proof at the given position in a chain does not follow from the one before it, or
+    /// for the first proof, from the expected public input.
+    #[error("public input of proof {0} does not match the preceding public output")]
+    ChainMismatch(usize),
+}
+
+/// Errors that occur when proving with a [`ProvingKey`](crate::stwo::seq::ProvingKey).
+#[derive(Debug, Error)]
+pub enum ProvingKeyError {
+    /// The proving key was derived for a different program.
+    #[error("proving key does not match the program")]
+    ProgramMismatch,
+
+    /// The execution is too long for the trace shape fixed by the proving key.
+    #[error("execution of {steps} steps does not fit within the proving key capacity of {capacity} steps")]
+    ExecutionTooLarge { steps: usize, capacity: usize },
+}
+
+/// Errors that occur while storing or retrieving proofs in a [`ProofStore`](crate::stwo::store::ProofStore).
+#[derive(Debug, Error)]
+pub enum ProofStoreError {
+    /// An error occurred reading or writing to the file system.
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+
+    /// The stored bytes are not a proof with the fingerprint it is stored under.
+    #[error("stored proof is corrupted")]
+    Corrupted,
+}
+
+/// Errors that occur while decoding a proof from its wire format, see
+/// [`Proof::from_bytes`](crate::stwo::seq::Proof::from_bytes).
+#[derive(Debug, Error)]
+pub enum ProofEncodingError {
+    /// The bytes do not start with the header of an encoded proof.
+    #[error("bytes are not an encoded proof")]
+    InvalidHeader,
+
+    /// The proof was encoded in a version of the wire format this SDK does not support.
+    #[error(
+        "proof is encoded in format version {found}, but only version {supported} is supported"
+    )]
+    UnsupportedVersion { found: u16, supported: u16 },
+
+    /// The proof was encoded with an unknown encoding.
+    #[error("proof is encoded with unknown encoding {0}")]
+    UnsupportedEncoding(u8),
+
+    /// The binary encoding of the proof is malformed.
+    #[error(transparent)]
+    BinaryError(#[from] postcard::Error),
+
+    /// The JSON encoding of the proof is malformed.
+    #[error(transparent)]
+    JsonError(#[from] serde_json::Error),
+}
+
+/// Errors that occur while proving on a remote [`ProvingService`](crate::stwo::network::ProvingService).
+#[derive(Debug, Error)]
+pub enum ProvingServiceError {
+    /// The service rejected the credentials of the request.
+    #[error("proving service rejected the request credentials")]
+    Unauthorized,
+
+    /// The service could not be reached, or responded unexpectedly.
+    #[error("unable to reach proving service: {0}")]
+    Transport(String),
+
+    /// The service failed to prove the execution.
+    #[error("proving service failed the job: {0}")]
+    JobFailed(String),
+
+    /// The service did not complete the job within its
+    /// [`job_timeout`](crate::stwo::network::ProvingService::job_timeout).
+    #[error("proving service did not complete the job within {0:?}")]
+    Timeout(std::time::Duration),
+
+    /// The proof returned by the service does not prove the execution as requested.
+    #[error("proving service returned an invalid proof")]
+    InvalidProof,
+}
+
+/// Diagnostics of proving aborted under a time budget, see [`Stwo::prove_within`](crate::stwo::seq::Stwo::prove_within).
+#[derive(Debug, Error)]
+#[error("proving aborted before {phase} after {elapsed:?}")]
+pub struct ProveAborted {
+    /// The phase that was about to start when the budget was found to be exhausted.
+    pub phase: nexus_core::stwo::ProvePhase,
+    /// The time spent before aborting.
+    pub elapsed: std::time::Duration,
+}
+
+/// Proving cancelled through a [`ProgressHandle`](crate::stwo::seq::ProgressHandle), see
+/// [`Stwo::prove_async`](crate::stwo::seq::Stwo::prove_async).
+#[derive(Debug, Error)]
+#[error("proving cancelled before {phase}")]
+pub struct ProveCancelled {
+    /// The phase that was about to start when cancellation was observed.
+    pub phase: nexus_core::stwo::ProvePhase,
+}
@@ -6,6 +6,7 @@
 pub enum KnownExitCodes {
     ExitSuccess = 0,
     ExitPanic = 1,
+    ExitAssertionFailed = 2,
 }
 
 /// Interface into proving with Stwo, a highly-efficient Circle STARK.
@@ -18,11 +19,23 @@ pub mod legacy;
 mod traits;
 pub use traits::*;
 
+mod associated_data;
+pub use associated_data::AssociatedData;
+
 /// Configure the dynamic compilation of guest programs.
 pub mod compile;
 
 /// Error types for SDK-specific interfaces.
 pub mod error;
 
+/// Merkle commitments to large public outputs, shared with the guest runtime (`nexus-rt`).
+pub use nexus_common::merkle;
+
+/// Deterministic randomness expanded from a seed, shared with the guest runtime (`nexus-rt`).
+pub use nexus_common::rand;
+
 /// Development macros for zkVM hosts.
 pub use nexus_sdk_macros;
+
+/// Derive [`IoType`] for public input and output types.
+pub use nexus_sdk_macros::IoType;
@@ -1,2 +1,8 @@
 /// Sequential (non-parallelized, non-distributed) proving for [Stwo](https://eprint.iacr.org/2024/278).
 pub mod seq;
+
+/// Content-addressed storage of proofs.
+pub mod store;
+
+/// Proving on a remote proving service.
+pub mod network;
@@ -0,0 +1,73 @@
+//! Proving on a remote proving service, see [`Stwo<Network>`](super::seq::Stwo).
+//!
+//! The SDK does not ship a transport: a [`ProvingService`] submits [`ProvingRequest`]s to a service and polls for
+//! their proofs over whatever protocol the service speaks, so that proving can be pointed at hosted or self-hosted
+//! provers alike.
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::error::ProvingServiceError;
+use crate::traits::IoSchema;
+
+use super::seq::ProvingConfig;
+
+/// The identifier a proving service assigns to a submitted job.
+pub type JobId = String;
+
+/// A request to prove an execution, as submitted to a [`ProvingService`].
+///
+/// The request carries the private input of the execution, so the service must be trusted with it.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ProvingRequest {
+    /// The program to prove.
+    pub elf: nexus_core::nvm::ElfFile,
+    /// The associated data to prove with.
+    pub ad: Vec<u8>,
+    /// The encoded public input.
+    pub public_input: Vec<u8>,
+    /// The encoded private input.
+    pub private_input: Vec<u8>,
+    /// The configuration to prove under.
+    pub config: ProvingConfig,
+    /// The i/o schema the proof must record, if any.
+    pub io_schema: Option<IoSchema>,
+    /// Credentials authenticating the request, as set by [`ProvingService::authenticate`].
+    pub credentials: Option<String>,
+}
+
+/// The status of a job submitted to a [`ProvingService`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JobStatus {
+    /// The job is queued or being proven.
+    Pending,
+    /// The job completed, with the proof in its wire format (see [`Proof::to_bytes`](super::seq::Proof::to_bytes)).
+    Completed(Vec<u8>),
+    /// The job failed, with a reason given by the service.
+    Failed(String),
+}
+
+/// A remote service proving executions on behalf of [`Stwo<Network>`](super::seq::Stwo).
+pub trait ProvingService: Send + Sync {
+    /// Submit a request, returning the identifier of the job proving it.
+    fn submit(&self, request: &ProvingRequest) -> Result<JobId, ProvingServiceError>;
+
+    /// Query the status of a submitted job.
+    fn poll(&self, job: &JobId) -> Result<JobStatus, ProvingServiceError>;
+
+    /// Authenticate a request before it is submitted, for example by setting its
+    /// [`credentials`](ProvingRequest::credentials). Requests are submitted unauthenticated by default.
+    fn authenticate(&self, _request: &mut ProvingRequest) -> Result<(), ProvingServiceError> {
+        Ok(())
+    }
+
+    /// How long to wait between polls of a pending job.
+    fn poll_interval(&self) -> Duration {
+        Duration::from_secs(1)
+    }
+
+    /// How long to wait for a submitted job to complete before giving up on it.
+    fn job_timeout(&self) -> Duration {
+        Duration::from_secs(60 * 60)
+    }
+}
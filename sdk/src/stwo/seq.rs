@@ -1,11 +1,28 @@
 use crate::compile::Compile;
 use crate::traits::*;
 
+use nexus_core::nvm::Trace;
+pub use nexus_core::stwo::ProverConfig;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::future::Future;
 use std::marker::PhantomData;
+use std::panic::AssertUnwindSafe;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
-use crate::error::{BuildError, ConfigurationError, IOError, PathError};
+use crate::error::{
+    BuildError, ConfigurationError, IOError, OutputCommitmentError, PathError, ProofEncodingError,
+    ProveAborted, ProveCancelled, ProvingKeyError, ProvingServiceError, VerifyError,
+};
+use crate::merkle;
+
+use super::network::{JobStatus, ProvingRequest, ProvingService};
 
 /// Errors that occur while proving using Stwo.
 #[derive(Debug, Error)]
@@ -14,6 +31,11 @@ pub enum Error {
     #[error(transparent)]
     ProvingError(#[from] nexus_core::stwo::ProvingError),
 
+    /// A zkVM execution could not be proven for a reason other than the proof itself failing, such as an execution
+    /// too long to prove or an invalid prover configuration.
+    #[error(transparent)]
+    ProveError(nexus_core::stwo::ProveError),
+
     /// An error occurred verifying a claimed proof of a zkVM execution.
     #[error(transparent)]
     VerificationError(#[from] nexus_core::stwo::VerificationError),
@@ -45,14 +67,63 @@ pub enum Error {
     /// An error occured configuring the prover.
     #[error(transparent)]
     ConfigurationError(#[from] ConfigurationError),
+
+    /// An error occured checking a Merkle-committed public output.
+    #[error(transparent)]
+    OutputCommitmentError(#[from] OutputCommitmentError),
+
+    /// An error occured verifying under a time budget.
+    #[error(transparent)]
+    VerifyError(#[from] VerifyError),
+
+    /// An error occured proving with a proving key.
+    #[error(transparent)]
+    ProvingKeyError(#[from] ProvingKeyError),
+
+    /// Proving did not complete within its time budget.
+    #[error(transparent)]
+    ProveAborted(#[from] ProveAborted),
+
+    /// An error occured decoding a proof from its wire format.
+    #[error(transparent)]
+    ProofEncodingError(#[from] ProofEncodingError),
+
+    /// An error occured proving on a remote proving service.
+    #[error(transparent)]
+    ProvingServiceError(#[from] ProvingServiceError),
+
+    /// Proving was cancelled through its [`ProgressHandle`].
+    #[error(transparent)]
+    ProveCancelled(#[from] ProveCancelled),
+}
+
+impl From<nexus_core::stwo::ProveError> for Error {
+    fn from(err: nexus_core::stwo::ProveError) -> Self {
+        // Failures of the proof itself are reported as they were before the prover grew other errors.
+        match err {
+            nexus_core::stwo::ProveError::Proving(err) => Self::ProvingError(err),
+            err => Self::ProveError(err),
+        }
+    }
 }
 
+/// The percentage of padding rows, or of the cycle limit, at and above which a [`Warning`] is raised.
+const WARNING_THRESHOLD_PERCENT: usize = 90;
+
 /// Prover for the Nexus zkVM, when using Stwo.
 pub struct Stwo<C: Compute = Local> {
     /// The program to be proven.
     pub elf: nexus_core::nvm::ElfFile,
     /// The associated data to prove with.
     pub ad: Vec<u8>,
+    /// The resource limits enforced while executing the program.
+    pub limits: nexus_core::nvm::ExecutionLimits,
+    fixed_shape: bool,
+    prover_config: ProverConfig,
+    service: Option<Arc<dyn ProvingService>>,
+    input_provider: Mutex<Option<Box<dyn nexus_core::nvm::InputProvider>>>,
+    host_functions: nexus_core::nvm::HostFunctions,
+    execute_zbb: bool,
     _compute: PhantomData<C>,
 }
 
@@ -61,6 +132,362 @@ pub struct Stwo<C: Compute = Local> {
 pub struct Proof {
     proof: nexus_core::stwo::Proof,
     memory_layout: nexus_core::nvm::internals::LinearMemoryLayout,
+    output_root: Option<merkle::Digest>,
+    config_digest: merkle::Digest,
+    public_input: Vec<u8>,
+    io_schema: Option<IoSchema>,
+}
+
+/// A proof within a pipeline of executions verified by [`Proof::verify_chained`], alongside the program it is
+/// expected to be a proof of.
+#[derive(Clone, Copy)]
+pub struct ChainLink<'a> {
+    /// The proof of the execution.
+    pub proof: &'a Proof,
+    /// The expected program.
+    pub elf: &'a nexus_core::nvm::ElfFile,
+    /// The expected associated data.
+    pub ad: &'a [u8],
+    /// The expected exit code.
+    pub exit_code: u32,
+}
+
+/// The configuration an execution was proven under, see [`Proof::verify_config`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProvingConfig {
+    /// The resource limits enforced while executing the program.
+    pub limits: nexus_core::nvm::ExecutionLimits,
+    /// Whether the proof is padded to a fixed shape.
+    pub fixed_shape: bool,
+    /// The parameters of the commitment scheme.
+    pub prover: ProverConfig,
+}
+
+impl ProvingConfig {
+    /// Compute a digest identifying the configuration.
+    pub fn digest(&self) -> Result<merkle::Digest, IOError> {
+        Ok(merkle::hash_leaf(&postcard::to_stdvec(self)?))
+    }
+}
+
+/// The version of the proof wire format written by [`Proof::to_bytes`], bumped whenever the layout of [`Proof`]
+/// changes.
+pub const PROOF_FORMAT_VERSION: u16 = 2;
+
+/// The magic bytes every encoded proof starts with.
+const PROOF_MAGIC: [u8; 4] = *b"NXPF";
+
+/// Length of the header of an encoded proof: the magic bytes, the little-endian format version and the encoding.
+const PROOF_HEADER_LEN: usize = PROOF_MAGIC.len() + 2 + 1;
+
+/// The encoding of the body of a proof in its wire format, see [`Proof::to_bytes_with`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ProofEncoding {
+    /// Compact binary encoding, with postcard.
+    #[default]
+    Binary,
+    /// Self-describing JSON encoding, for inspecting proofs with external tooling.
+    Json,
+}
+
+impl ProofEncoding {
+    fn tag(self) -> u8 {
+        match self {
+            Self::Binary => 0,
+            Self::Json => 1,
+        }
+    }
+
+    fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            0 => Some(Self::Binary),
+            1 => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Reusable proving data for a program, fixing the shape of its proofs (see [`Stwo::proving_key`]).
+///
+/// Only the program binding and the trace shape are serialized: the preprocessed trace is regenerated once on first
+/// use after deserialization, and then reused for every proof made with the key.
+#[derive(Serialize, Deserialize)]
+pub struct ProvingKey {
+    program_digest: merkle::Digest,
+    log_size: u32,
+    #[serde(skip)]
+    preprocessed_trace: OnceLock<nexus_core::stwo::PreprocessedTraces>,
+}
+
+/// Encode an input for the zkVM input tapes, cobs-encoded and padded to a whole number of words unless it encodes
+/// to nothing.
+fn encode_input<T: Serialize>(input: &T) -> Result<Vec<u8>, IOError> {
+    let mut encoded = postcard::to_stdvec(input)?;
+    if !encoded.is_empty() {
+        encoded = postcard::to_stdvec_cobs(input)?;
+        let padded_len = (encoded.len() + 3) & !3;
+
+        assert!(padded_len >= encoded.len());
+        encoded.resize(padded_len, 0x00); // cobs ignores 0x00 padding
+    }
+    Ok(encoded)
+}
+
+/// Encode an object to be read off the private input tape by `nexus_rt::read_private`, for use with
+/// [`Stwo::with_input_provider`].
+pub fn encode_private_input<T: Serialize>(input: &T) -> Result<Vec<u8>, IOError> {
+    encode_input(input)
+}
+
+/// Compute a digest identifying a program.
+///
+/// The digest covers only what is loaded into the zkVM: the code, the initial read-only and read-write memory, the
+/// entrypoint, and the precompile metadata. Debug sections and other build metadata of the ELF are not included, and
+/// guests built with [`Compiler`](crate::compile::Compiler) have their build paths remapped, so independent builds of
+/// identical source produce identical digests.
+pub fn program_digest(elf: &nexus_core::nvm::ElfFile) -> Result<merkle::Digest, IOError> {
+    Ok(merkle::hash_leaf(&postcard::to_stdvec(elf)?))
+}
+
+/// The context proofs are bound to besides their associated data, so that the configuration and the i/o schema they
+/// declare cannot be changed without invalidating them (see
+/// [`View::set_statement_context`](nexus_core::nvm::View::set_statement_context)).
+fn statement_context(
+    config_digest: &merkle::Digest,
+    io_schema: Option<&IoSchema>,
+) -> Result<Vec<u8>, IOError> {
+    Ok(merkle::hash_leaf(&postcard::to_stdvec(&(config_digest, io_schema))?).to_vec())
+}
+
+impl ProvingKey {
+    fn preprocessed_trace(&self) -> &nexus_core::stwo::PreprocessedTraces {
+        self.preprocessed_trace
+            .get_or_init(|| nexus_core::stwo::PreprocessedTraces::new(self.log_size))
+    }
+}
+
+/// A warning about a proven execution, which does not affect the validity of the proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Warning {
+    /// Most of the trace is padding, so the proof is larger than the execution requires.
+    TracePadding { steps: usize, rows: usize },
+    /// The execution came close to exhausting the configured cycle limit.
+    NearCycleLimit { cycles: usize, limit: usize },
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TracePadding { steps, rows } => write!(
+                f,
+                "trace is {}% padding ({steps} steps in {rows} rows)",
+                (rows - steps) * 100 / rows
+            ),
+            Self::NearCycleLimit { cycles, limit } => write!(
+                f,
+                "execution used {}% of the cycle limit ({cycles} of {limit} cycles)",
+                cycles * 100 / limit
+            ),
+        }
+    }
+}
+
+/// How serious a [`ProvingLint`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Proving is unaffected, but some work is spent on nothing.
+    Info,
+    /// Proving is likely to be much slower than needed, or to fail.
+    Warning,
+}
+
+/// A likely misconfiguration of proving found by [`Stwo::lint`], whose message is given by its [`Display`](fmt::Display).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProvingLint {
+    /// Most of the planned trace is padding, so the proof is larger than the execution requires.
+    ExcessivePadding { steps: usize, rows: usize },
+    /// The execution comes close to exhausting the configured cycle limit.
+    NearCycleLimit { cycles: usize, limit: usize },
+    /// The execution never uses the chip with the given type name, whose columns are nonetheless committed to.
+    UnusedChip(&'static str),
+}
+
+impl ProvingLint {
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::ExcessivePadding { .. } | Self::NearCycleLimit { .. } => Severity::Warning,
+            Self::UnusedChip(_) => Severity::Info,
+        }
+    }
+}
+
+impl From<Warning> for ProvingLint {
+    fn from(warning: Warning) -> Self {
+        match warning {
+            Warning::TracePadding { steps, rows } => Self::ExcessivePadding { steps, rows },
+            Warning::NearCycleLimit { cycles, limit } => Self::NearCycleLimit { cycles, limit },
+        }
+    }
+}
+
+impl fmt::Display for ProvingLint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ExcessivePadding { steps, rows } => write!(
+                f,
+                "planned trace is {}% padding ({steps} steps in {rows} rows)",
+                (rows - steps) * 100 / rows
+            ),
+            Self::NearCycleLimit { cycles, limit } => write!(
+                f,
+                "execution uses {}% of the cycle limit ({cycles} of {limit} cycles)",
+                cycles * 100 / limit
+            ),
+            Self::UnusedChip(chip) => write!(f, "{chip} is never used by the execution"),
+        }
+    }
+}
+
+/// The result of proving an execution using [`Stwo::prove_full`].
+pub struct ProveOutput {
+    /// A view of the execution output.
+    pub view: nexus_core::nvm::View,
+    /// The proof of the execution.
+    pub proof: Proof,
+    /// Warnings raised while proving the execution.
+    pub warnings: Vec<Warning>,
+}
+
+/// The projected cost of proving an execution, found by [`Stwo::estimate`] without proving.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CostReport {
+    /// The number of cycles (steps) of the execution.
+    pub cycles: usize,
+    /// The log size of the main trace proving the execution.
+    pub log_size: u32,
+    /// The number of trace rows taken by each opcode, by mnemonic.
+    pub opcode_rows: BTreeMap<String, usize>,
+    /// The number of retired instructions in each class.
+    pub instruction_mix: HashMap<nexus_core::nvm::InstrClass, u64>,
+    /// A lower bound on the memory needed for proving, see [`ProverConfig::committed_trace_bytes`].
+    pub trace_bytes: usize,
+}
+
+impl CostReport {
+    /// The number of rows of the main trace, including padding.
+    pub fn rows(&self) -> usize {
+        1 << self.log_size
+    }
+
+    /// Project the time proving takes from the time `reference_time` taken to prove the execution estimated by
+    /// `reference`, on the same machine and under the same configuration.
+    ///
+    /// Proving time grows quasi-linearly in the number of rows, so a reference proof of a few thousand cycles is
+    /// enough to size much larger ones.
+    pub fn project_proving_time(
+        &self,
+        reference: &CostReport,
+        reference_time: Duration,
+    ) -> Duration {
+        let scale = (self.rows() as f64 * self.log_size as f64)
+            / (reference.rows() as f64 * reference.log_size as f64);
+        reference_time.mul_f64(scale)
+    }
+}
+
+/// A snapshot of the progress of proving started with [`Stwo::prove_async`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    /// The phase being run.
+    pub phase: nexus_core::stwo::ProvePhase,
+    /// The number of steps executed, once the execution has completed.
+    pub steps: Option<usize>,
+    /// The number of rows of the main trace, once proving has started.
+    pub rows: Option<usize>,
+}
+
+/// A handle to follow and cancel proving started with [`Stwo::prove_async`].
+///
+/// Cancellation is checked cooperatively between phases, as the budget of [`Stwo::prove_within`] is, so a phase that
+/// has already started runs to completion.
+#[derive(Clone)]
+pub struct ProgressHandle {
+    progress: Arc<Mutex<Progress>>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl ProgressHandle {
+    fn new() -> Self {
+        Self {
+            progress: Arc::new(Mutex::new(Progress {
+                phase: nexus_core::stwo::ProvePhase::Execution,
+                steps: None,
+                rows: None,
+            })),
+            cancelled: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// The progress proving has reached.
+    pub fn progress(&self) -> Progress {
+        *self.progress.lock().unwrap()
+    }
+
+    /// Request proving to stop before its next phase, failing with [`Error::ProveCancelled`].
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether cancellation was requested.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    fn set_steps(&self, steps: usize) {
+        self.progress.lock().unwrap().steps = Some(steps);
+    }
+}
+
+impl nexus_core::stwo::ProveMonitor for ProgressHandle {
+    fn enter_phase(&self, phase: nexus_core::stwo::ProvePhase, log_size: u32) -> bool {
+        let mut progress = self.progress.lock().unwrap();
+        progress.phase = phase;
+        progress.rows = Some(1 << log_size);
+        !self.is_cancelled()
+    }
+}
+
+type ProveResult = Result<(nexus_core::nvm::View, Proof), Error>;
+
+#[derive(Default)]
+struct ProveFutureState {
+    result: Option<std::thread::Result<ProveResult>>,
+    waker: Option<Waker>,
+}
+
+/// The result of proving started with [`Stwo::prove_async`], resolving once the background proving thread finishes.
+///
+/// The future doesn't depend on any async runtime, and proving runs to completion (or cancellation) whether or not
+/// it is polled.
+pub struct ProveFuture {
+    state: Arc<Mutex<ProveFutureState>>,
+}
+
+impl Future for ProveFuture {
+    type Output = ProveResult;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.lock().unwrap();
+        match state.result.take() {
+            Some(Ok(result)) => Poll::Ready(result),
+            // Propagate panics of the proving thread to the task awaiting it.
+            Some(Err(panic)) => std::panic::resume_unwind(panic),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
 }
 
 impl<C: Compute> ByGuestCompilation for Stwo<C>
@@ -72,7 +499,10 @@ where
     fn compile(compiler: &mut impl Compile) -> Result<Self, <Self as Prover>::Error> {
         let elf_path = compiler.build()?;
 
-        Self::new_from_file(&elf_path)
+        let mut prover = Self::new_from_file(&elf_path)?;
+        prover.limits = compiler.limits();
+
+        Ok(prover)
     }
 }
 
@@ -83,11 +513,7 @@ impl Prover for Stwo<Local> {
 
     /// Construct a new proving instance.
     fn new(elf: &nexus_core::nvm::ElfFile) -> Result<Self, <Self as Prover>::Error> {
-        Ok(Self {
-            elf: elf.clone(),
-            ad: Vec::new(),
-            _compute: PhantomData,
-        })
+        Ok(Self::from_elf(elf))
     }
 
     /// Set the associated data bytes to be bound into the proof.
@@ -102,76 +528,1333 @@ impl Prover for Stwo<Local> {
         private_input: &S,
         public_input: &T,
     ) -> Result<Self::View, <Self as Prover>::Error> {
-        let mut private_encoded = postcard::to_stdvec(&private_input).map_err(IOError::from)?;
-        if !private_encoded.is_empty() {
-            private_encoded = postcard::to_stdvec_cobs(&private_input).map_err(IOError::from)?;
-            let private_padded_len = (private_encoded.len() + 3) & !3;
+        let private_encoded = encode_input(private_input)?;
+        let public_encoded = encode_input(public_input)?;
 
-            assert!(private_padded_len >= private_encoded.len());
-            private_encoded.resize(private_padded_len, 0x00); // cobs ignores 0x00 padding
-        }
+        self.run_encoded(private_encoded.as_slice(), public_encoded.as_slice())
+    }
 
-        let mut public_encoded = postcard::to_stdvec(&public_input).map_err(IOError::from)?;
-        if !public_encoded.is_empty() {
-            public_encoded = postcard::to_stdvec_cobs(&public_input).map_err(IOError::from)?;
-            let public_padded_len = (public_encoded.len() + 3) & !3;
+    /// Run the zkVM and return a verifiable proof, along with a view of the execution output.
+    fn prove(self) -> Result<(Self::View, Self::Proof), <Self as Prover>::Error> {
+        let ProveOutput { view, proof, .. } = self.prove_full()?;
+        Ok((view, proof))
+    }
 
-            assert!(public_padded_len >= public_encoded.len());
-            public_encoded.resize(public_padded_len, 0x00); // cobs ignores 0x00 padding
-        }
+    /// Run the zkVM on private input of type `S` and public input of type `T` and return a verifiable proof, along with a view of the execution output.
+    fn prove_with_input<S: Serialize + Sized, T: Serialize + DeserializeOwned + Sized>(
+        self,
+        private_input: &S,
+        public_input: &T,
+    ) -> Result<(Self::View, Self::Proof), <Self as Prover>::Error> {
+        let ProveOutput { view, proof, .. } =
+            self.prove_full_with_input(private_input, public_input)?;
+        Ok((view, proof))
+    }
+}
+
+impl Prover for Stwo<Network> {
+    type Proof = Proof;
+    type View = nexus_core::nvm::View;
+    type Error = Error;
 
-        let (view, _) = nexus_core::nvm::k_trace(
+    /// Construct a new proving instance, to be pointed at a proving service with [`Stwo::with_service`].
+    fn new(elf: &nexus_core::nvm::ElfFile) -> Result<Self, <Self as Prover>::Error> {
+        Ok(Self::from_elf(elf))
+    }
+
+    /// Set the associated data bytes to be bound into the proof.
+    fn set_associated_data(&mut self, ad: &[u8]) -> Result<(), <Self as Prover>::Error> {
+        self.ad = ad.to_vec();
+        Ok(())
+    }
+
+    /// Run the zkVM locally on private input of type `S` and public input of type `T` and return a view of the
+    /// execution output.
+    fn run_with_input<S: Serialize + Sized, T: Serialize + DeserializeOwned + Sized>(
+        &self,
+        private_input: &S,
+        public_input: &T,
+    ) -> Result<Self::View, <Self as Prover>::Error> {
+        let (view, _) = nexus_core::nvm::k_trace_with_limits(
             self.elf.clone(),
             self.ad.as_slice(),
-            public_encoded.as_slice(),
-            private_encoded.as_slice(),
+            encode_input(public_input)?.as_slice(),
+            encode_input(private_input)?.as_slice(),
             1,
-        )?; // todo: run without tracing?
+            &self.limits,
+        )?;
 
         Ok(view)
     }
 
-    /// Run the zkVM on private input of type `S` and public input of type `T` and return a verifiable proof, along with a view of the execution output.
+    /// Prove the execution on private input of type `S` and public input of type `T` on the proving service, and
+    /// return the proof along with a view of the execution output.
+    ///
+    /// The execution is also run locally to obtain the view, and the proof returned by the service is only accepted
+    /// once it verifies against that view under the requested [`ProverConfig`], failing with
+    /// [`ProvingServiceError::InvalidProof`] otherwise. Jobs not completed within the
+    /// [`job_timeout`](ProvingService::job_timeout) of the service fail with [`ProvingServiceError::Timeout`].
     fn prove_with_input<S: Serialize + Sized, T: Serialize + DeserializeOwned + Sized>(
         self,
         private_input: &S,
         public_input: &T,
     ) -> Result<(Self::View, Self::Proof), <Self as Prover>::Error> {
-        let mut private_encoded = postcard::to_stdvec(&private_input).map_err(IOError::from)?;
-        if !private_encoded.is_empty() {
-            private_encoded = postcard::to_stdvec_cobs(&private_input).map_err(IOError::from)?;
-            let private_padded_len = (private_encoded.len() + 3) & !3;
+        let service = self
+            .service
+            .clone()
+            .ok_or(ConfigurationError::NotYetConfigured)?;
+
+        let private_encoded = encode_input(private_input)?;
+        let public_encoded = encode_input(public_input)?;
+
+        let (view, _) = nexus_core::nvm::k_trace_with_limits(
+            self.elf.clone(),
+            self.ad.as_slice(),
+            public_encoded.as_slice(),
+            private_encoded.as_slice(),
+            1,
+            &self.limits,
+        )?;
+
+        let mut request = ProvingRequest {
+            elf: self.elf.clone(),
+            ad: self.ad.clone(),
+            public_input: public_encoded,
+            private_input: private_encoded,
+            config: self.proving_config(),
+            io_schema: None,
+            credentials: None,
+        };
+        service.authenticate(&mut request)?;
+
+        let job = service.submit(&request)?;
+        let timeout = service.job_timeout();
+        let start = Instant::now();
+        let bytes = loop {
+            match service.poll(&job)? {
+                JobStatus::Pending => {
+                    let elapsed = start.elapsed();
+                    if elapsed >= timeout {
+                        return Err(ProvingServiceError::Timeout(timeout).into());
+                    }
+                    std::thread::sleep(service.poll_interval().min(timeout - elapsed));
+                }
+                JobStatus::Completed(bytes) => break bytes,
+                JobStatus::Failed(reason) => {
+                    return Err(ProvingServiceError::JobFailed(reason).into())
+                }
+            }
+        };
 
-            assert!(private_padded_len >= private_encoded.len());
-            private_encoded.resize(private_padded_len, 0x00); // cobs ignores 0x00 padding
+        let proof = Proof::from_bytes(&bytes).map_err(|_| ProvingServiceError::InvalidProof)?;
+        // The proof is verified under the parameters it carries, so they must be checked against the requested ones
+        // for the service not to be able to weaken them.
+        if proof.config_digest != request.config.digest()?
+            || proof.proof.config
+                != (ProverConfig {
+                    debug_constraints: false,
+                    ..request.config.prover
+                })
+            || proof.public_input != request.public_input
+            || proof.io_schema != request.io_schema
+            || proof.verify(&view).is_err()
+        {
+            return Err(ProvingServiceError::InvalidProof.into());
         }
 
-        let mut public_encoded = postcard::to_stdvec(&public_input).map_err(IOError::from)?;
-        if !public_encoded.is_empty() {
-            public_encoded = postcard::to_stdvec_cobs(&public_input).map_err(IOError::from)?;
-            let public_padded_len = (public_encoded.len() + 3) & !3;
+        Ok((view, proof))
+    }
+}
 
-            assert!(public_padded_len >= public_encoded.len());
-            public_encoded.resize(public_padded_len, 0x00); // cobs ignores 0x00 padding
+impl Stwo<Network> {
+    /// Prove on the given proving service.
+    pub fn with_service(mut self, service: impl ProvingService + 'static) -> Self {
+        self.service = Some(Arc::new(service));
+        self
+    }
+}
+
+impl<C: Compute> Stwo<C> {
+    /// A prover for `elf` under the default configuration.
+    fn from_elf(elf: &nexus_core::nvm::ElfFile) -> Self {
+        Self {
+            elf: elf.clone(),
+            ad: Vec::new(),
+            limits: nexus_core::nvm::ExecutionLimits::default(),
+            fixed_shape: false,
+            prover_config: ProverConfig::default(),
+            service: None,
+            input_provider: Mutex::new(None),
+            host_functions: nexus_core::nvm::HostFunctions::default(),
+            execute_zbb: false,
+            _compute: PhantomData,
         }
+    }
 
-        let (view, trace) = nexus_core::nvm::k_trace(
-            self.elf.clone(),
+    /// Force proofs to be padded to a worst-case shape, so that the proof size does not depend on the (possibly secret) input.
+    ///
+    /// The shape is sized by the configured cycle limit (see [`Stwo::with_limits`]), without which proving fails with
+    /// [`ConfigurationError::UnboundedShape`]. Executions that do not fit within it still prove, but with a larger proof.
+    pub fn with_fixed_shape(mut self, fixed_shape: bool) -> Self {
+        self.fixed_shape = fixed_shape;
+        self
+    }
+
+    /// Set the resource limits enforced while executing the program, overriding those declared by the guest.
+    ///
+    /// An execution exceeding a limit fails with a [`VMError`](nexus_core::nvm::VMError) naming the limit and the usage
+    /// before any trace is generated, and the view of an execution reports how close it came to each limit, see
+    /// [`View::limit_usage`](nexus_core::nvm::View::limit_usage).
+    pub fn with_limits(mut self, limits: nexus_core::nvm::ExecutionLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Set the parameters of the commitment scheme, trading proving time and proof size for security.
+    ///
+    /// The default parameters are meant for testing and provide little security, see [`ProverConfig::default`].
+    pub fn with_prover_config(mut self, config: ProverConfig) -> Self {
+        self.prover_config = config;
+        self
+    }
+
+    /// The configuration proofs are produced under.
+    pub fn proving_config(&self) -> ProvingConfig {
+        ProvingConfig {
+            limits: self.limits.clone(),
+            fixed_shape: self.fixed_shape,
+            prover: self.prover_config,
+        }
+    }
+
+    /// The static disassembly of the program, as the address and decoded mnemonic of every instruction in its code
+    /// section, see [`ElfFile::disassemble`](nexus_core::nvm::ElfFile::disassemble).
+    pub fn disassemble(&self) -> Vec<(u32, String)> {
+        self.elf.disassemble()
+    }
+
+    /// The log size proofs are padded to under a fixed shape, sized by the cycle limit.
+    fn fixed_shape_log_size(&self) -> Result<u32, Error> {
+        self.limits
+            .cycle_limit
+            .map(|limit| limit.next_power_of_two().ilog2())
+            .ok_or_else(|| ConfigurationError::UnboundedShape.into())
+    }
+}
+
+impl Stwo<Local> {
+    /// Extend the private input tape on demand with chunks from `provider`, once the private input given to an
+    /// execution is exhausted. The guest reads the objects on the tape one at a time with `nexus_rt::read_private`,
+    /// and each object must be encoded with [`encode_private_input`].
+    ///
+    /// The provider is consumed by the next execution of the program. Only the bytes the guest reads are replayed
+    /// when proving, and they are recorded in the [`InputTranscript`](nexus_core::nvm::InputTranscript) of the view.
+    pub fn with_input_provider(
+        self,
+        provider: impl nexus_core::nvm::InputProvider + 'static,
+    ) -> Self {
+        *self
+            .input_provider
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some(Box::new(provider));
+        self
+    }
+
+    /// Answer calls the guest makes with the syscall code `code`, which must be in
+    /// [`HOST_CALL_CODES`](nexus_core::nvm::HOST_CALL_CODES), with `handler`.
+    ///
+    /// The handler receives the arguments of the call from registers `a0` through `a6` and returns its result in
+    /// `a0`. Each call is recorded in the view, see [`View::host_calls`](nexus_core::nvm::View::host_calls).
+    /// Executions that call host functions can be run, but not yet proven: proving them fails with
+    /// [`ConfigurationError::UnprovableHostCalls`].
+    pub fn with_syscall(
+        mut self,
+        code: u32,
+        handler: impl nexus_core::nvm::HostFunction + 'static,
+    ) -> Result<Self, Error> {
+        self.host_functions.register(code, handler)?;
+        Ok(self)
+    }
+
+    /// Switch to emulation-only mode, in which Zbb bit-manipulation instructions are executed rather than rejected.
+    ///
+    /// No chip constrains Zbb instructions, so in this mode executions are only run on the first-pass emulator, and
+    /// every proving (or cost estimating) operation fails with [`ConfigurationError::EmulationOnly`] before the
+    /// program is executed.
+    pub fn with_zbb_emulation(mut self) -> Self {
+        self.execute_zbb = true;
+        self
+    }
+
+    /// Fail with [`ConfigurationError::EmulationOnly`] in emulation-only mode, see [`Stwo::with_zbb_emulation`].
+    fn check_provable(&self) -> Result<(), Error> {
+        if self.execute_zbb {
+            return Err(ConfigurationError::EmulationOnly.into());
+        }
+        Ok(())
+    }
+
+    /// Run the program on private input of type `S` and public input of type `T` under a debugger, serving the GDB
+    /// remote serial protocol on `addr`, and return the exit code of the guest if it exited while being debugged.
+    ///
+    /// Blocks until a debugger connects, for example `gdb` loading the guest ELF and running
+    /// `target remote 127.0.0.1:9001` for `addr` being `"127.0.0.1:9001"`. The debugged execution is not traced,
+    /// so it cannot be proven.
+    #[cfg(feature = "gdb")]
+    pub fn debug<S: Serialize + Sized, T: Serialize + DeserializeOwned + Sized>(
+        &self,
+        addr: impl std::net::ToSocketAddrs,
+        private_input: &S,
+        public_input: &T,
+    ) -> Result<Option<u32>, Error> {
+        Ok(nexus_core::nvm::debug_with_hooks(
+            &self.elf,
+            encode_input(public_input)?.as_slice(),
+            encode_input(private_input)?.as_slice(),
+            self.hooks(),
+            &self.limits,
+            addr,
+        )?)
+    }
+
+    /// Run the zkVM on private input of type `S` and public input of type `T` without proving it, and write the
+    /// execution trace to `writer` in the format described in [`TraceExport`](nexus_core::nvm::TraceExport), for
+    /// analysis by external tools.
+    pub fn export_trace<S: Serialize + Sized, T: Serialize + DeserializeOwned + Sized>(
+        &self,
+        private_input: &S,
+        public_input: &T,
+        writer: impl std::io::Write,
+    ) -> Result<nexus_core::nvm::View, Error> {
+        let (view, trace) = self.trace(
+            self.ad.as_slice(),
+            encode_input(public_input)?.as_slice(),
+            encode_input(private_input)?.as_slice(),
+        )?;
+        nexus_core::nvm::export_trace(&trace, writer)?;
+        Ok(view)
+    }
+
+    /// Run the zkVM on already encoded private and public input (see [`encode_private_input`]), such as read from
+    /// files, and return a view of the execution output.
+    pub fn run_encoded(
+        &self,
+        private_encoded: &[u8],
+        public_encoded: &[u8],
+    ) -> Result<nexus_core::nvm::View, Error> {
+        if self.execute_zbb {
+            return Ok(nexus_core::nvm::emulate_with_hooks(
+                &self.elf,
+                public_encoded,
+                private_encoded,
+                self.hooks(),
+                &self.limits,
+                true,
+            )?);
+        }
+
+        let (view, _) = self.trace(self.ad.as_slice(), public_encoded, private_encoded)?;
+        Ok(view)
+    }
+
+    /// Run the zkVM and return a verifiable proof, along with a view of the execution output and any warnings.
+    pub fn prove_full(self) -> Result<ProveOutput, Error> {
+        self.prove_full_with_input::<(), ()>(&(), &())
+    }
+
+    /// Run the zkVM on private input of type `S` and public input of type `T` and return a verifiable proof, along
+    /// with a view of the execution output and any warnings.
+    pub fn prove_full_with_input<S: Serialize + Sized, T: Serialize + DeserializeOwned + Sized>(
+        self,
+        private_input: &S,
+        public_input: &T,
+    ) -> Result<ProveOutput, Error> {
+        let private_encoded = encode_input(private_input)?;
+        let public_encoded = encode_input(public_input)?;
+
+        self.prove_encoded(
+            public_encoded.as_slice(),
+            private_encoded.as_slice(),
+            self.ad.as_slice(),
+            None,
+            None,
+            None,
+        )
+    }
+
+    /// Run the zkVM and return a verifiable proof, along with a view of the execution output, but give up with
+    /// [`Error::ProveAborted`] if running and proving take longer than `budget`.
+    ///
+    /// The budget is checked cooperatively between phases rather than by interrupting them, so proving may overrun it
+    /// by at most the duration of a single phase. On abort, [`ProveAborted`] describes the phase that was reached.
+    pub fn prove_within(self, budget: Duration) -> Result<(nexus_core::nvm::View, Proof), Error> {
+        self.prove_within_with_input::<(), ()>(&(), &(), budget)
+    }
+
+    /// Run the zkVM on private input of type `S` and public input of type `T` and return a verifiable proof, along
+    /// with a view of the execution output, but give up if running and proving take longer than `budget`, as
+    /// [`Stwo::prove_within`].
+    pub fn prove_within_with_input<
+        S: Serialize + Sized,
+        T: Serialize + DeserializeOwned + Sized,
+    >(
+        self,
+        private_input: &S,
+        public_input: &T,
+        budget: Duration,
+    ) -> Result<(nexus_core::nvm::View, Proof), Error> {
+        let private_encoded = encode_input(private_input)?;
+        let public_encoded = encode_input(public_input)?;
+
+        let ProveOutput { view, proof, .. } = self.prove_encoded(
+            public_encoded.as_slice(),
+            private_encoded.as_slice(),
+            self.ad.as_slice(),
+            Some(budget),
+            None,
+            None,
+        )?;
+        Ok((view, proof))
+    }
+
+    /// Run the zkVM and prove the execution on a background thread, returning a future resolving to a verifiable proof
+    /// and a view of the execution output, along with a handle to follow and cancel proving.
+    pub fn prove_async(self) -> Result<(ProveFuture, ProgressHandle), Error> {
+        self.prove_async_with_input::<(), ()>(&(), &())
+    }
+
+    /// Run the zkVM on private input of type `S` and public input of type `T` and prove the execution on a background
+    /// thread, as [`Stwo::prove_async`].
+    pub fn prove_async_with_input<S: Serialize + Sized, T: Serialize + DeserializeOwned + Sized>(
+        self,
+        private_input: &S,
+        public_input: &T,
+    ) -> Result<(ProveFuture, ProgressHandle), Error> {
+        self.check_provable()?;
+        let private_encoded = encode_input(private_input)?;
+        let public_encoded = encode_input(public_input)?;
+
+        let progress = ProgressHandle::new();
+        let state = Arc::new(Mutex::new(ProveFutureState::default()));
+
+        let (thread_progress, thread_state) = (progress.clone(), state.clone());
+        std::thread::spawn(move || {
+            let result = std::panic::catch_unwind(AssertUnwindSafe(|| -> ProveResult {
+                let ProveOutput { view, proof, .. } = self.prove_encoded(
+                    public_encoded.as_slice(),
+                    private_encoded.as_slice(),
+                    self.ad.as_slice(),
+                    None,
+                    Some(&thread_progress),
+                    None,
+                )?;
+                Ok((view, proof))
+            }));
+
+            let mut state = thread_state.lock().unwrap();
+            state.result = Some(result);
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+        });
+
+        Ok((ProveFuture { state }, progress))
+    }
+
+    /// Derive a proving key for the program, which can be reused to prove many executions using [`Stwo::prove_with_key`].
+    ///
+    /// The key fixes the shape of the proofs as [`Stwo::with_fixed_shape`] does, so it needs a cycle limit to be
+    /// sized by and fails with [`ConfigurationError::UnboundedShape`] otherwise.
+    pub fn proving_key(&self) -> Result<ProvingKey, Error> {
+        let program_log_size = self.elf.instructions.len().next_power_of_two().ilog2();
+        let log_size = self
+            .fixed_shape_log_size()?
+            .max(program_log_size)
+            .max(nexus_core::stwo::PreprocessedTraces::MIN_LOG_SIZE);
+
+        let key = ProvingKey {
+            program_digest: program_digest(&self.elf)?,
+            log_size,
+            preprocessed_trace: OnceLock::new(),
+        };
+        key.preprocessed_trace();
+
+        Ok(key)
+    }
+
+    /// Run the zkVM on private input of type `S` and public input of type `T` and return a verifiable proof, along
+    /// with a view of the execution output, reusing the proving data in `key`.
+    pub fn prove_with_key<S: Serialize + Sized, T: Serialize + DeserializeOwned + Sized>(
+        self,
+        key: &ProvingKey,
+        private_input: &S,
+        public_input: &T,
+    ) -> Result<(nexus_core::nvm::View, Proof), Error> {
+        self.check_provable()?;
+        if program_digest(&self.elf)? != key.program_digest {
+            return Err(ProvingKeyError::ProgramMismatch.into());
+        }
+
+        self.prove_encoded_with_key(
+            key,
+            encode_input(private_input)?,
+            encode_input(public_input)?,
+        )
+    }
+
+    /// Prove executions of the program on each pair of private and public input in `inputs`, as
+    /// [`Stwo::prove_with_key`], returning the views and proofs in the order of `inputs`.
+    ///
+    /// The program is checked against `key` once, and its preprocessed trace is shared by every proof. Executions are
+    /// proven one after the other, each already spreading its work over the prover's thread pool. An input provider
+    /// cannot be shared across executions, so proving a batch with one set fails with
+    /// [`ConfigurationError::NotApplicableOperation`].
+    pub fn prove_batch<S, T>(
+        self,
+        key: &ProvingKey,
+        inputs: &[(S, T)],
+    ) -> Result<Vec<(nexus_core::nvm::View, Proof)>, Error>
+    where
+        S: Serialize,
+        T: Serialize + DeserializeOwned,
+    {
+        self.check_provable()?;
+        if program_digest(&self.elf)? != key.program_digest {
+            return Err(ProvingKeyError::ProgramMismatch.into());
+        }
+        if self
+            .input_provider
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_some()
+        {
+            return Err(ConfigurationError::NotApplicableOperation.into());
+        }
+
+        inputs
+            .iter()
+            .map(|(private_input, public_input)| {
+                self.prove_encoded_with_key(
+                    key,
+                    encode_input(private_input)?,
+                    encode_input(public_input)?,
+                )
+            })
+            .collect()
+    }
+
+    /// Prove an execution on encoded input with the proving data in `key`, which the program was checked against.
+    fn prove_encoded_with_key(
+        &self,
+        key: &ProvingKey,
+        private_encoded: Vec<u8>,
+        public_encoded: Vec<u8>,
+    ) -> Result<(nexus_core::nvm::View, Proof), Error> {
+        let (mut view, trace) = self.trace(
             self.ad.as_slice(),
             public_encoded.as_slice(),
             private_encoded.as_slice(),
-            1,
         )?;
-        let proof = nexus_core::stwo::prove(&trace, &view)?;
+        if !view.host_calls().is_empty() {
+            return Err(ConfigurationError::UnprovableHostCalls(view.host_calls().len()).into());
+        }
+
+        let capacity = 1 << key.log_size;
+        if trace.get_num_steps() > capacity {
+            return Err(ProvingKeyError::ExecutionTooLarge {
+                steps: trace.get_num_steps(),
+                capacity,
+            }
+            .into());
+        }
+
+        // The key fixes the shape of the proof.
+        let config = ProvingConfig {
+            fixed_shape: true,
+            ..self.proving_config()
+        };
+        let config_digest = config.digest()?;
+        view.set_statement_context(statement_context(&config_digest, None)?);
+
+        let proof = nexus_core::stwo::prove_with_preprocessed_trace(
+            &trace,
+            &view,
+            key.preprocessed_trace(),
+            &self.prover_config,
+        )?;
 
         Ok((
             view,
             Proof {
                 proof,
                 memory_layout: trace.memory_layout,
+                output_root: None,
+                config_digest,
+                public_input: public_encoded,
+                io_schema: None,
             },
         ))
     }
+
+    /// Re-run and prove an execution from a transcript of its inputs (see [`Viewable::input_transcript`]).
+    ///
+    /// The replayed execution is identical to the one the transcript was recorded from, and so is its proof when
+    /// `io_schema` is the schema the original proof recorded. Without a schema, the proof can only be checked through
+    /// [`Verifiable::verify`].
+    pub fn replay(
+        self,
+        transcript: &nexus_core::nvm::InputTranscript,
+        io_schema: Option<IoSchema>,
+    ) -> Result<(nexus_core::nvm::View, Proof), Error> {
+        let ProveOutput { view, proof, .. } = self.prove_encoded(
+            transcript.public_input.as_slice(),
+            transcript.private_input.as_slice(),
+            transcript.associated_data.as_slice(),
+            None,
+            None,
+            io_schema,
+        )?;
+        Ok((view, proof))
+    }
+
+    /// Take the input provider (if any) and the host functions, for the next execution of the program.
+    fn hooks(&self) -> nexus_core::nvm::ExecutionHooks {
+        nexus_core::nvm::ExecutionHooks {
+            input_provider: self
+                .input_provider
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .take(),
+            host_functions: self.host_functions.clone(),
+        }
+    }
+
+    /// Execute the program, drawing on the input provider (if any) once the private input is exhausted, and answering
+    /// calls to host functions.
+    fn trace(
+        &self,
+        ad: &[u8],
+        public_encoded: &[u8],
+        private_encoded: &[u8],
+    ) -> Result<(nexus_core::nvm::View, nexus_core::nvm::UniformTrace), nexus_core::nvm::VMError>
+    {
+        nexus_core::nvm::k_trace_with_hooks(
+            self.elf.clone(),
+            ad,
+            public_encoded,
+            private_encoded,
+            self.hooks(),
+            1,
+            &self.limits,
+        )
+    }
+
+    fn prove_encoded(
+        &self,
+        public_encoded: &[u8],
+        private_encoded: &[u8],
+        ad: &[u8],
+        budget: Option<Duration>,
+        progress: Option<&ProgressHandle>,
+        io_schema: Option<IoSchema>,
+    ) -> Result<ProveOutput, Error> {
+        self.check_provable()?;
+        let start = Instant::now();
+        let aborted = |phase| ProveAborted {
+            phase,
+            elapsed: start.elapsed(),
+        };
+        if budget.is_some_and(|budget| start.elapsed() >= budget) {
+            return Err(aborted(nexus_core::stwo::ProvePhase::Execution).into());
+        }
+        if progress.is_some_and(ProgressHandle::is_cancelled) {
+            return Err(ProveCancelled {
+                phase: nexus_core::stwo::ProvePhase::Execution,
+            }
+            .into());
+        }
+
+        let (mut view, trace) = {
+            #[cfg(feature = "tracing")]
+            let _span = tracing::info_span!("execute").entered();
+            self.trace(ad, public_encoded, private_encoded)?
+        };
+        if !view.host_calls().is_empty() {
+            return Err(ConfigurationError::UnprovableHostCalls(view.host_calls().len()).into());
+        }
+        #[cfg(feature = "tracing")]
+        tracing::info!(steps = trace.get_num_steps(), "guest executed");
+        if let Some(progress) = progress {
+            progress.set_steps(trace.get_num_steps());
+        }
+
+        let min_log_size = if self.fixed_shape {
+            self.fixed_shape_log_size()?
+        } else {
+            nexus_core::stwo::PreprocessedTraces::MIN_LOG_SIZE
+        };
+        let config_digest = self.proving_config().digest()?;
+        view.set_statement_context(statement_context(&config_digest, io_schema.as_ref())?);
+
+        let deadline = budget.map(|budget| start + budget);
+        let proof = match progress {
+            Some(progress) => nexus_core::stwo::prove_monitored(
+                &trace,
+                &view,
+                min_log_size,
+                &self.prover_config,
+                deadline,
+                progress,
+            ),
+            None => nexus_core::stwo::prove_with_config(
+                &trace,
+                &view,
+                min_log_size,
+                &self.prover_config,
+                deadline,
+            ),
+        }
+        .map_err(|err| match err {
+            nexus_core::stwo::ProveError::DeadlineExceeded { phase } => Error::from(aborted(phase)),
+            nexus_core::stwo::ProveError::Cancelled { phase } => ProveCancelled { phase }.into(),
+            err => err.into(),
+        })?;
+
+        let warnings = self.warnings(trace.get_num_steps(), 1 << proof.log_size[0]);
+
+        Ok(ProveOutput {
+            view,
+            proof: Proof {
+                proof,
+                memory_layout: trace.memory_layout,
+                output_root: None,
+                config_digest,
+                public_input: public_encoded.to_vec(),
+                io_schema,
+            },
+            warnings,
+        })
+    }
+
+    fn warnings(&self, steps: usize, rows: usize) -> Vec<Warning> {
+        let mut warnings = Vec::new();
+
+        if (rows - steps) * 100 >= rows * WARNING_THRESHOLD_PERCENT {
+            warnings.push(Warning::TracePadding { steps, rows });
+        }
+        if let Some(limit) = self.limits.cycle_limit {
+            if steps * 100 >= limit * WARNING_THRESHOLD_PERCENT {
+                warnings.push(Warning::NearCycleLimit {
+                    cycles: steps,
+                    limit,
+                });
+            }
+        }
+
+        warnings
+    }
+
+    /// Estimate the cost of proving the execution on private input of type `S` and public input of type `T`, by
+    /// running the program without proving it.
+    pub fn estimate<S: Serialize + Sized, T: Serialize + DeserializeOwned + Sized>(
+        &self,
+        private_input: &S,
+        public_input: &T,
+    ) -> Result<CostReport, Error> {
+        self.estimate_encoded(
+            encode_input(private_input)?.as_slice(),
+            encode_input(public_input)?.as_slice(),
+        )
+    }
+
+    /// Estimate the cost of proving the execution as [`Self::estimate`], on already encoded private and public input
+    /// (see [`encode_private_input`]), such as read from files.
+    pub fn estimate_encoded(
+        &self,
+        private_encoded: &[u8],
+        public_encoded: &[u8],
+    ) -> Result<CostReport, Error> {
+        self.check_provable()?;
+        let (view, trace) = self.trace(self.ad.as_slice(), public_encoded, private_encoded)?;
+
+        let mut opcode_rows = BTreeMap::new();
+        for step in trace.get_blocks_iter().flat_map(|block| &block.steps) {
+            *opcode_rows
+                .entry(step.instruction.opcode.to_string())
+                .or_default() += 1;
+        }
+
+        let cycles = trace.get_num_steps();
+        let log_size = self.trace_log_size(cycles)?;
+        Ok(CostReport {
+            cycles,
+            log_size,
+            opcode_rows,
+            instruction_mix: view.instruction_mix(),
+            trace_bytes: self.prover_config.committed_trace_bytes(log_size),
+        })
+    }
+
+    /// The log size of the main trace proving an execution of `steps` steps.
+    fn trace_log_size(&self, steps: usize) -> Result<u32, Error> {
+        let program_log_size = self.elf.instructions.len().next_power_of_two().ilog2();
+        let log_size = steps
+            .next_power_of_two()
+            .ilog2()
+            .max(program_log_size)
+            .max(nexus_core::stwo::PreprocessedTraces::MIN_LOG_SIZE);
+        if self.fixed_shape {
+            Ok(log_size.max(self.fixed_shape_log_size()?))
+        } else {
+            Ok(log_size)
+        }
+    }
+
+    /// Check the program and the trace proving it would plan for likely misconfigurations, without proving.
+    ///
+    /// The program is run on empty input, as by [`Prover::prove`], to find the number of steps; which chips an
+    /// execution uses is judged from its instruction mix. The run uses the configured host functions, and consumes the
+    /// input provider as an execution for proving would.
+    pub fn lint(&self) -> Result<Vec<ProvingLint>, Error> {
+        self.check_provable()?;
+        let (view, trace) =
+            self.trace(self.ad.as_slice(), &encode_input(&())?, &encode_input(&())?)?;
+
+        let steps = trace.get_num_steps();
+        let log_size = self.trace_log_size(steps)?;
+
+        let mut lints: Vec<ProvingLint> = self
+            .warnings(steps, 1 << log_size)
+            .into_iter()
+            .map(ProvingLint::from)
+            .collect();
+
+        use nexus_core::stwo::chips;
+        let mix = view.instruction_mix();
+        for (class, chip_names) in [
+            (
+                nexus_core::nvm::InstrClass::Memory,
+                &[std::any::type_name::<chips::LoadStoreChip>()][..],
+            ),
+            (
+                nexus_core::nvm::InstrClass::MulDiv,
+                &[
+                    std::any::type_name::<chips::DivRemChip>(),
+                    std::any::type_name::<chips::DivuRemuChip>(),
+                    std::any::type_name::<chips::MulChip>(),
+                    std::any::type_name::<chips::MulhMulhsuChip>(),
+                    std::any::type_name::<chips::MulhuChip>(),
+                ][..],
+            ),
+        ] {
+            if mix.get(&class).copied().unwrap_or(0) == 0 {
+                lints.extend(chip_names.iter().copied().map(ProvingLint::UnusedChip));
+            }
+        }
+
+        Ok(lints)
+    }
+
+    /// Prove the execution described by `view`, pulling its steps from `steps` while filling the trace rather than
+    /// holding them all in memory, so that proving can be interleaved with an emulator producing them.
+    ///
+    /// The trace size is fixed before any step is pulled, so the proof is padded to a fixed shape as under
+    /// [`Stwo::with_fixed_shape`], which the execution must fit within. This needs a cycle limit to size the shape by,
+    /// and fails with [`ConfigurationError::UnboundedShape`] without one.
+    ///
+    /// The proof records `io_schema` as the types of the public input and output, without which it can only be
+    /// checked through [`Verifiable::verify`].
+    ///
+    /// Fails with [`Error::ProveError`] if `steps` yields more steps than fit within the trace.
+    pub fn prove_from_steps(
+        self,
+        view: &nexus_core::nvm::View,
+        steps: impl Iterator<Item = nexus_core::stwo::ProgramStep>,
+        io_schema: Option<IoSchema>,
+    ) -> Result<Proof, Error> {
+        self.check_provable()?;
+        let memory_layout = *view
+            .view_memory_layout()
+            .ok_or(IOError::NotYetAvailableError)?;
+        let config = ProvingConfig {
+            fixed_shape: true,
+            ..self.proving_config()
+        };
+        let config_digest = config.digest()?;
+        let mut view = view.clone();
+        view.set_statement_context(statement_context(&config_digest, io_schema.as_ref())?);
+
+        let proof = nexus_core::stwo::prove_from_steps(
+            steps,
+            &view,
+            self.fixed_shape_log_size()?,
+            &self.prover_config,
+        )?;
+
+        Ok(Proof {
+            proof,
+            memory_layout,
+            output_root: None,
+            config_digest,
+            public_input: view.view_public_input().unwrap_or_default(),
+            io_schema,
+        })
+    }
+
+    /// Run the zkVM on private input of type `S` and public input of type `T`, for a guest whose public output is the
+    /// [`merkle::root`] of its (possibly large) actual output, and return a proof carrying that root.
+    ///
+    /// Individual output elements can then be checked using [`Proof::verify_output_leaf`]. Guests may instead return
+    /// the [`merkle::output_digest`] of their whole output, which is then checked using
+    /// [`Proof::verify_expected_hashed_output`].
+    pub fn prove_with_committed_output<
+        S: Serialize + Sized,
+        T: Serialize + DeserializeOwned + IoType,
+    >(
+        self,
+        private_input: &S,
+        public_input: &T,
+    ) -> Result<(nexus_core::nvm::View, Proof), Error> {
+        let (view, mut proof) = self.prove_with_schema(
+            private_input,
+            public_input,
+            IoSchema::of::<T, merkle::Digest>(),
+        )?;
+        proof.output_root = Some(view.public_output::<merkle::Digest>()?);
+
+        Ok((view, proof))
+    }
+
+    /// Run the zkVM on private input of type `S` and public input of type `T`, for a guest whose public output is of
+    /// type `U`, and return a proof recording both types (see [`IoSchema`]).
+    ///
+    /// Fails if the public output does not decode as `U`, and the proof then only verifies through
+    /// [`Verifiable::verify_typed`] with the same `T` and `U`.
+    pub fn prove_typed<
+        S: Serialize + Sized,
+        T: Serialize + DeserializeOwned + IoType,
+        U: Serialize + DeserializeOwned + IoType,
+    >(
+        self,
+        private_input: &S,
+        public_input: &T,
+    ) -> Result<(nexus_core::nvm::View, Proof), Error> {
+        let (view, proof) =
+            self.prove_with_schema(private_input, public_input, IoSchema::of::<T, U>())?;
+        view.public_output::<U>()?;
+
+        Ok((view, proof))
+    }
+
+    /// Prove the execution on private input of type `S` and public input of type `T`, recording `io_schema` in the
+    /// proof.
+    fn prove_with_schema<S: Serialize + Sized, T: Serialize + DeserializeOwned + Sized>(
+        self,
+        private_input: &S,
+        public_input: &T,
+        io_schema: IoSchema,
+    ) -> Result<(nexus_core::nvm::View, Proof), Error> {
+        let ProveOutput { view, proof, .. } = self.prove_encoded(
+            encode_input(public_input)?.as_slice(),
+            encode_input(private_input)?.as_slice(),
+            self.ad.as_slice(),
+            None,
+            None,
+            Some(io_schema),
+        )?;
+        Ok((view, proof))
+    }
+}
+
+impl Proof {
+    /// The Merkle root of the output committed to by the guest, if the proof was generated with
+    /// [`Stwo::prove_with_committed_output`].
+    pub fn output_root(&self) -> Option<&merkle::Digest> {
+        self.output_root.as_ref()
+    }
+
+    /// Encode the proof into a canonical byte form, for caching or deduplicating proofs by their bytes.
+    ///
+    /// Proofs that are logically equal canonicalize identically, including after a round trip through any serde
+    /// format. Proving is deterministic, so proving the same execution under the same configuration canonicalizes
+    /// identically too. Proofs of the same statement from different executions (for example with different private
+    /// input, or a different fixed shape) are genuinely distinct proofs, and will not match.
+    pub fn canonicalize(&self) -> Vec<u8> {
+        // Postcard encodes fields in declaration order, without padding and with minimal varints, and the proof has
+        // no unordered collections, so each proof has exactly one encoding.
+        postcard::to_stdvec(self).expect("proof serialization is infallible")
+    }
+
+    /// Encode the proof in its versioned wire format, with the compact [`ProofEncoding::Binary`] encoding.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes_with(ProofEncoding::Binary)
+    }
+
+    /// Encode the proof in its versioned wire format, with the given encoding.
+    ///
+    /// The encoding starts with a header carrying [`PROOF_FORMAT_VERSION`], so that [`Proof::from_bytes`] rejects
+    /// proofs encoded by an incompatible SDK with [`ProofEncodingError::UnsupportedVersion`] instead of misreading them.
+    pub fn to_bytes_with(&self, encoding: ProofEncoding) -> Vec<u8> {
+        let mut bytes = Vec::from(PROOF_MAGIC);
+        bytes.extend_from_slice(&PROOF_FORMAT_VERSION.to_le_bytes());
+        bytes.push(encoding.tag());
+        match encoding {
+            ProofEncoding::Binary => bytes.extend(self.canonicalize()),
+            ProofEncoding::Json => {
+                serde_json::to_writer(&mut bytes, self).expect("proof serialization is infallible")
+            }
+        }
+        bytes
+    }
+
+    /// Decode a proof encoded by [`Proof::to_bytes`] or [`Proof::to_bytes_with`], in any encoding.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProofEncodingError> {
+        if bytes.len() < PROOF_HEADER_LEN || bytes[..PROOF_MAGIC.len()] != PROOF_MAGIC {
+            return Err(ProofEncodingError::InvalidHeader);
+        }
+        let (header, body) = bytes.split_at(PROOF_HEADER_LEN);
+
+        let version = u16::from_le_bytes([header[4], header[5]]);
+        if version != PROOF_FORMAT_VERSION {
+            return Err(ProofEncodingError::UnsupportedVersion {
+                found: version,
+                supported: PROOF_FORMAT_VERSION,
+            });
+        }
+
+        match ProofEncoding::from_tag(header[6]) {
+            Some(ProofEncoding::Binary) => Ok(postcard::from_bytes(body)?),
+            Some(ProofEncoding::Json) => Ok(serde_json::from_slice(body)?),
+            None => Err(ProofEncodingError::UnsupportedEncoding(header[6])),
+        }
+    }
+
+    /// Compute a digest identifying the proof, the hash of its [`Proof::canonicalize`] form.
+    pub fn fingerprint(&self) -> merkle::Digest {
+        merkle::hash_leaf(&self.canonicalize())
+    }
+
+    /// Check that the proof was produced under `expected_config`, failing with [`VerifyError::ConfigMismatch`]
+    /// otherwise.
+    ///
+    /// The digest of the configuration is bound by the proof, so it cannot be changed without the proof failing to
+    /// verify, and this should be combined with [`Verifiable::verify_expected`].
+    pub fn verify_config(&self, expected_config: &ProvingConfig) -> Result<(), Error> {
+        if expected_config.digest()? != self.config_digest {
+            return Err(VerifyError::ConfigMismatch.into());
+        }
+        Ok(())
+    }
+
+    /// The parameters of the commitment scheme the proof was made with.
+    pub fn prover_config(&self) -> &ProverConfig {
+        &self.proof.config
+    }
+
+    /// Check that the proof was made with at least `min_security_bits` bits of conjectured security, failing with
+    /// [`Error::VerificationError`] otherwise.
+    ///
+    /// Verification accepts proofs under any valid configuration, which the prover chooses. Verifiers accepting proofs
+    /// from untrusted provers should call this before [`Verifiable::verify_expected`], for example with
+    /// [`ProverConfig::RECOMMENDED_MIN_SECURITY_BITS`].
+    pub fn check_security(&self, min_security_bits: u32) -> Result<(), Error> {
+        Ok(self.proof.check_security(min_security_bits)?)
+    }
+
+    /// Decode the public input of type `T` the proof is bound to, so that the verifier doesn't need to know it in
+    /// advance.
+    ///
+    /// The input is carried alongside the proof, and is only authentic once the proof has been verified against it,
+    /// so prefer [`Proof::verify_extracting_public_input`] which does both.
+    pub fn extract_public_input<T: Serialize + DeserializeOwned + Sized>(
+        &self,
+    ) -> Result<T, IOError> {
+        let mut bytes = self.public_input.clone();
+        if bytes.is_empty() {
+            // Inputs that encode to nothing, such as `()`, are not cobs-encoded.
+            Ok(postcard::from_bytes::<T>(&bytes)?)
+        } else {
+            Ok(postcard::from_bytes_cobs::<T>(&mut bytes)?)
+        }
+    }
+
+    /// Verify the proof of an execution, as [`Verifiable::verify_expected`], against the public input carried by the
+    /// proof, and return that input.
+    ///
+    /// The returned input is the one the execution was proven with.
+    pub fn verify_extracting_public_input<
+        T: Serialize + DeserializeOwned + Sized,
+        U: Serialize + DeserializeOwned + Sized,
+    >(
+        &self,
+        expected_exit_code: u32,
+        expected_public_output: &U,
+        expected_elf: &nexus_core::nvm::ElfFile,
+        expected_ad: &[u8],
+    ) -> Result<T, Error> {
+        let public_input = self.extract_public_input::<T>()?;
+
+        self.verify_expected(
+            &public_input,
+            expected_exit_code,
+            expected_public_output,
+            expected_elf,
+            expected_ad,
+        )?;
+        Ok(public_input)
+    }
+
+    /// Verify the proof of an execution whose public output is the committed output root carried by the proof.
+    pub fn verify_expected_committed_output<T: Serialize + DeserializeOwned + Sized>(
+        &self,
+        expected_public_input: &T,
+        expected_exit_code: u32,
+        expected_elf: &nexus_core::nvm::ElfFile,
+        expected_ad: &[u8],
+    ) -> Result<(), Error> {
+        let root = self
+            .output_root
+            .ok_or(OutputCommitmentError::NotCommitted)?;
+
+        self.verify_expected(
+            expected_public_input,
+            expected_exit_code,
+            &root,
+            expected_elf,
+            expected_ad,
+        )
+    }
+
+    /// Verify the proof of an execution whose public output is the [`merkle::output_digest`] of
+    /// `expected_public_output`, so that the full output never has to be carried by the proof.
+    ///
+    /// Verifiers without the full output can check the digest alone using
+    /// [`Proof::verify_expected_committed_output`].
+    pub fn verify_expected_hashed_output<
+        T: Serialize + DeserializeOwned + Sized,
+        U: Serialize + DeserializeOwned + Sized,
+    >(
+        &self,
+        expected_public_input: &T,
+        expected_exit_code: u32,
+        expected_public_output: &U,
+        expected_elf: &nexus_core::nvm::ElfFile,
+        expected_ad: &[u8],
+    ) -> Result<(), Error> {
+        self.verify_expected(
+            expected_public_input,
+            expected_exit_code,
+            &merkle::output_digest(expected_public_output),
+            expected_elf,
+            expected_ad,
+        )
+    }
+
+    /// Verify the proof of an execution, as [`Verifiable::verify_expected`], loading the expected ELF from a reader.
+    ///
+    /// Only the parts of the ELF needed to load the program are read, so debug sections are never buffered.
+    pub fn verify_expected_from_reader<
+        T: Serialize + DeserializeOwned + Sized,
+        U: Serialize + DeserializeOwned + Sized,
+        R: std::io::Read + std::io::Seek,
+    >(
+        &self,
+        expected_elf: R,
+        expected_public_input: &T,
+        expected_exit_code: u32,
+        expected_public_output: &U,
+        expected_ad: &[u8],
+    ) -> Result<(), Error> {
+        let elf = nexus_core::nvm::ElfFile::from_reader(expected_elf)?;
+
+        self.verify_expected(
+            expected_public_input,
+            expected_exit_code,
+            expected_public_output,
+            &elf,
+            expected_ad,
+        )
+    }
+
+    /// Verify the proof of an execution, as [`Verifiable::verify_expected`], of the program identified by
+    /// `expected_program_digest` (see [`program_digest`]), obtaining the ELF from `fetcher`.
+    ///
+    /// The fetched ELF is only trusted once its digest matches, failing with [`VerifyError::ProgramDigestMismatch`]
+    /// otherwise, so the fetcher may use any transport or store. Fetching failures are reported as
+    /// [`VerifyError::FetchError`].
+    pub fn verify_expected_fetching<
+        T: Serialize + DeserializeOwned + Sized,
+        U: Serialize + DeserializeOwned + Sized,
+        E: fmt::Display,
+    >(
+        &self,
+        expected_public_input: &T,
+        expected_exit_code: u32,
+        expected_public_output: &U,
+        expected_program_digest: &merkle::Digest,
+        expected_ad: &[u8],
+        fetcher: impl Fn(&merkle::Digest) -> Result<Vec<u8>, E>,
+    ) -> Result<(), Error> {
+        let elf_bytes = fetcher(expected_program_digest)
+            .map_err(|err| VerifyError::FetchError(err.to_string()))?;
+        let elf = nexus_core::nvm::ElfFile::from_bytes(&elf_bytes)?;
+        if program_digest(&elf)? != *expected_program_digest {
+            return Err(VerifyError::ProgramDigestMismatch.into());
+        }
+
+        self.verify_expected(
+            expected_public_input,
+            expected_exit_code,
+            expected_public_output,
+            &elf,
+            expected_ad,
+        )
+    }
+
+    /// Verify the proof of an execution, as [`Verifiable::verify_expected`], but give up with [`VerifyError::Timeout`]
+    /// if verification takes longer than `timeout`.
+    ///
+    /// The timeout is checked cooperatively between verification phases rather than by interrupting them, so
+    /// verification may overrun it by at most the duration of a single phase.
+    pub fn verify_expected_with_timeout<
+        T: Serialize + DeserializeOwned + Sized,
+        U: Serialize + DeserializeOwned + Sized,
+    >(
+        &self,
+        expected_public_input: &T,
+        expected_exit_code: u32,
+        expected_public_output: &U,
+        expected_elf: &nexus_core::nvm::ElfFile,
+        expected_ad: &[u8],
+        timeout: Duration,
+    ) -> Result<(), Error> {
+        let deadline = Instant::now() + timeout;
+
+        let input_encoded = encode_input(expected_public_input)?;
+        let output_encoded = encode_input(expected_public_output)?;
+
+        let mut view = nexus_core::nvm::View::new_from_expected(
+            self.get_memory_layout(),
+            input_encoded.as_slice(),
+            &expected_exit_code.to_le_bytes(),
+            output_encoded.as_slice(),
+            expected_elf,
+            expected_ad,
+        );
+        view.set_statement_context(statement_context(
+            &self.config_digest,
+            self.io_schema.as_ref(),
+        )?);
+
+        match nexus_core::stwo::verify_with_deadline(self.proof.clone(), &view, deadline) {
+            Ok(()) => Ok(()),
+            Err(nexus_core::stwo::DeadlineVerificationError::Verification(err)) => Err(err.into()),
+            Err(nexus_core::stwo::DeadlineVerificationError::DeadlineExceeded) => {
+                Err(VerifyError::Timeout(timeout).into())
+            }
+        }
+    }
+
+    /// Verify a pipeline of executions, where the public output of each proof is the public input of the next.
+    ///
+    /// Each proof is verified against the public input it carries and, as its public output, the public input
+    /// carried by the next proof, so an intermediate value is only accepted if it is both the output of one execution
+    /// and the input of the next. The first proof must carry `expected_public_input`, and the last must have output
+    /// `expected_public_output`. A break in the chain is reported as [`VerifyError::ChainMismatch`].
+    pub fn verify_chained<
+        T: Serialize + DeserializeOwned + IoType,
+        U: Serialize + DeserializeOwned + IoType,
+    >(
+        links: &[ChainLink],
+        expected_public_input: &T,
+        expected_public_output: &U,
+    ) -> Result<(), Error> {
+        let (first, last) = match (links.first(), links.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return Err(VerifyError::EmptyChain.into()),
+        };
+
+        // Only the input of the first proof and the output of the last are of known types, and they are checked
+        // against the schemas the proofs record, if any.
+        let expected = IoSchema::of::<T, U>();
+        if let Some(schema) = first.proof.io_schema() {
+            if schema.public_input != expected.public_input {
+                return Err(IOError::IoSchemaMismatch {
+                    proven: schema.clone(),
+                    expected,
+                }
+                .into());
+            }
+        }
+        if let Some(schema) = last.proof.io_schema() {
+            if schema.public_output.is_some() && schema.public_output != expected.public_output {
+                return Err(IOError::IoSchemaMismatch {
+                    proven: schema.clone(),
+                    expected,
+                }
+                .into());
+            }
+        }
+
+        if first.proof.public_input != encode_input(expected_public_input)? {
+            return Err(VerifyError::ChainMismatch(0).into());
+        }
+        let final_output = encode_input(expected_public_output)?;
+
+        for (i, link) in links.iter().enumerate() {
+            let output = match links.get(i + 1) {
+                Some(next) => {
+                    if let (Some(output_schema), Some(input_schema)) =
+                        (link.proof.io_schema(), next.proof.io_schema())
+                    {
+                        if output_schema
+                            .public_output
+                            .as_ref()
+                            .is_some_and(|output| *output != input_schema.public_input)
+                        {
+                            return Err(VerifyError::ChainMismatch(i + 1).into());
+                        }
+                    }
+                    next.proof.public_input.as_slice()
+                }
+                None => final_output.as_slice(),
+            };
+
+            let view = nexus_core::nvm::View::new_from_expected(
+                link.proof.get_memory_layout(),
+                &link.proof.public_input,
+                &link.exit_code.to_le_bytes(),
+                output,
+                link.elf,
+                link.ad,
+            );
+            link.proof.verify(&view)?;
+        }
+
+        Ok(())
+    }
+
+    /// Check that `leaf` is the output element at `index`, using the authentication path produced by [`merkle::path`].
+    ///
+    /// This only opens the leaf against the committed output root: the proof itself must be checked using
+    /// [`Proof::verify_expected_committed_output`] for the root to be trusted.
+    pub fn verify_output_leaf(
+        &self,
+        index: usize,
+        leaf: &[u8],
+        proof_path: &[merkle::Digest],
+    ) -> Result<(), Error> {
+        let root = self
+            .output_root
+            .as_ref()
+            .ok_or(OutputCommitmentError::NotCommitted)?;
+
+        if merkle::verify_path(root, index, leaf, proof_path) {
+            Ok(())
+        } else {
+            Err(OutputCommitmentError::InvalidOpening(index).into())
+        }
+    }
 }
 
 impl Verifiable for Proof {
@@ -183,10 +1866,19 @@ impl Verifiable for Proof {
     }
 
     fn verify(&self, view: &Self::View) -> Result<(), <Self as Verifiable>::Error> {
-        nexus_core::stwo::verify(self.proof.clone(), view)?;
+        let mut view = view.clone();
+        view.set_statement_context(statement_context(
+            &self.config_digest,
+            self.io_schema.as_ref(),
+        )?);
+        nexus_core::stwo::verify(self.proof.clone(), &view)?;
         Ok(())
     }
 
+    fn io_schema(&self) -> Option<&IoSchema> {
+        self.io_schema.as_ref()
+    }
+
     fn size_estimate(&self) -> usize {
         self.proof.size_estimate()
     }
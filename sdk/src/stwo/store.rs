@@ -0,0 +1,82 @@
+//! Content-addressed storage of [`Proof`]s, for caching proofs by their [`Proof::fingerprint`].
+
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use crate::error::ProofStoreError;
+use crate::merkle;
+
+use super::seq::Proof;
+
+/// The key a proof is stored under, its [`Proof::fingerprint`].
+pub type Key = merkle::Digest;
+
+/// A store of proofs addressed by their fingerprint.
+///
+/// Proofs don't carry the digest of the program they prove, so keeping proofs of different programs apart (for
+/// example using one store per [`program_digest`](super::seq::program_digest)) is up to the caller.
+pub trait ProofStore {
+    type Error;
+
+    /// Store the proof, returning the key it can be retrieved with.
+    fn put(&self, proof: &Proof) -> Result<Key, Self::Error>;
+
+    /// Retrieve the proof stored under `key`, or `None` if there is none.
+    fn get(&self, key: &Key) -> Result<Option<Proof>, Self::Error>;
+}
+
+/// A [`ProofStore`] on the filesystem, keeping each proof in its own file at `<root>/<xx>/<fingerprint>.proof`,
+/// where `<xx>` are the first two hexadecimal digits of the fingerprint.
+#[derive(Debug, Clone)]
+pub struct FsProofStore {
+    root: PathBuf,
+}
+
+impl FsProofStore {
+    /// Create a store rooted at the given directory, which is created on first use.
+    pub fn new(root: impl AsRef<Path>) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+        }
+    }
+
+    /// The path the proof with the given key is stored at.
+    pub fn path(&self, key: &Key) -> PathBuf {
+        let hex: String = key.iter().map(|byte| format!("{byte:02x}")).collect();
+        self.root.join(&hex[..2]).join(format!("{hex}.proof"))
+    }
+}
+
+impl ProofStore for FsProofStore {
+    type Error = ProofStoreError;
+
+    fn put(&self, proof: &Proof) -> Result<Key, Self::Error> {
+        let bytes = proof.canonicalize();
+        let key = merkle::hash_leaf(&bytes);
+        let path = self.path(&key);
+
+        let dir = path.parent().expect("proof path has a parent");
+        std::fs::create_dir_all(dir)?;
+
+        // Write to a temporary file first, so a concurrent `get` never observes a partially written proof.
+        let tmp_path = path.with_extension(format!("proof.{}", uuid::Uuid::new_v4()));
+        std::fs::write(&tmp_path, &bytes)?;
+        std::fs::rename(&tmp_path, &path)?;
+
+        Ok(key)
+    }
+
+    fn get(&self, key: &Key) -> Result<Option<Proof>, Self::Error> {
+        let bytes = match std::fs::read(self.path(key)) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+
+        let proof: Proof = postcard::from_bytes(&bytes).map_err(|_| ProofStoreError::Corrupted)?;
+        if proof.fingerprint() != *key {
+            return Err(ProofStoreError::Corrupted);
+        }
+        Ok(Some(proof))
+    }
+}
@@ -1,7 +1,7 @@
 use crypto::digest::{Digest, OutputSizeUser};
 use crypto_common::generic_array::{ArrayLength, GenericArray};
 use nexus_common::constants::WORD_SIZE;
-use serde::{de::DeserializeOwned, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::path::Path;
 
 use nexus_core::nvm::internals::*;
@@ -16,6 +16,10 @@ pub trait Compute {}
 pub enum Local {}
 impl Compute for Local {}
 
+/// Use a remote proving service to prove the zkVM, see [`ProvingService`](crate::stwo::network::ProvingService).
+pub enum Network {}
+impl Compute for Network {}
+
 /// A view of an execution, the correctness of which is guaranteed by the proving and checked by the verification.
 pub trait CheckedView {
     /// Rebuild from constitutent parts, for use by the verifier during verification.
@@ -167,6 +171,18 @@ pub trait Viewable {
 
     /// Recover any debug logs produced by the execution.
     fn logs(&self) -> Result<Vec<String>, IOError>;
+
+    /// Recover the structured log records produced by the execution at `max_level` or more severe.
+    fn structured_logs(
+        &self,
+        max_level: nexus_core::nvm::LogLevel,
+    ) -> Result<Vec<nexus_core::nvm::LogRecord>, IOError>;
+
+    /// Recover the transcript of host-provided inputs consumed by the execution, for use in deterministic replay.
+    fn input_transcript(&self) -> Result<nexus_core::nvm::InputTranscript, IOError>;
+
+    /// Attribute the cycles of the execution to the functions of the guest program, using its ELF symbols.
+    fn profile(&self) -> nexus_core::nvm::Profile;
 }
 
 impl Viewable for nexus_core::nvm::View {
@@ -217,6 +233,25 @@ impl Viewable for nexus_core::nvm::View {
             Err(IOError::NotYetAvailableError)
         }
     }
+
+    /// Recover the structured log records produced by the execution at `max_level` or more severe.
+    fn structured_logs(
+        &self,
+        max_level: nexus_core::nvm::LogLevel,
+    ) -> Result<Vec<nexus_core::nvm::LogRecord>, IOError> {
+        Ok(self.view_log_records(max_level))
+    }
+
+    /// Recover the transcript of host-provided inputs consumed by the execution, for use in deterministic replay.
+    fn input_transcript(&self) -> Result<nexus_core::nvm::InputTranscript, IOError> {
+        self.view_input_transcript()
+            .ok_or(IOError::NotYetAvailableError)
+    }
+
+    /// Attribute the cycles of the execution to the functions of the guest program, using its ELF symbols.
+    fn profile(&self) -> nexus_core::nvm::Profile {
+        nexus_core::nvm::View::profile(self)
+    }
 }
 
 /// A proving instance that can be constructed through compiling a guest program.
@@ -234,7 +269,10 @@ pub trait Prover: Sized {
     /// Construct a new proving instance.
     fn new(elf: &nexus_core::nvm::ElfFile) -> Result<Self, <Self as Prover>::Error>;
 
-    /// Construct a new proving instance from raw ELF bytes.
+    /// Construct a new proving instance from raw ELF bytes, such as those of a prebuilt or non-Rust guest.
+    ///
+    /// The ELF must be a 32-bit RISC-V executable whose entry point lies within its code, otherwise the error
+    /// names the failed check.
     fn new_from_bytes(elf_bytes: &[u8]) -> Result<Self, <Self as Prover>::Error> {
         Self::new(&nexus_core::nvm::ElfFile::from_bytes(elf_bytes)?)
     }
@@ -420,6 +458,144 @@ impl Preprocessing for () {
     }
 }
 
+/// A type with a declared schema identifier, recorded in an [`IoSchema`] for public input or output of that type.
+///
+/// Identifiers are spelled out rather than taken from [`std::any::type_name`], so that they do not change between
+/// compilers. They are provided for primitive and common standard library types, can be derived with
+/// [`#[derive(IoType)]`](nexus_sdk_macros::IoType) from the name of a type and the identifiers of its fields (e.g.,
+/// `Point { x: i32, y: i32 }`), or can be declared explicitly by implementing the trait.
+pub trait IoType {
+    /// The schema identifier of the type.
+    fn schema_id() -> String;
+}
+
+macro_rules! impl_io_type {
+    ($($ty:ty),*) => {
+        $(
+            impl IoType for $ty {
+                fn schema_id() -> String {
+                    String::from(stringify!($ty))
+                }
+            }
+        )*
+    };
+}
+
+impl_io_type!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64,
+    String
+);
+
+macro_rules! impl_io_type_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: IoType),+> IoType for ($($name,)+) {
+            fn schema_id() -> String {
+                let ids = [$($name::schema_id()),+];
+                if ids.len() == 1 {
+                    format!("({},)", ids[0])
+                } else {
+                    format!("({})", ids.join(", "))
+                }
+            }
+        }
+    };
+}
+
+impl_io_type_tuple!(A);
+impl_io_type_tuple!(A, B);
+impl_io_type_tuple!(A, B, C);
+impl_io_type_tuple!(A, B, C, D);
+impl_io_type_tuple!(A, B, C, D, E);
+impl_io_type_tuple!(A, B, C, D, E, F);
+impl_io_type_tuple!(A, B, C, D, E, F, G);
+impl_io_type_tuple!(A, B, C, D, E, F, G, H);
+
+impl<T: IoType> IoType for Vec<T> {
+    fn schema_id() -> String {
+        format!("Vec<{}>", T::schema_id())
+    }
+}
+
+impl<T: IoType> IoType for Option<T> {
+    fn schema_id() -> String {
+        format!("Option<{}>", T::schema_id())
+    }
+}
+
+impl<T: IoType, const N: usize> IoType for [T; N] {
+    fn schema_id() -> String {
+        format!("[{}; {N}]", T::schema_id())
+    }
+}
+
+// Boxes serialize as the value they hold.
+impl<T: IoType> IoType for Box<T> {
+    fn schema_id() -> String {
+        T::schema_id()
+    }
+}
+
+/// The types of the public input and output an execution was proven with.
+///
+/// Public input and output are encoded canonically with postcard, cobs-framed and zero-padded to a whole number of
+/// words (or not at all, for types that encode to nothing). As the encoding is not self-describing, a verifier
+/// expecting the wrong type could otherwise silently decode a different value from the same bytes. The schema recorded
+/// by the prover lets [`Verifiable::verify_typed`] reject such a verifier with [`IOError::IoSchemaMismatch`].
+///
+/// The schema is bound by the proof, so it cannot be changed or removed without the proof failing to verify.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IoSchema {
+    /// The schema identifier of the public input, see [`IoType`].
+    pub public_input: String,
+    /// The schema identifier of the public output, if known to the prover.
+    pub public_output: Option<String>,
+}
+
+impl IoSchema {
+    /// The schema of public input of type `T` and public output of type `U`.
+    pub fn of<T: IoType, U: IoType>() -> Self {
+        Self {
+            public_input: T::schema_id(),
+            public_output: Some(U::schema_id()),
+        }
+    }
+
+    /// Check that public input of type `T` and public output of type `U` match the schema.
+    pub fn check<T: IoType, U: IoType>(&self) -> Result<(), IOError> {
+        let expected = Self::of::<T, U>();
+        let input_matches = self.public_input == expected.public_input;
+        let output_matches = self
+            .public_output
+            .as_ref()
+            .is_none_or(|output| Some(output) == expected.public_output.as_ref());
+
+        if input_matches && output_matches {
+            Ok(())
+        } else {
+            Err(IOError::IoSchemaMismatch {
+                proven: self.clone(),
+                expected,
+            })
+        }
+    }
+}
+
 /// A verifiable proof of a zkVM execution.
 pub trait Verifiable: Serialize + DeserializeOwned {
     type View: CheckedView;
@@ -431,7 +607,15 @@ pub trait Verifiable: Serialize + DeserializeOwned {
     /// Verify the proof of an execution for a constructed [`CheckedView`](crate::traits::CheckedView).
     fn verify(&self, expected_view: &Self::View) -> Result<(), <Self as Verifiable>::Error>;
 
+    /// The types of the public input and output the execution was proven with, if recorded by the prover.
+    fn io_schema(&self) -> Option<&IoSchema> {
+        None
+    }
+
     /// Verify the proof of an execution.
+    ///
+    /// The public input and output are encoded as described for [`IoSchema`]. Any schema recorded by the prover is
+    /// not checked against `T` and `U`, see [`Verifiable::verify_typed`] for that.
     fn verify_expected<
         T: Serialize + DeserializeOwned + Sized,
         U: Serialize + DeserializeOwned + Sized,
@@ -477,6 +661,58 @@ pub trait Verifiable: Serialize + DeserializeOwned {
         self.verify(&view)
     }
 
+    /// Verify the proof of an execution as [`Verifiable::verify_expected`], after checking that `T` and `U` are the
+    /// types the prover recorded (see [`IoSchema`]).
+    ///
+    /// Fails with [`IOError::IoSchemaMismatch`] when they are not, and with [`IOError::MissingIoSchema`] if the prover
+    /// recorded no schema.
+    fn verify_typed<
+        T: Serialize + DeserializeOwned + IoType,
+        U: Serialize + DeserializeOwned + IoType,
+    >(
+        &self,
+        expected_public_input: &T,
+        expected_exit_code: u32,
+        expected_public_output: &U,
+        expected_elf: &nexus_core::nvm::ElfFile,
+        expected_ad: &[u8],
+    ) -> Result<(), <Self as Verifiable>::Error> {
+        self.io_schema()
+            .ok_or(IOError::MissingIoSchema)?
+            .check::<T, U>()?;
+
+        self.verify_expected(
+            expected_public_input,
+            expected_exit_code,
+            expected_public_output,
+            expected_elf,
+            expected_ad,
+        )
+    }
+
+    /// Verify the proof of an execution as [`Verifiable::verify_expected`], expecting the guest to have halted for
+    /// `expected_halt_reason`, such as [`HaltReason::Exit(0)`](nexus_core::nvm::HaltReason::Exit) to require a clean
+    /// exit.
+    fn verify_expected_halt<
+        T: Serialize + DeserializeOwned + Sized,
+        U: Serialize + DeserializeOwned + Sized,
+    >(
+        &self,
+        expected_public_input: &T,
+        expected_halt_reason: nexus_core::nvm::HaltReason,
+        expected_public_output: &U,
+        expected_elf: &nexus_core::nvm::ElfFile,
+        expected_ad: &[u8],
+    ) -> Result<(), <Self as Verifiable>::Error> {
+        self.verify_expected(
+            expected_public_input,
+            expected_halt_reason.exit_code(),
+            expected_public_output,
+            expected_elf,
+            expected_ad,
+        )
+    }
+
     /// Verify the proof of an execution, with the elf provided as raw bytes.
     fn verify_expected_from_program_bytes<
         T: Serialize + DeserializeOwned + Sized,
@@ -0,0 +1,14 @@
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+extern crate alloc;
+
+use alloc::{boxed::Box, vec::Vec};
+use core::hint::black_box;
+
+#[nexus_rt::main]
+fn main() {
+    // Two allocations, of 64 and 32 bytes.
+    let bytes: Vec<u8> = Vec::with_capacity(64);
+    let words = Box::new([7u32; 8]);
+    black_box((&bytes, &words));
+}
@@ -0,0 +1,11 @@
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+use nexus_rt::nexus_assert;
+
+#[nexus_rt::main]
+#[nexus_rt::public_input(x)]
+fn main(x: u32) -> u32 {
+    nexus_assert(x < 10, 3);
+    nexus_assert(x % 2 == 0, 7);
+    x
+}
@@ -0,0 +1,8 @@
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+#[nexus_rt::main]
+#[nexus_rt::public_input(x)]
+fn main(x: u32) -> u32 {
+    // Built with the Zbb extension, these compile to `clz` and `cpop`.
+    x.leading_zeros() * 100 + x.count_ones()
+}
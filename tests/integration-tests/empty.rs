@@ -0,0 +1,4 @@
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+#[nexus_rt::main]
+fn main() {}
@@ -0,0 +1,14 @@
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+use core::hint::black_box;
+
+#[nexus_rt::main]
+fn main() {
+    // Built with the F extension, these compile to hardware floating-point instructions.
+    let x = black_box(3u32) as f32;
+    let y = black_box(4u32) as f32;
+    let ratio = black_box((x * x + y * y) / 2.0);
+
+    assert_eq!(ratio as u32, 12);
+    assert_eq!((-ratio) as i32, -12);
+}
@@ -0,0 +1,15 @@
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::hint::black_box;
+
+#[nexus_rt::main]
+fn main() {
+    // Allocate 1 MiB at a time, never freeing, until the heap runs into the stack.
+    loop {
+        let chunk: Vec<u8> = Vec::with_capacity(1 << 20);
+        core::mem::forget(black_box(chunk));
+    }
+}
@@ -0,0 +1,11 @@
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+use nexus_rt::host_call;
+
+const PRICE_FEED: u32 = 0x1000;
+
+#[nexus_rt::main]
+#[nexus_rt::public_input(asset)]
+fn main(asset: u32) -> u32 {
+    host_call(PRICE_FEED, [asset, 0, 0, 0]) * 2
+}
@@ -0,0 +1,10 @@
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+use nexus_rt::LogLevel;
+
+#[nexus_rt::main]
+fn main() {
+    nexus_rt::log!(LogLevel::Info, "starting");
+    nexus_rt::log!(LogLevel::Debug, "x = {}", 7);
+    nexus_rt::log!(LogLevel::Warn, "done");
+}
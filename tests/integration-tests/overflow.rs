@@ -0,0 +1,7 @@
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+#[nexus_rt::main]
+fn main() -> u32 {
+    // Overflows, which panics when overflow checks are enabled (e.g., in debug builds).
+    core::hint::black_box(u32::MAX) + 1
+}
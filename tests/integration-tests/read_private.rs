@@ -0,0 +1,9 @@
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+use nexus_rt::read_private;
+
+#[nexus_rt::main]
+#[nexus_rt::public_input(n)]
+fn main(n: u32) -> u32 {
+    (0..n).map(|_| read_private::<u32>().unwrap()).sum()
+}
@@ -0,0 +1,29 @@
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+/// A signature of `MESSAGE_HASH` under `PUBLIC_KEY`.
+const MESSAGE_HASH: [u8; 32] = [
+    0x2a, 0xf7, 0xe6, 0xf8, 0x11, 0x22, 0x7d, 0x60, 0x9c, 0x9d, 0x44, 0xd6, 0xd3, 0xa0, 0x46, 0x62,
+    0x5c, 0x45, 0xb0, 0xc7, 0xcb, 0x2e, 0x19, 0x73, 0xef, 0xef, 0xbf, 0x4b, 0xf1, 0x21, 0xc1, 0x9b,
+];
+const SIGNATURE: [u8; 64] = [
+    0x17, 0xf5, 0x32, 0x89, 0xea, 0xc9, 0x61, 0xe5, 0xad, 0xc8, 0x58, 0xd3, 0xca, 0x50, 0xda, 0xb0,
+    0x56, 0xdd, 0xca, 0x7a, 0x1a, 0x90, 0x6c, 0x08, 0x15, 0xa0, 0x36, 0x93, 0x12, 0xd1, 0xaa, 0x49,
+    0x3b, 0x10, 0xab, 0xc8, 0x49, 0x17, 0xe9, 0xcf, 0x23, 0xf0, 0x39, 0x7f, 0x3f, 0x7e, 0x39, 0x42,
+    0x44, 0x00, 0x5d, 0xb2, 0xa9, 0xda, 0x89, 0x40, 0x26, 0x78, 0x34, 0xb7, 0xea, 0x01, 0x51, 0xdd,
+];
+const PUBLIC_KEY: [u8; 64] = [
+    0x13, 0x02, 0x4f, 0x02, 0x83, 0x1c, 0x4b, 0xe9, 0x30, 0xaf, 0x82, 0x71, 0x9a, 0x0c, 0x04, 0xdd,
+    0xe5, 0x08, 0x53, 0xc0, 0xa7, 0x07, 0x7a, 0xf0, 0xb6, 0x92, 0xac, 0xa9, 0x28, 0x3b, 0x49, 0x0e,
+    0xf8, 0xf1, 0xab, 0xb8, 0x35, 0xfa, 0x83, 0xa3, 0xb5, 0xaf, 0xf2, 0x11, 0xac, 0xa5, 0xd0, 0xa7,
+    0x9b, 0x30, 0x92, 0x52, 0x46, 0x68, 0x61, 0xb0, 0xde, 0x2d, 0xc5, 0xda, 0xcd, 0x58, 0x08, 0xe6,
+];
+
+#[nexus_rt::main]
+#[nexus_rt::public_input(tamper)]
+fn main(tamper: bool) -> bool {
+    let mut message_hash = MESSAGE_HASH;
+    if tamper {
+        message_hash[0] ^= 1;
+    }
+    nexus_rt::secp256k1::verify(&message_hash, &SIGNATURE, &PUBLIC_KEY)
+}
@@ -0,0 +1,13 @@
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+#[nexus_rt::main]
+fn main() {
+    // Read freshly allocated heap memory that was never written, which the zkVM zero-initializes.
+    let buffer: Vec<u32> = Vec::with_capacity(16);
+    let value = unsafe { core::ptr::read_volatile(buffer.as_ptr().add(8)) };
+    assert_eq!(value, 0);
+}
@@ -2,19 +2,50 @@
 mod test {
     use nexus_common::memory::alignment::Alignable;
     use nexus_common_testing::emulator::{
-        compile_multi, emulate, parse_output, EmulatorType, IOArgs, Input, Output,
+        compile_guest_project, compile_multi, emulate, parse_output, setup_guest_project,
+        write_guest_source_code, EmulatorType, IOArgs, Input, Output,
     };
     use nexus_common_testing::program_trace;
+    use nexus_sdk::{
+        compile::{cargo::CargoPackager, Compile, Compiler},
+        error::{
+            ConfigurationError, IOError, ProofEncodingError, ProofStoreError, ProveAborted,
+            ProveCancelled, ProvingKeyError, ProvingServiceError, VerifyError,
+        },
+        merkle, rand,
+        stwo::{
+            network::{JobId, JobStatus, ProvingRequest, ProvingService},
+            seq::{
+                self, ChainLink, ProofEncoding, ProverConfig, ProvingConfig, ProvingLint, Severity,
+                Stwo, Warning,
+            },
+            store::{FsProofStore, ProofStore},
+        },
+        AssociatedData, ByGuestCompilation, IoSchema, IoType, KnownExitCodes, Local, Network,
+        Prover, Verifiable, Viewable,
+    };
     use nexus_vm::elf::ElfFile;
-    use nexus_vm::emulator::InternalView;
+    use nexus_vm::emulator::{
+        AllocStats, Emulator, FloatPolicy, HaltReason, HarvardEmulator, InputTranscript,
+        InternalView, LogLevel, TrapKind,
+    };
+    use nexus_vm::error::VMErrorKind;
     use nexus_vm::trace::{k_trace, k_trace_direct};
+    use nexus_vm::WORD_SIZE;
     use nexus_vm_prover::{
         extensions::ExtensionComponent,
         machine::{BaseComponent, Machine},
-        prove, verify,
+        prove,
+        trace::program::program_steps,
+        verify, ProvePhase,
     };
     use postcard::to_allocvec_cobs;
     use serial_test::serial;
+    use std::collections::HashMap;
+    use std::io::Cursor;
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
     const K: usize = 1;
 
     const EXAMPLES: &[&str] = &[
@@ -164,6 +195,122 @@ mod test {
         verify(proof, &view).unwrap();
     }
 
+    #[test]
+    #[serial]
+    fn test_replay_input_transcript() {
+        let elfs = compile_multi(
+            "examples/src/bin/io/input_output",
+            &["-C opt-level=3"],
+            &HOME_PATH,
+        );
+
+        let mut prover = Stwo::<Local>::new(&elfs[0]).unwrap();
+        prover.set_associated_data(&[0xde, 0xad]).unwrap();
+        let (view, proof) = prover.prove_with_input::<u32, u32>(&4, &3).unwrap();
+        let transcript = view.input_transcript().unwrap();
+
+        let (replay_view, replay_proof) = Stwo::<Local>::new(&elfs[0])
+            .unwrap()
+            .replay(&transcript, None)
+            .unwrap();
+
+        assert_eq!(replay_view.public_output::<u32>().unwrap(), 12);
+        assert_eq!(replay_view.input_transcript().unwrap(), transcript);
+        assert_eq!(
+            postcard::to_allocvec(&replay_proof).unwrap(),
+            postcard::to_allocvec(&proof).unwrap()
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_emulate_secp256k1_verify() {
+        // Verification runs on RV32IM instructions alone, so the second pass, which is what gets proven, accepts it.
+        test_example_multi(
+            vec![EmulatorType::TwoPass],
+            vec!["-C opt-level=3"],
+            "tests/integration-tests/secp256k1_verify",
+            vec![
+                IOArgs::<bool, (), bool>::new(Some(false), None, Some(true)),
+                IOArgs::<bool, (), bool>::new(Some(true), None, Some(false)),
+            ],
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_lazy_private_input() {
+        let elfs = compile_multi(
+            "tests/integration-tests/read_private",
+            &["-C opt-level=3"],
+            &HOME_PATH,
+        );
+
+        let mut values = 1u32..;
+        let (view, proof) = Stwo::<Local>::new(&elfs[0])
+            .unwrap()
+            .with_input_provider(move || {
+                Ok(Some(
+                    seq::encode_private_input(&values.next().unwrap()).unwrap(),
+                ))
+            })
+            .prove_with_input::<(), u32>(&(), &3)
+            .unwrap();
+
+        assert_eq!(view.public_output::<u32>().unwrap(), 6);
+        proof.verify(&view).unwrap();
+
+        // Only the objects read by the guest are recorded, and they replay identically.
+        let transcript = view.input_transcript().unwrap();
+        let (replay_view, _) = Stwo::<Local>::new(&elfs[0])
+            .unwrap()
+            .replay(&transcript, None)
+            .unwrap();
+        assert_eq!(replay_view.public_output::<u32>().unwrap(), 6);
+        assert_eq!(replay_view.input_transcript().unwrap(), transcript);
+    }
+
+    #[test]
+    #[serial]
+    fn test_host_call() {
+        let elfs = compile_multi(
+            "tests/integration-tests/host_call",
+            &["-C opt-level=3"],
+            &HOME_PATH,
+        );
+
+        let price_feed = |args: &[u32]| args[0] * 100;
+        let view = Stwo::<Local>::new(&elfs[0])
+            .unwrap()
+            .with_syscall(0x1000, price_feed)
+            .unwrap()
+            .run_with_input::<(), u32>(&(), &7)
+            .unwrap();
+
+        assert_eq!(view.public_output::<u32>().unwrap(), 1400);
+        assert_eq!(view.host_calls().len(), 1);
+        assert_eq!(view.host_calls()[0].args[0], 7);
+        assert_eq!(view.host_calls()[0].result, 700);
+
+        // Only reserved syscall codes can be registered.
+        assert!(Stwo::<Local>::new(&elfs[0])
+            .unwrap()
+            .with_syscall(0x400, price_feed)
+            .is_err());
+
+        // The results of host functions cannot be proven yet.
+        let err = Stwo::<Local>::new(&elfs[0])
+            .unwrap()
+            .with_syscall(0x1000, price_feed)
+            .unwrap()
+            .prove_with_input::<(), u32>(&(), &7)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            seq::Error::ConfigurationError(ConfigurationError::UnprovableHostCalls(1))
+        ));
+    }
+
     #[test]
     #[serial]
     fn test_emulate_fact() {
@@ -527,6 +674,121 @@ mod test {
         verify(proof, &view).unwrap();
     }
 
+    #[test]
+    #[serial]
+    fn test_prove_hashed_output() {
+        let elfs = compile_multi(
+            "examples/src/bin/io/hashed_output",
+            &["-C opt-level=3"],
+            &HOME_PATH,
+        );
+        let output: Vec<u32> = (0..1024u32).map(|i| i * i).collect();
+
+        let prover = Stwo::<Local>::new(&elfs[0]).unwrap();
+        let (_, proof) = prover
+            .prove_with_committed_output::<(), ()>(&(), &())
+            .unwrap();
+        assert_eq!(proof.output_root(), Some(&merkle::output_digest(&output)));
+
+        // Verifiers may check either the full output or only its digest.
+        proof
+            .verify_expected_hashed_output::<(), Vec<u32>>(
+                &(),
+                KnownExitCodes::ExitSuccess as u32,
+                &output,
+                &elfs[0],
+                &[],
+            )
+            .unwrap();
+        proof
+            .verify_expected_committed_output::<()>(
+                &(),
+                KnownExitCodes::ExitSuccess as u32,
+                &elfs[0],
+                &[],
+            )
+            .unwrap();
+
+        let mut tampered = output.clone();
+        tampered[7] += 1;
+        assert!(proof
+            .verify_expected_hashed_output::<(), Vec<u32>>(
+                &(),
+                KnownExitCodes::ExitSuccess as u32,
+                &tampered,
+                &elfs[0],
+                &[],
+            )
+            .is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_prove_seeded_randomness() {
+        let elfs = compile_multi(
+            "examples/src/bin/io/rand_seed",
+            &["-C opt-level=3"],
+            &HOME_PATH,
+        );
+        let seed = [7u8; 32];
+
+        let prover = Stwo::<Local>::new(&elfs[0]).unwrap();
+        let (view, proof) = prover.prove_with_input::<(), [u8; 32]>(&(), &seed).unwrap();
+
+        // The host recomputes the stream the guest drew from the seed.
+        let mut rng = rand::Rng::new(seed);
+        let expected: [u32; 10] = core::array::from_fn(|_| rng.next_u32());
+        assert_eq!(view.public_output::<[u32; 10]>().unwrap(), expected);
+
+        proof
+            .verify_expected::<[u8; 32], [u32; 10]>(
+                &seed,
+                KnownExitCodes::ExitSuccess as u32,
+                &expected,
+                &elfs[0],
+                &[],
+            )
+            .unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_prove_merkle_committed_output() {
+        let elfs = compile_multi(
+            "examples/src/bin/io/merkle_output",
+            &["-C opt-level=3"],
+            &HOME_PATH,
+        );
+        let output: Vec<[u8; 4]> = (0..256u32).map(|i| (i * i).to_le_bytes()).collect();
+
+        let prover = Stwo::<Local>::new(&elfs[0]).unwrap();
+        let (_, proof) = prover
+            .prove_with_committed_output::<(), ()>(&(), &())
+            .unwrap();
+        assert_eq!(proof.output_root(), Some(&merkle::root(&output)));
+
+        proof
+            .verify_expected_committed_output::<()>(
+                &(),
+                KnownExitCodes::ExitSuccess as u32,
+                &elfs[0],
+                &[],
+            )
+            .unwrap();
+
+        for index in [3, 200] {
+            let path = merkle::path(&output, index).unwrap();
+            proof
+                .verify_output_leaf(index, &output[index], &path)
+                .unwrap();
+
+            // A different element must not open at the same position.
+            assert!(proof
+                .verify_output_leaf(index, &output[index + 1], &path)
+                .is_err());
+        }
+    }
+
     #[test]
     #[serial]
     fn test_emulate_fail() {
@@ -554,6 +816,40 @@ mod test {
         verify(proof, &view).unwrap();
     }
 
+    #[test]
+    #[serial]
+    fn test_prove_halt_reason() {
+        let exit_elfs = compile_multi(
+            "tests/integration-tests/empty",
+            &["-C opt-level=3"],
+            &HOME_PATH,
+        );
+        let trap_elfs = compile_multi("examples/src/bin/fail", &["-C opt-level=3"], &HOME_PATH);
+
+        let (exit_view, exit_proof) = Stwo::<Local>::new(&exit_elfs[0]).unwrap().prove().unwrap();
+        let (trap_view, trap_proof) = Stwo::<Local>::new(&trap_elfs[0]).unwrap().prove().unwrap();
+
+        assert_eq!(exit_view.halt_reason(), HaltReason::Exit(0));
+        assert_eq!(trap_view.halt_reason(), HaltReason::Trap(TrapKind::Panic));
+
+        let clean_exit = HaltReason::Exit(0);
+        exit_proof
+            .verify_expected_halt::<(), ()>(&(), clean_exit, &(), &exit_elfs[0], &[])
+            .unwrap();
+        assert!(trap_proof
+            .verify_expected_halt::<(), ()>(&(), clean_exit, &(), &trap_elfs[0], &[])
+            .is_err());
+        trap_proof
+            .verify_expected_halt::<(), ()>(
+                &(),
+                HaltReason::Trap(TrapKind::Panic),
+                &(),
+                &trap_elfs[0],
+                &[],
+            )
+            .unwrap();
+    }
+
     #[test]
     #[serial]
     #[ignore]
@@ -706,6 +1002,959 @@ mod test {
         }
     }
 
+    #[test]
+    #[serial]
+    fn test_prove_fixed_shape() {
+        let elfs = compile_multi(
+            "tests/integration-tests/fib",
+            &["-C opt-level=3"],
+            &HOME_PATH,
+        );
+
+        let sizes: Vec<usize> = [1u32, 20u32]
+            .iter()
+            .map(|input| {
+                let mut prover = Stwo::<Local>::new(&elfs[0]).unwrap().with_fixed_shape(true);
+                prover.limits.cycle_limit = Some(1 << 16);
+                let (_, proof) = prover.prove_with_input::<(), u32>(&(), input).unwrap();
+                proof.size_estimate()
+            })
+            .collect();
+
+        assert_eq!(sizes[0], sizes[1]);
+
+        // Without a cycle limit there is nothing to size the shape by.
+        let prover = Stwo::<Local>::new(&elfs[0]).unwrap().with_fixed_shape(true);
+        assert!(matches!(
+            prover.prove_with_input::<(), u32>(&(), &1),
+            Err(seq::Error::ConfigurationError(
+                ConfigurationError::UnboundedShape
+            ))
+        ));
+    }
+
+    #[test]
+    #[serial]
+    fn test_prove_full_padding_warning() {
+        let elfs = compile_multi(
+            "tests/integration-tests/fib",
+            &["-C opt-level=3"],
+            &HOME_PATH,
+        );
+
+        let mut prover = Stwo::<Local>::new(&elfs[0]).unwrap().with_fixed_shape(true);
+        prover.limits.cycle_limit = Some(1 << 16);
+        let output = prover.prove_full_with_input::<(), u32>(&(), &1).unwrap();
+
+        assert!(output
+            .warnings
+            .iter()
+            .any(|warning| matches!(warning, Warning::TracePadding { .. })));
+        output.proof.verify(&output.view).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_verify_expected_with_timeout() {
+        let elfs = compile_multi(
+            "tests/integration-tests/fib",
+            &["-C opt-level=3"],
+            &HOME_PATH,
+        );
+
+        let prover = Stwo::<Local>::new(&elfs[0]).unwrap();
+        let (view, proof) = prover.prove_with_input::<(), u32>(&(), &10).unwrap();
+        let output = view.public_output::<u32>().unwrap();
+
+        proof
+            .verify_expected_with_timeout::<u32, u32>(
+                &10,
+                KnownExitCodes::ExitSuccess as u32,
+                &output,
+                &elfs[0],
+                &[],
+                Duration::from_secs(600),
+            )
+            .unwrap();
+
+        let result = proof.verify_expected_with_timeout::<u32, u32>(
+            &10,
+            KnownExitCodes::ExitSuccess as u32,
+            &output,
+            &elfs[0],
+            &[],
+            Duration::from_nanos(1),
+        );
+        assert!(matches!(
+            result,
+            Err(seq::Error::VerifyError(VerifyError::Timeout(_)))
+        ));
+    }
+
+    #[test]
+    #[serial]
+    fn test_associated_data_fields() {
+        let elfs = compile_multi(
+            "tests/integration-tests/fib",
+            &["-C opt-level=3"],
+            &HOME_PATH,
+        );
+
+        let prover_ad = AssociatedData::new()
+            .field("chain", b"testnet")
+            .field("nonce", 7u64.to_le_bytes());
+
+        let mut prover = Stwo::<Local>::new(&elfs[0]).unwrap();
+        prover.set_associated_data(&prover_ad.encode()).unwrap();
+        let (view, proof) = prover.prove_with_input::<(), u32>(&(), &10).unwrap();
+        let output = view.public_output::<u32>().unwrap();
+
+        // The verifier adds the same fields in a different order.
+        let verifier_ad = AssociatedData::new()
+            .field("nonce", 7u64.to_le_bytes())
+            .field("chain", b"testnet");
+
+        proof
+            .verify_expected::<u32, u32>(
+                &10,
+                KnownExitCodes::ExitSuccess as u32,
+                &output,
+                &elfs[0],
+                &verifier_ad.encode(),
+            )
+            .unwrap();
+
+        let mismatched_ad = verifier_ad.field("nonce", 8u64.to_le_bytes());
+
+        assert!(proof
+            .verify_expected::<u32, u32>(
+                &10,
+                KnownExitCodes::ExitSuccess as u32,
+                &output,
+                &elfs[0],
+                &mismatched_ad.encode(),
+            )
+            .is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_prove_with_proving_key() {
+        let elfs = compile_multi(
+            "tests/integration-tests/fib",
+            &["-C opt-level=3", "-C opt-level=0"],
+            &HOME_PATH,
+        );
+
+        let mut prover = Stwo::<Local>::new(&elfs[0]).unwrap();
+        assert!(matches!(
+            prover.proving_key(),
+            Err(seq::Error::ConfigurationError(
+                ConfigurationError::UnboundedShape
+            ))
+        ));
+        prover.limits.cycle_limit = Some(1 << 16);
+        let key = prover.proving_key().unwrap();
+
+        for input in [5u32, 10u32] {
+            let prover = Stwo::<Local>::new(&elfs[0]).unwrap();
+            let (view, proof) = prover.prove_with_key::<(), u32>(&key, &(), &input).unwrap();
+
+            proof
+                .verify_expected::<u32, u32>(
+                    &input,
+                    KnownExitCodes::ExitSuccess as u32,
+                    &view.public_output::<u32>().unwrap(),
+                    &elfs[0],
+                    &[],
+                )
+                .unwrap();
+        }
+
+        let prover = Stwo::<Local>::new(&elfs[1]).unwrap();
+        let result = prover.prove_with_key::<(), u32>(&key, &(), &5);
+        assert!(matches!(
+            result,
+            Err(seq::Error::ProvingKeyError(
+                ProvingKeyError::ProgramMismatch
+            ))
+        ));
+    }
+
+    #[test]
+    #[serial]
+    fn test_prove_batch() {
+        let elfs = compile_multi(
+            "tests/integration-tests/fib",
+            &["-C opt-level=3"],
+            &HOME_PATH,
+        );
+
+        let mut prover = Stwo::<Local>::new(&elfs[0]).unwrap();
+        prover.limits.cycle_limit = Some(1 << 16);
+        let key = prover.proving_key().unwrap();
+        let inputs = [((), 5u32), ((), 10u32), ((), 12u32)];
+        let proven = prover.prove_batch(&key, &inputs).unwrap();
+
+        assert_eq!(proven.len(), inputs.len());
+        for ((_, input), (view, proof)) in inputs.iter().zip(proven) {
+            proof
+                .verify_expected::<u32, u32>(
+                    input,
+                    KnownExitCodes::ExitSuccess as u32,
+                    &view.public_output::<u32>().unwrap(),
+                    &elfs[0],
+                    &[],
+                )
+                .unwrap();
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_verify_chained() {
+        let elfs = compile_multi(
+            "tests/integration-tests/fib",
+            &["-C opt-level=3"],
+            &HOME_PATH,
+        );
+
+        let prover = Stwo::<Local>::new(&elfs[0]).unwrap();
+        let (view_a, proof_a) = prover.prove_with_input::<(), u32>(&(), &7).unwrap();
+        let intermediate = view_a.public_output::<u32>().unwrap();
+
+        let prover = Stwo::<Local>::new(&elfs[0]).unwrap();
+        let (view_b, proof_b) = prover
+            .prove_with_input::<(), u32>(&(), &intermediate)
+            .unwrap();
+        let output = view_b.public_output::<u32>().unwrap();
+
+        let link = |proof| ChainLink {
+            proof,
+            elf: &elfs[0],
+            ad: &[],
+            exit_code: KnownExitCodes::ExitSuccess as u32,
+        };
+
+        seq::Proof::verify_chained::<u32, u32>(&[link(&proof_a), link(&proof_b)], &7, &output)
+            .unwrap();
+
+        // The output of the second execution is not the input of the first.
+        let err = seq::Proof::verify_chained::<u32, u32>(
+            &[link(&proof_b), link(&proof_a)],
+            &intermediate,
+            &intermediate,
+        )
+        .unwrap_err();
+        assert!(matches!(err, seq::Error::VerificationError(_)));
+
+        let err =
+            seq::Proof::verify_chained::<u32, u32>(&[link(&proof_a), link(&proof_b)], &8, &output)
+                .unwrap_err();
+        assert!(matches!(
+            err,
+            seq::Error::VerifyError(VerifyError::ChainMismatch(0))
+        ));
+
+        let err = seq::Proof::verify_chained::<u32, u32>(&[], &7, &output).unwrap_err();
+        assert!(matches!(
+            err,
+            seq::Error::VerifyError(VerifyError::EmptyChain)
+        ));
+    }
+
+    #[test]
+    #[serial]
+    fn test_emulate_arithmetic_overflow() {
+        let elfs = compile_multi(
+            "tests/integration-tests/overflow",
+            &["-C opt-level=0 -C overflow-checks=on"],
+            &HOME_PATH,
+        );
+
+        // Without reporting enabled, the overflow is an ordinary panic exit.
+        let mut emulator = HarvardEmulator::from_elf(&elfs[0], &[], &[]);
+        let err = emulator.execute(false).unwrap_err();
+        assert_eq!(err.source, VMErrorKind::VMExited(1));
+
+        let mut emulator = HarvardEmulator::from_elf(&elfs[0], &[], &[]);
+        emulator.get_executor_mut().report_arithmetic_overflow = true;
+        let err = emulator.execute(false).unwrap_err();
+        assert!(matches!(
+            err.source,
+            VMErrorKind::ArithmeticOverflow { ref message, .. } if message == "attempt to add with overflow"
+        ));
+    }
+
+    #[test]
+    #[serial]
+    fn test_alloc_stats() {
+        let elfs = compile_multi(
+            "tests/integration-tests/alloc_pattern",
+            &["-C opt-level=0"],
+            &HOME_PATH,
+        );
+
+        let view = Stwo::<Local>::new(&elfs[0]).unwrap().run().unwrap();
+        assert_eq!(
+            view.alloc_stats(),
+            AllocStats {
+                allocations: 2,
+                frees: 0,
+                peak_bytes: 96,
+                bytes_leaked: 96,
+            }
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_structured_logs() {
+        let elfs = compile_multi(
+            "tests/integration-tests/log_levels",
+            &["-C opt-level=3"],
+            &HOME_PATH,
+        );
+
+        let view = Stwo::<Local>::new(&elfs[0]).unwrap().run().unwrap();
+
+        let records = view.structured_logs(LogLevel::Trace).unwrap();
+        let messages: Vec<_> = records.iter().map(|r| r.message.as_str()).collect();
+        assert_eq!(messages, ["starting", "x = 7", "done"]);
+        assert!(records.windows(2).all(|w| w[0].cycle < w[1].cycle));
+
+        let records = view.structured_logs(LogLevel::Info).unwrap();
+        assert_eq!(
+            records.iter().map(|r| r.level).collect::<Vec<_>>(),
+            [LogLevel::Info, LogLevel::Warn]
+        );
+
+        // The records remain visible through the plain logs.
+        assert!(view
+            .logs()
+            .unwrap()
+            .contains(&"[DEBUG] x = 7\n".to_string()));
+    }
+
+    #[test]
+    #[serial]
+    fn test_emulate_heap_stack_collision() {
+        let elfs = compile_multi(
+            "tests/integration-tests/heap_stack_collision",
+            &["-C opt-level=3"],
+            &HOME_PATH,
+        );
+
+        let mut emulator = HarvardEmulator::from_elf(&elfs[0], &[], &[]);
+        let err = emulator.execute(false).unwrap_err();
+        assert!(matches!(
+            err.source,
+            VMErrorKind::HeapStackCollision { heap_end, sp, .. } if heap_end > sp
+        ));
+    }
+
+    #[test]
+    #[serial]
+    fn test_emulate_float_policy() {
+        let elfs = compile_multi(
+            "tests/integration-tests/float_ops",
+            &["-C opt-level=3 -C target-feature=+f"],
+            &HOME_PATH,
+        );
+
+        // Float instructions are rejected by default.
+        let mut emulator = HarvardEmulator::from_elf(&elfs[0], &[], &[]);
+        let err = emulator.execute(false).unwrap_err();
+        assert!(matches!(err.source, VMErrorKind::UnsupportedFloat { .. }));
+
+        // Under soft-float, the guest's assertions on the results hold.
+        let mut emulator = HarvardEmulator::from_elf(&elfs[0], &[], &[]);
+        emulator.executor.float_policy = FloatPolicy::SoftFloat;
+        let err = emulator.execute(false).unwrap_err();
+        assert_eq!(err.source, VMErrorKind::VMExited(0));
+    }
+
+    #[test]
+    #[serial]
+    fn test_zbb_emulation_only() {
+        let elfs = compile_multi(
+            "tests/integration-tests/bit_count",
+            &["-C opt-level=3 -C target-feature=+zbb"],
+            &HOME_PATH,
+        );
+
+        // Zbb instructions are rejected by default.
+        assert!(Stwo::<Local>::new(&elfs[0])
+            .unwrap()
+            .run_with_input::<(), u32>(&(), &0xf0)
+            .is_err());
+
+        // In emulation-only mode the program runs, but cannot be proven.
+        let view = Stwo::<Local>::new(&elfs[0])
+            .unwrap()
+            .with_zbb_emulation()
+            .run_with_input::<(), u32>(&(), &0xf0)
+            .unwrap();
+        assert_eq!(view.public_output::<u32>().unwrap(), 2404);
+
+        let err = Stwo::<Local>::new(&elfs[0])
+            .unwrap()
+            .with_zbb_emulation()
+            .prove_with_input::<(), u32>(&(), &0xf0)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            seq::Error::ConfigurationError(ConfigurationError::EmulationOnly)
+        ));
+    }
+
+    #[test]
+    #[serial]
+    fn test_emulate_uninitialized_read() {
+        let elfs = compile_multi(
+            "tests/integration-tests/uninitialized_read",
+            &["-C opt-level=3"],
+            &HOME_PATH,
+        );
+
+        // Uninitialized memory reads as zero by default.
+        let mut emulator = HarvardEmulator::from_elf(&elfs[0], &[], &[]);
+        let err = emulator.execute(false).unwrap_err();
+        assert_eq!(err.source, VMErrorKind::VMExited(0));
+
+        let mut emulator = HarvardEmulator::from_elf(&elfs[0], &[], &[]);
+        emulator.executor.trap_uninitialized_reads = true;
+        let err = emulator.execute(false).unwrap_err();
+        assert!(matches!(err.source, VMErrorKind::UninitializedRead { .. }));
+    }
+
+    #[test]
+    #[serial]
+    fn test_io_schema_mismatch() {
+        #[derive(IoType)]
+        #[allow(dead_code)]
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+        assert_eq!(
+            IoSchema::of::<Vec<(u32, String)>, Option<Point>>(),
+            IoSchema {
+                public_input: "Vec<(u32, String)>".into(),
+                public_output: Some("Option<Point { x: i32, y: i32 }>".into()),
+            }
+        );
+
+        let elfs = compile_multi(
+            "tests/integration-tests/fib",
+            &["-C opt-level=3"],
+            &HOME_PATH,
+        );
+
+        let prover = Stwo::<Local>::new(&elfs[0]).unwrap();
+        let (view, proof) = prover.prove_typed::<(), u32, u32>(&(), &10).unwrap();
+        let output = view.public_output::<u32>().unwrap();
+        assert_eq!(proof.io_schema(), Some(&IoSchema::of::<u32, u32>()));
+
+        proof
+            .verify_typed::<u32, u32>(
+                &10,
+                KnownExitCodes::ExitSuccess as u32,
+                &output,
+                &elfs[0],
+                &[],
+            )
+            .unwrap();
+
+        // A u64 encodes 10 identically to a u32, but is rejected as the wrong type.
+        let err = proof
+            .verify_typed::<u64, u32>(
+                &10,
+                KnownExitCodes::ExitSuccess as u32,
+                &output,
+                &elfs[0],
+                &[],
+            )
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            seq::Error::GuestIOError(IOError::IoSchemaMismatch { .. })
+        ));
+
+        // The schema survives the wire format.
+        let decoded = seq::Proof::from_bytes(&proof.to_bytes()).unwrap();
+        assert_eq!(decoded.io_schema(), proof.io_schema());
+
+        // A proof without a schema still verifies against expected values, but not as typed ones.
+        let transcript = view.input_transcript().unwrap();
+        let (view, proof) = Stwo::<Local>::new(&elfs[0])
+            .unwrap()
+            .replay(&transcript, None)
+            .unwrap();
+        proof.verify(&view).unwrap();
+        proof
+            .verify_expected::<u32, u32>(
+                &10,
+                KnownExitCodes::ExitSuccess as u32,
+                &output,
+                &elfs[0],
+                &[],
+            )
+            .unwrap();
+        assert!(matches!(
+            proof.verify_typed::<u32, u32>(
+                &10,
+                KnownExitCodes::ExitSuccess as u32,
+                &output,
+                &elfs[0],
+                &[],
+            ),
+            Err(seq::Error::GuestIOError(IOError::MissingIoSchema))
+        ));
+    }
+
+    #[test]
+    #[serial]
+    fn test_prove_failed_assertion() {
+        let elfs = compile_multi(
+            "tests/integration-tests/assert_id",
+            &["-C opt-level=3"],
+            &HOME_PATH,
+        );
+
+        let prover = Stwo::<Local>::new(&elfs[0]).unwrap();
+        let (view, proof) = prover.prove_with_input::<(), u32>(&(), &5).unwrap();
+
+        assert_eq!(
+            view.exit_code().unwrap(),
+            KnownExitCodes::ExitAssertionFailed as u32
+        );
+        let id = view.public_output::<u32>().unwrap();
+        assert_eq!(id, 7);
+
+        proof
+            .verify_expected::<u32, u32>(
+                &5,
+                KnownExitCodes::ExitAssertionFailed as u32,
+                &7,
+                &elfs[0],
+                &[],
+            )
+            .unwrap();
+
+        // The proof does not attest to a different assertion failing.
+        assert!(proof
+            .verify_expected::<u32, u32>(
+                &5,
+                KnownExitCodes::ExitAssertionFailed as u32,
+                &3,
+                &elfs[0],
+                &[],
+            )
+            .is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_program_digest_independent_of_build_path() {
+        let home = std::env::current_dir().unwrap();
+
+        // Build the same guest in two different temporary projects.
+        let digests: Vec<merkle::Digest> = (0..2)
+            .map(|_| {
+                let tmp_dir = setup_guest_project(&PathBuf::from(HOME_PATH).join("runtime"));
+                let tmp_project_path = tmp_dir.path().join("integration");
+                write_guest_source_code(
+                    &tmp_project_path,
+                    &format!("{HOME_PATH}/tests/integration-tests/assert_id.rs"),
+                );
+
+                std::env::set_current_dir(&tmp_project_path).unwrap();
+                let mut compiler = Compiler::<CargoPackager>::new("integration");
+                compiler.set_unique_build(true);
+                let prover = Stwo::<Local>::compile(&mut compiler);
+                std::env::set_current_dir(&home).unwrap();
+
+                seq::program_digest(&prover.unwrap().elf).unwrap()
+            })
+            .collect();
+
+        assert_eq!(digests[0], digests[1]);
+    }
+
+    #[test]
+    #[serial]
+    fn test_verify_expected_fetching() {
+        let tmp_dir = setup_guest_project(&PathBuf::from(HOME_PATH).join("runtime"));
+        let tmp_project_path = tmp_dir.path().join("integration");
+        let linker_path = PathBuf::from(HOME_PATH).join("runtime/linker-scripts/default.x");
+        let mut compile = |source: &str| {
+            write_guest_source_code(&tmp_project_path, &format!("{HOME_PATH}/{source}"));
+            compile_guest_project(&tmp_project_path, &linker_path, "-C opt-level=3")
+        };
+        let elf_bytes = compile("examples/src/bin/io/input_output.rs");
+        let other_elf_bytes = compile("tests/integration-tests/empty.rs");
+
+        let elf = ElfFile::from_bytes(&elf_bytes).unwrap();
+        let digest = seq::program_digest(&elf).unwrap();
+        let other_digest =
+            seq::program_digest(&ElfFile::from_bytes(&other_elf_bytes).unwrap()).unwrap();
+
+        // An in-memory store of programs by digest.
+        let store: HashMap<merkle::Digest, Vec<u8>> =
+            HashMap::from([(digest, elf_bytes), (other_digest, other_elf_bytes)]);
+        let fetcher = |digest: &merkle::Digest| store.get(digest).cloned().ok_or("not found");
+
+        let prover = Stwo::<Local>::new(&elf).unwrap();
+        let (_, proof) = prover.prove_with_input::<u32, u32>(&4, &3).unwrap();
+
+        proof
+            .verify_expected_fetching::<u32, u32, _>(&3, 0, &12, &digest, &[], fetcher)
+            .unwrap();
+
+        // A store serving a different program under the digest is caught before verifying.
+        let mismatched = |_: &merkle::Digest| Ok::<_, String>(store[&other_digest].clone());
+        assert!(matches!(
+            proof.verify_expected_fetching::<u32, u32, _>(&3, 0, &12, &digest, &[], mismatched),
+            Err(seq::Error::VerifyError(VerifyError::ProgramDigestMismatch))
+        ));
+
+        assert!(matches!(
+            proof.verify_expected_fetching::<u32, u32, _>(&3, 0, &12, &[0; 32], &[], fetcher),
+            Err(seq::Error::VerifyError(VerifyError::FetchError(_)))
+        ));
+    }
+
+    #[test]
+    #[serial]
+    fn test_verify_expected_from_reader() {
+        let tmp_dir = setup_guest_project(&PathBuf::from(HOME_PATH).join("runtime"));
+        let tmp_project_path = tmp_dir.path().join("integration");
+        write_guest_source_code(
+            &tmp_project_path,
+            &format!("{HOME_PATH}/examples/src/bin/io/input_output.rs"),
+        );
+        let elf_bytes = compile_guest_project(
+            &tmp_project_path,
+            &PathBuf::from(HOME_PATH).join("runtime/linker-scripts/default.x"),
+            "-C opt-level=3",
+        );
+        let elf = ElfFile::from_bytes(&elf_bytes).unwrap();
+
+        // Loading through a reader gives the same program as loading from memory.
+        let streamed = ElfFile::from_reader(Cursor::new(&elf_bytes)).unwrap();
+        assert_eq!(
+            seq::program_digest(&streamed).unwrap(),
+            seq::program_digest(&elf).unwrap()
+        );
+
+        let prover = Stwo::<Local>::new(&elf).unwrap();
+        let (_, proof) = prover.prove_with_input::<u32, u32>(&4, &3).unwrap();
+
+        for (output, ok) in [(12u32, true), (13u32, false)] {
+            let in_memory = proof.verify_expected::<u32, u32>(&3, 0, &output, &elf, &[]);
+            let from_reader = proof.verify_expected_from_reader::<u32, u32, _>(
+                Cursor::new(&elf_bytes),
+                &3,
+                0,
+                &output,
+                &[],
+            );
+            assert_eq!(in_memory.is_ok(), ok);
+            assert_eq!(from_reader.is_ok(), ok);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_prove_empty_guest() {
+        let elfs = compile_multi(
+            "tests/integration-tests/empty",
+            &["-C opt-level=3"],
+            &HOME_PATH,
+        );
+
+        let prover = Stwo::<Local>::new(&elfs[0]).unwrap();
+        let (view, proof) = prover.prove().unwrap();
+        assert_eq!(
+            view.exit_code().unwrap(),
+            KnownExitCodes::ExitSuccess as u32
+        );
+
+        proof
+            .verify_expected::<(), ()>(&(), KnownExitCodes::ExitSuccess as u32, &(), &elfs[0], &[])
+            .unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_verify_config() {
+        let elfs = compile_multi(
+            "tests/integration-tests/fib",
+            &["-C opt-level=3"],
+            &HOME_PATH,
+        );
+
+        let mut prover = Stwo::<Local>::new(&elfs[0]).unwrap();
+        prover.limits.cycle_limit = Some(1 << 20);
+        let config = prover.proving_config();
+        let (view, proof) = prover.prove_with_input::<(), u32>(&(), &10).unwrap();
+        let output = view.public_output::<u32>().unwrap();
+
+        proof
+            .verify_expected::<u32, u32>(
+                &10,
+                KnownExitCodes::ExitSuccess as u32,
+                &output,
+                &elfs[0],
+                &[],
+            )
+            .unwrap();
+        proof.verify_config(&config).unwrap();
+
+        // A verifier expecting a different configuration rejects the proof.
+        let other = ProvingConfig::default();
+        assert!(matches!(
+            proof.verify_config(&other),
+            Err(seq::Error::VerifyError(VerifyError::ConfigMismatch))
+        ));
+
+        // The configuration is bound by the proof, so relabeling it breaks verification.
+        let (from, to) = (config.digest().unwrap(), other.digest().unwrap());
+        let mut bytes = proof.to_bytes();
+        let at = bytes.windows(from.len()).position(|w| w == from).unwrap();
+        bytes[at..at + to.len()].copy_from_slice(&to);
+        let relabeled = seq::Proof::from_bytes(&bytes).unwrap();
+        relabeled.verify_config(&other).unwrap();
+        assert!(relabeled
+            .verify_expected::<u32, u32>(
+                &10,
+                KnownExitCodes::ExitSuccess as u32,
+                &output,
+                &elfs[0],
+                &[],
+            )
+            .is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_prove_network() {
+        /// A proving service proving in-process, standing in for a remote one.
+        #[derive(Default)]
+        struct InProcessService {
+            jobs: Mutex<Vec<JobStatus>>,
+            /// Never complete jobs.
+            stall: bool,
+            /// Prove under these parameters rather than the requested ones.
+            prover_config: Option<ProverConfig>,
+        }
+
+        impl ProvingService for InProcessService {
+            fn submit(&self, request: &ProvingRequest) -> Result<JobId, ProvingServiceError> {
+                if request.credentials.as_deref() != Some("secret") {
+                    return Err(ProvingServiceError::Unauthorized);
+                }
+
+                if self.stall {
+                    let mut jobs = self.jobs.lock().unwrap();
+                    jobs.push(JobStatus::Pending);
+                    return Ok((jobs.len() - 1).to_string());
+                }
+
+                let mut prover = Stwo::<Local>::new(&request.elf)
+                    .unwrap()
+                    .with_fixed_shape(request.config.fixed_shape)
+                    .with_prover_config(self.prover_config.unwrap_or(request.config.prover));
+                prover.limits = request.config.limits.clone();
+                let transcript = InputTranscript {
+                    public_input: request.public_input.clone(),
+                    private_input: request.private_input.clone(),
+                    associated_data: request.ad.clone(),
+                };
+                let status = match prover.replay(&transcript, request.io_schema.clone()) {
+                    Ok((_, proof)) => JobStatus::Completed(proof.to_bytes()),
+                    Err(err) => JobStatus::Failed(err.to_string()),
+                };
+
+                let mut jobs = self.jobs.lock().unwrap();
+                jobs.push(status);
+                Ok((jobs.len() - 1).to_string())
+            }
+
+            fn poll(&self, job: &JobId) -> Result<JobStatus, ProvingServiceError> {
+                job.parse::<usize>()
+                    .ok()
+                    .and_then(|index| self.jobs.lock().unwrap().get(index).cloned())
+                    .ok_or_else(|| ProvingServiceError::Transport(format!("unknown job {job}")))
+            }
+
+            fn authenticate(
+                &self,
+                request: &mut ProvingRequest,
+            ) -> Result<(), ProvingServiceError> {
+                request.credentials = Some("secret".to_string());
+                Ok(())
+            }
+
+            fn poll_interval(&self) -> std::time::Duration {
+                std::time::Duration::from_millis(10)
+            }
+
+            fn job_timeout(&self) -> std::time::Duration {
+                std::time::Duration::from_millis(100)
+            }
+        }
+
+        let elfs = compile_multi(
+            "tests/integration-tests/fib",
+            &["-C opt-level=3"],
+            &HOME_PATH,
+        );
+
+        let prover = Stwo::<Network>::new(&elfs[0])
+            .unwrap()
+            .with_service(InProcessService::default());
+        let (view, proof) = prover.prove_with_input::<(), u32>(&(), &10).unwrap();
+        let output = view.public_output::<u32>().unwrap();
+
+        proof
+            .verify_expected::<u32, u32>(
+                &10,
+                KnownExitCodes::ExitSuccess as u32,
+                &output,
+                &elfs[0],
+                &[],
+            )
+            .unwrap();
+
+        // Proofs made under other parameters than the requested ones are rejected.
+        let prover = Stwo::<Network>::new(&elfs[0])
+            .unwrap()
+            .with_service(InProcessService {
+                prover_config: Some(ProverConfig::with_security_bits(128, 2, 16)),
+                ..Default::default()
+            });
+        assert!(matches!(
+            prover.prove_with_input::<(), u32>(&(), &10),
+            Err(seq::Error::ProvingServiceError(
+                ProvingServiceError::InvalidProof
+            ))
+        ));
+
+        // Jobs that do not complete in time are given up on.
+        let prover = Stwo::<Network>::new(&elfs[0])
+            .unwrap()
+            .with_service(InProcessService {
+                stall: true,
+                ..Default::default()
+            });
+        assert!(matches!(
+            prover.prove_with_input::<(), u32>(&(), &10),
+            Err(seq::Error::ProvingServiceError(
+                ProvingServiceError::Timeout(_)
+            ))
+        ));
+
+        // Proving requires a service.
+        let prover = Stwo::<Network>::new(&elfs[0]).unwrap();
+        assert!(matches!(
+            prover.prove_with_input::<(), u32>(&(), &10),
+            Err(seq::Error::ConfigurationError(
+                ConfigurationError::NotYetConfigured
+            ))
+        ));
+    }
+
+    #[test]
+    #[serial]
+    fn test_prove_async() {
+        /// Wakes the thread blocked on a future.
+        struct ThreadWaker(std::thread::Thread);
+
+        impl std::task::Wake for ThreadWaker {
+            fn wake(self: Arc<Self>) {
+                self.0.unpark();
+            }
+        }
+
+        fn block_on<F: std::future::Future>(future: F) -> F::Output {
+            let waker: std::task::Waker = Arc::new(ThreadWaker(std::thread::current())).into();
+            let mut cx = std::task::Context::from_waker(&waker);
+            let mut future = std::pin::pin!(future);
+            loop {
+                match future.as_mut().poll(&mut cx) {
+                    std::task::Poll::Ready(output) => return output,
+                    std::task::Poll::Pending => std::thread::park(),
+                }
+            }
+        }
+
+        let elfs = compile_multi(
+            "tests/integration-tests/fib",
+            &["-C opt-level=3"],
+            &HOME_PATH,
+        );
+
+        let prover = Stwo::<Local>::new(&elfs[0]).unwrap();
+        let (future, progress) = prover.prove_async_with_input::<(), u32>(&(), &10).unwrap();
+        let (view, proof) = block_on(future).unwrap();
+        let output = view.public_output::<u32>().unwrap();
+
+        proof
+            .verify_expected::<u32, u32>(
+                &10,
+                KnownExitCodes::ExitSuccess as u32,
+                &output,
+                &elfs[0],
+                &[],
+            )
+            .unwrap();
+        let progress = progress.progress();
+        assert_eq!(progress.phase, ProvePhase::StarkProof);
+        assert!(progress.steps.is_some_and(|steps| steps > 0));
+        assert!(progress.rows >= progress.steps);
+
+        // Cancelling stops proving before its next phase.
+        let prover = Stwo::<Local>::new(&elfs[0]).unwrap();
+        let (future, progress) = prover.prove_async_with_input::<(), u32>(&(), &10).unwrap();
+        progress.cancel();
+        assert!(matches!(
+            block_on(future),
+            Err(seq::Error::ProveCancelled(ProveCancelled { .. }))
+        ));
+    }
+
+    #[test]
+    #[serial]
+    fn test_estimate() {
+        let elfs = compile_multi(
+            "tests/integration-tests/fib",
+            &["-C opt-level=3"],
+            &HOME_PATH,
+        );
+
+        let prover = Stwo::<Local>::new(&elfs[0]).unwrap();
+        let report = prover.estimate::<(), u32>(&(), &10).unwrap();
+
+        assert!(report.cycles > 0);
+        assert!(report.rows() >= report.cycles);
+        assert_eq!(report.opcode_rows.values().sum::<usize>(), report.cycles);
+        assert_eq!(
+            report.instruction_mix.values().sum::<u64>(),
+            report.cycles as u64
+        );
+        assert!(report.trace_bytes > 0);
+
+        // A larger input takes more cycles.
+        let larger = prover.estimate::<(), u32>(&(), &1000).unwrap();
+        assert!(larger.cycles > report.cycles);
+        assert_eq!(
+            report.project_proving_time(&report, Duration::from_secs(10)),
+            Duration::from_secs(10)
+        );
+    }
+
     #[test]
     #[serial]
     fn test_prove_synthetic_trace() {
@@ -738,4 +1987,286 @@ mod test {
             verify(proof, &view).unwrap();
         }
     }
+
+    #[test]
+    #[serial]
+    fn test_disassemble() {
+        let elfs = compile_multi("examples/src/bin/fib", &["-C opt-level=3"], &HOME_PATH);
+
+        let prover = Stwo::<Local>::new(&elfs[0]).unwrap();
+        let listing = prover.disassemble();
+        assert_eq!(listing.len(), elfs[0].instructions.len());
+        assert!(listing
+            .windows(2)
+            .all(|pair| pair[1].0 == pair[0].0 + WORD_SIZE as u32));
+
+        // The runtime entry point starts by setting up the global pointer.
+        let (_, entry) = listing
+            .iter()
+            .find(|(address, _)| *address == elfs[0].entry)
+            .expect("entry point must be listed");
+        assert!(entry.starts_with("auipc gp"), "unexpected entry: {entry}");
+    }
+
+    #[test]
+    #[serial]
+    fn test_prove_within() {
+        let elfs = compile_multi(
+            "tests/integration-tests/empty",
+            &["-C opt-level=3"],
+            &HOME_PATH,
+        );
+
+        let prover = Stwo::<Local>::new(&elfs[0]).unwrap();
+        let result = prover.prove_within(Duration::ZERO);
+        assert!(matches!(
+            result,
+            Err(seq::Error::ProveAborted(ProveAborted {
+                phase: ProvePhase::Execution,
+                ..
+            }))
+        ));
+
+        let prover = Stwo::<Local>::new(&elfs[0]).unwrap();
+        let (view, proof) = prover.prove_within(Duration::from_secs(600)).unwrap();
+        proof.verify(&view).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_prove_within_with_input() {
+        let elfs = compile_multi(
+            "tests/integration-tests/fib",
+            &["-C opt-level=3"],
+            &HOME_PATH,
+        );
+
+        let prover = Stwo::<Local>::new(&elfs[0]).unwrap();
+        let (view, proof) = prover
+            .prove_within_with_input::<(), u32>(&(), &10, Duration::from_secs(600))
+            .unwrap();
+        proof
+            .verify_expected::<u32, u32>(
+                &10,
+                KnownExitCodes::ExitSuccess as u32,
+                &view.public_output::<u32>().unwrap(),
+                &elfs[0],
+                &[],
+            )
+            .unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_prove_from_steps() {
+        let elfs = compile_multi(
+            "tests/integration-tests/empty",
+            &["-C opt-level=3"],
+            &HOME_PATH,
+        );
+
+        let mut prover = Stwo::<Local>::new(&elfs[0]).unwrap().with_fixed_shape(true);
+        prover.limits.cycle_limit = Some(1 << 16);
+        let (_, buffered) = prover.prove().unwrap();
+
+        let (view, execution_trace) =
+            k_trace(elfs[0].clone(), &[], &[], &[], K).expect("error generating trace");
+        let mut prover = Stwo::<Local>::new(&elfs[0]).unwrap();
+        prover.limits.cycle_limit = Some(1 << 16);
+        let pulled = prover
+            .prove_from_steps(&view, program_steps(&execution_trace), None)
+            .unwrap();
+
+        assert_eq!(pulled.canonicalize(), buffered.canonicalize());
+        pulled.verify(&view).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_extract_public_input() {
+        let elfs = compile_multi(
+            "tests/integration-tests/fib",
+            &["-C opt-level=3"],
+            &HOME_PATH,
+        );
+
+        let prover = Stwo::<Local>::new(&elfs[0]).unwrap();
+        let (view, proof) = prover.prove_with_input::<(), u32>(&(), &10).unwrap();
+        let output = view.public_output::<u32>().unwrap();
+
+        // The verifier learns the input from the proof.
+        assert_eq!(proof.extract_public_input::<u32>().unwrap(), 10);
+        let public_input = proof
+            .verify_extracting_public_input::<u32, u32>(
+                KnownExitCodes::ExitSuccess as u32,
+                &output,
+                &elfs[0],
+                &[],
+            )
+            .unwrap();
+        assert_eq!(public_input, 10);
+    }
+
+    #[test]
+    #[serial]
+    fn test_canonicalize_proof() {
+        let elfs = compile_multi(
+            "tests/integration-tests/fib",
+            &["-C opt-level=3"],
+            &HOME_PATH,
+        );
+
+        let prover = Stwo::<Local>::new(&elfs[0]).unwrap();
+        let (_, proof) = prover.prove_with_input::<(), u32>(&(), &10).unwrap();
+
+        let bytes = postcard::to_allocvec(&proof).unwrap();
+        let roundtripped: seq::Proof = postcard::from_bytes(&bytes).unwrap();
+        assert_eq!(proof.canonicalize(), roundtripped.canonicalize());
+
+        // Proving the same execution again yields the same proof.
+        let prover = Stwo::<Local>::new(&elfs[0]).unwrap();
+        let (_, reproved) = prover.prove_with_input::<(), u32>(&(), &10).unwrap();
+        assert_eq!(proof.canonicalize(), reproved.canonicalize());
+    }
+
+    #[test]
+    #[serial]
+    fn test_proof_wire_format() {
+        let elfs = compile_multi(
+            "tests/integration-tests/fib",
+            &["-C opt-level=3"],
+            &HOME_PATH,
+        );
+
+        let prover = Stwo::<Local>::new(&elfs[0]).unwrap();
+        let (_, proof) = prover.prove_with_input::<(), u32>(&(), &10).unwrap();
+
+        for encoding in [ProofEncoding::Binary, ProofEncoding::Json] {
+            let bytes = proof.to_bytes_with(encoding);
+            let decoded = seq::Proof::from_bytes(&bytes).unwrap();
+            assert_eq!(decoded.canonicalize(), proof.canonicalize());
+        }
+
+        // Bump the format version.
+        let mut bytes = proof.to_bytes();
+        bytes[4] += 1;
+        assert!(matches!(
+            seq::Proof::from_bytes(&bytes),
+            Err(ProofEncodingError::UnsupportedVersion { found, supported })
+                if found == seq::PROOF_FORMAT_VERSION + 1 && supported == seq::PROOF_FORMAT_VERSION
+        ));
+
+        // Unversioned proofs, as serialized directly with serde, are rejected.
+        let unversioned = postcard::to_allocvec(&proof).unwrap();
+        assert!(matches!(
+            seq::Proof::from_bytes(&unversioned),
+            Err(ProofEncodingError::InvalidHeader)
+        ));
+    }
+
+    #[test]
+    #[serial]
+    fn test_proof_store() {
+        let elfs = compile_multi(
+            "tests/integration-tests/fib",
+            &["-C opt-level=3"],
+            &HOME_PATH,
+        );
+
+        let prover = Stwo::<Local>::new(&elfs[0]).unwrap();
+        let (_, proof) = prover.prove_with_input::<(), u32>(&(), &10).unwrap();
+
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let store = FsProofStore::new(tmp_dir.path());
+
+        let key = store.put(&proof).unwrap();
+        assert_eq!(key, proof.fingerprint());
+        let stored = store.get(&key).unwrap().expect("proof must be stored");
+        assert_eq!(stored.canonicalize(), proof.canonicalize());
+        assert!(store.get(&[0u8; 32]).unwrap().is_none());
+
+        // Flip a byte of the stored proof.
+        let path = store.path(&key);
+        let mut bytes = std::fs::read(&path).unwrap();
+        let middle = bytes.len() / 2;
+        bytes[middle] ^= 0x01;
+        std::fs::write(&path, &bytes).unwrap();
+
+        assert!(matches!(store.get(&key), Err(ProofStoreError::Corrupted)));
+    }
+
+    #[test]
+    #[serial]
+    fn test_compile_from_git() {
+        let home = std::env::current_dir().unwrap();
+
+        let tmp_dir = setup_guest_project(&PathBuf::from(HOME_PATH).join("runtime"));
+        let tmp_project_path = tmp_dir.path().join("integration");
+        write_guest_source_code(
+            &tmp_project_path,
+            &format!("{HOME_PATH}/tests/integration-tests/empty.rs"),
+        );
+
+        // Commit the guest to a local repository, so that the test doesn't need network access.
+        let git = |args: &[&str]| {
+            let output = std::process::Command::new("git")
+                .current_dir(&tmp_project_path)
+                .args(["-c", "user.name=nexus", "-c", "user.email=nexus@localhost"])
+                .args(args)
+                .output()
+                .expect("Failed to run git");
+            assert!(output.status.success());
+            String::from_utf8(output.stdout).unwrap()
+        };
+        git(&["init"]);
+        git(&["add", "-A"]);
+        git(&["commit", "-m", "guest"]);
+        let rev = git(&["rev-parse", "HEAD"]);
+
+        let url = format!("file://{}", tmp_project_path.display());
+        let mut compiler = Compiler::<CargoPackager>::from_git(&url, rev.trim(), "integration");
+        let prover = Stwo::<Local>::compile(&mut compiler).unwrap();
+
+        // The binary doesn't depend on where the guest was checked out.
+        std::env::set_current_dir(&tmp_project_path).unwrap();
+        let mut compiler = Compiler::<CargoPackager>::new("integration");
+        compiler.set_unique_build(true);
+        let local_prover = Stwo::<Local>::compile(&mut compiler);
+        std::env::set_current_dir(&home).unwrap();
+        assert_eq!(
+            seq::program_digest(&prover.elf).unwrap(),
+            seq::program_digest(&local_prover.unwrap().elf).unwrap()
+        );
+
+        let elf = prover.elf.clone();
+        let (view, proof) = prover.prove().unwrap();
+        assert_eq!(
+            view.exit_code().unwrap(),
+            KnownExitCodes::ExitSuccess as u32
+        );
+        proof
+            .verify_expected::<(), ()>(&(), KnownExitCodes::ExitSuccess as u32, &(), &elf, &[])
+            .unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_lint() {
+        let elfs = compile_multi(
+            "tests/integration-tests/empty",
+            &["-C opt-level=3"],
+            &HOME_PATH,
+        );
+
+        let mut prover = Stwo::<Local>::new(&elfs[0]).unwrap().with_fixed_shape(true);
+        prover.limits.cycle_limit = Some(1 << 16);
+        let lints = prover.lint().unwrap();
+
+        let padding = lints
+            .iter()
+            .find(|lint| matches!(lint, ProvingLint::ExcessivePadding { .. }))
+            .expect("short guest should trigger ExcessivePadding");
+        assert_eq!(padding.severity(), Severity::Warning);
+        assert!(padding.to_string().contains("padding"));
+    }
 }
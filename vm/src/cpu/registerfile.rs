@@ -15,6 +15,11 @@ impl RegisterFile {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Returns the values of all registers, indexed by register number.
+    pub fn to_array(&self) -> [u32; 32] {
+        self.registers
+    }
 }
 
 impl Registers for RegisterFile {
@@ -87,6 +87,10 @@ pub enum ParserError {
     #[error("invalid entry point offset")]
     InvalidEntryPointOffset,
 
+    /// The entry point does not lie within the executable code
+    #[error("entry point 0x{0:08x} is outside of the executable code")]
+    EntryPointOutsideCode(u32),
+
     /// An error occurred while parsing the ELF headers
     #[error(transparent)]
     ELFError(#[from] elf::ParseError),
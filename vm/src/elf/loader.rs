@@ -22,6 +22,7 @@
 //!
 //! - `ElfFile::from_bytes`: Allows creation of `ElfFile` from raw bytes
 //! - `ElfFile::from_path`: Allows creation of `ElfFile` from a file path
+//! - `ElfFile::from_reader`: Allows creation of `ElfFile` from a seekable reader, skipping debug sections
 //!
 //! # Usage
 //!
@@ -43,15 +44,29 @@
 //! This loader is designed for little-endian RISC-V 32-bit executables and implements
 //! a Harvard architecture model. Ensure your ELF files are compatible with these specifications.
 
-use crate::{elf::parser, error::VMError, memory::MemorySegmentImage};
+use crate::{
+    elf::parser, error::VMError, memory::MemorySegmentImage, riscv::decode_instruction, WORD_SIZE,
+};
 
 use elf::{endian::LittleEndian, ElfBytes};
 use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
 use std::path::Path;
 
 use super::{error::ParserError, parser::ParsedElfData};
 use serde::{Deserialize, Serialize};
 
+/// A function symbol of the ELF symbol table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionSymbol {
+    /// The (possibly mangled) name of the function.
+    pub name: String,
+    /// The address of the first instruction of the function.
+    pub address: u32,
+    /// The size of the function in bytes, or zero if unknown.
+    pub size: u32,
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 pub struct ElfFile {
     /// The instructions of the program encoded as 32-bits.
@@ -71,6 +86,13 @@ pub struct ElfFile {
 
     /// Nexus-specific metadata embedded in the ELF file.
     pub nexus_metadata: Vec<u32>,
+
+    /// The function symbols, sorted by address, for attributing execution to functions (see
+    /// [`View::profile`](crate::emulator::View::profile)).
+    ///
+    /// Symbols are build metadata rather than part of the program, so they are not serialized.
+    #[serde(skip)]
+    pub symbols: Vec<FunctionSymbol>,
 }
 
 impl ElfFile {
@@ -89,6 +111,7 @@ impl ElfFile {
             rom_image,
             ram_image,
             nexus_metadata,
+            symbols: Vec::new(),
         }
     }
 
@@ -96,6 +119,21 @@ impl ElfFile {
         &self.instructions[address..address + n]
     }
 
+    /// The static disassembly of the code segment, as the address and decoded mnemonic of every instruction.
+    ///
+    /// Unlike a trace of the execution, this includes instructions that are never executed. Words that don't decode
+    /// to a supported instruction are listed as `unimpl`.
+    pub fn disassemble(&self) -> Vec<(u32, String)> {
+        self.instructions
+            .iter()
+            .enumerate()
+            .map(|(i, &raw)| {
+                let address = self.base + (i * WORD_SIZE) as u32;
+                (address, decode_instruction(raw).to_string())
+            })
+            .collect()
+    }
+
     pub fn from_bytes(data: &[u8]) -> Result<Self, VMError> {
         let elf =
             ElfBytes::<LittleEndian>::minimal_parse(data).map_err(Into::<ParserError>::into)?;
@@ -110,13 +148,20 @@ impl ElfFile {
 
         let parsed_elf_data: ParsedElfData = parser::parse_segments(&elf, data)?;
 
+        let base = parsed_elf_data.base_address as u32;
+        let code = base..base + (parsed_elf_data.instructions.len() * WORD_SIZE) as u32;
+        if !code.contains(&entry) {
+            return Err(ParserError::EntryPointOutsideCode(entry).into());
+        }
+
         Ok(ElfFile {
             instructions: parsed_elf_data.instructions,
             entry,
-            base: parsed_elf_data.base_address as u32,
+            base,
             rom_image: parsed_elf_data.readonly_memory,
             ram_image: parsed_elf_data.writable_memory,
             nexus_metadata: parsed_elf_data.nexus_metadata,
+            symbols: parser::parse_function_symbols(&elf),
         })
     }
 
@@ -128,13 +173,103 @@ impl ElfFile {
             .collect();
         Self::from_bytes(data.as_slice())
     }
+
+    /// Load an ELF file from a seekable reader.
+    ///
+    /// Only the headers, the segments, and the non-debug sections are read; debug sections, which
+    /// make up most of an unstripped binary, are skipped. The result is identical to
+    /// [`ElfFile::from_bytes`] on the full contents.
+    pub fn from_reader<R: Read + Seek>(mut reader: R) -> Result<Self, VMError> {
+        let len = reader
+            .seek(SeekFrom::End(0))
+            .map_err(Into::<ParserError>::into)? as usize;
+
+        // Skipped ranges are left zeroed, and are never touched by the parser.
+        let mut data = vec![0u8; len];
+        let mut read_range = |data: &mut [u8], offset: usize, size: usize| {
+            let range = offset
+                .checked_add(size)
+                .filter(|&end| end <= len)
+                .map(|end| offset..end)
+                .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::UnexpectedEof))?;
+            reader.seek(SeekFrom::Start(offset as u64))?;
+            reader.read_exact(&mut data[range])
+        };
+        let field = |data: &[u8], offset: usize, size: usize| {
+            data[offset..offset + size]
+                .iter()
+                .rev()
+                .fold(0usize, |acc, &b| (acc << 8) | b as usize)
+        };
+
+        // ELF32 header, program header table, and section header table. Anything else is read in
+        // full, so that the usual parser reports what is wrong with it.
+        read_range(&mut data, 0, ELF32_HEADER_SIZE.min(len)).map_err(Into::<ParserError>::into)?;
+        if len < ELF32_HEADER_SIZE || data[4] != ELFCLASS32 {
+            read_range(&mut data, 0, len).map_err(Into::<ParserError>::into)?;
+            return Self::from_bytes(&data);
+        }
+        let (phoff, phentsize, phnum) = (
+            field(&data, 0x1C, 4),
+            field(&data, 0x2A, 2),
+            field(&data, 0x2C, 2),
+        );
+        let (shoff, shentsize, shnum) = (
+            field(&data, 0x20, 4),
+            field(&data, 0x2E, 2),
+            field(&data, 0x30, 2),
+        );
+        let shstrndx = field(&data, 0x32, 2);
+        read_range(&mut data, phoff, phentsize * phnum).map_err(Into::<ParserError>::into)?;
+        read_range(&mut data, shoff, shentsize * shnum).map_err(Into::<ParserError>::into)?;
+
+        for i in 0..phnum {
+            let phdr = phoff + i * phentsize;
+            let (offset, filesz) = (field(&data, phdr + 4, 4), field(&data, phdr + 16, 4));
+            read_range(&mut data, offset, filesz).map_err(Into::<ParserError>::into)?;
+        }
+
+        // Section names are needed to tell the debug sections apart.
+        let section = |data: &[u8], i: usize| {
+            let shdr = shoff + i * shentsize;
+            (
+                field(data, shdr, 4),
+                field(data, shdr + 4, 4),
+                field(data, shdr + 16, 4),
+                field(data, shdr + 20, 4),
+            )
+        };
+        if shstrndx >= shnum {
+            read_range(&mut data, 0, len).map_err(Into::<ParserError>::into)?;
+            return Self::from_bytes(&data);
+        }
+        let (_, _, strtab_offset, strtab_size) = section(&data, shstrndx);
+        read_range(&mut data, strtab_offset, strtab_size).map_err(Into::<ParserError>::into)?;
+
+        for i in 0..shnum {
+            let (name, sh_type, offset, size) = section(&data, i);
+            let name = data[strtab_offset..strtab_offset + strtab_size]
+                .get(name..)
+                .unwrap_or_default();
+            if sh_type == SHT_NOBITS || name.starts_with(b".debug") {
+                continue;
+            }
+            read_range(&mut data, offset, size).map_err(Into::<ParserError>::into)?;
+        }
+
+        Self::from_bytes(&data)
+    }
 }
 
+const ELF32_HEADER_SIZE: usize = 52;
+const ELFCLASS32: u8 = 1;
+const SHT_NOBITS: usize = 8;
+
 #[cfg(test)]
 mod tests {
     use nexus_common::constants::ELF_TEXT_START;
 
-    use crate::{memory::MemorySegmentImage, read_testing_elf_from_path};
+    use crate::{error::VMErrorKind, memory::MemorySegmentImage, read_testing_elf_from_path};
 
     use super::*;
     use std::io::Write;
@@ -195,4 +330,37 @@ mod tests {
 
         assert_eq!(elf.instructions.len(), NUMBER_OF_INSTRUCTIONS);
     }
+
+    #[test]
+    fn test_reject_entry_point_outside_code() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/test/fib_10.elf");
+        let mut data = std::fs::read(path).unwrap();
+        // e_entry of the ELF32 header
+        data[0x18..0x1C].copy_from_slice(&0u32.to_le_bytes());
+
+        assert!(matches!(
+            ElfFile::from_bytes(&data).unwrap_err().source,
+            VMErrorKind::ElfError(ParserError::EntryPointOutsideCode(0))
+        ));
+    }
+
+    #[test]
+    fn test_parse_elf_from_reader() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/test/fib_10.elf");
+        let elf = ElfFile::from_path(path).unwrap();
+        let streamed = ElfFile::from_reader(File::open(path).unwrap()).unwrap();
+
+        assert_eq!(streamed.instructions, elf.instructions);
+        assert_eq!(streamed.entry, elf.entry);
+        assert_eq!(streamed.base, elf.base);
+        assert_eq!(
+            streamed.rom_image.as_byte_slice(),
+            elf.rom_image.as_byte_slice()
+        );
+        assert_eq!(
+            streamed.ram_image.as_byte_slice(),
+            elf.ram_image.as_byte_slice()
+        );
+        assert_eq!(streamed.nexus_metadata, elf.nexus_metadata);
+    }
 }
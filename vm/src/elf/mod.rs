@@ -3,5 +3,5 @@ mod loader;
 mod parser;
 
 pub use error::ParserError as ElfError;
-pub use loader::ElfFile;
+pub use loader::{ElfFile, FunctionSymbol};
 pub use nexus_common::constants::WORD_SIZE;
@@ -50,6 +50,7 @@ use tracing::debug;
 use crate::{error::Result, memory::MemorySegmentImage};
 
 use super::error::ParserError;
+use super::loader::FunctionSymbol;
 
 type Instructions = Vec<u32>;
 type Metadata = Vec<u32>;
@@ -323,6 +324,31 @@ impl Ord for PrecompileDescription<'_> {
     }
 }
 
+/// Parses the function symbols of the ELF file, sorted by address.
+///
+/// Symbols are only used for diagnostics, so a stripped ELF or a malformed symbol table yields no symbols rather than
+/// an error.
+pub fn parse_function_symbols(elf: &ElfBytes<LittleEndian>) -> Vec<FunctionSymbol> {
+    let Ok(Some((symbol_table, symbol_string_table))) = elf.symbol_table() else {
+        return Vec::new();
+    };
+
+    let mut symbols: Vec<FunctionSymbol> = symbol_table
+        .iter()
+        .filter(|symbol| symbol.st_symtype() == abi::STT_FUNC && symbol.st_value != 0)
+        .filter_map(|symbol| {
+            let name = symbol_string_table.get(symbol.st_name as usize).ok()?;
+            Some(FunctionSymbol {
+                name: name.to_string(),
+                address: symbol.st_value.try_into().ok()?,
+                size: symbol.st_size.try_into().ok()?,
+            })
+        })
+        .collect();
+    symbols.sort_by_key(|symbol| symbol.address);
+    symbols
+}
+
 /// Parses the precompile metadata from the ELF file. This function finds all symbols that indicate
 /// pieces of precompile metadata and then ensures that there is a complete contiguous set of unique
 /// precompiles labeled 0 though N-1 via heapification.
@@ -0,0 +1,134 @@
+//! Handling of basic bit-manipulation (Zbb) instructions.
+//!
+//! No chip constrains Zbb instructions, and guests are built for the `riscv32im` target which never emits them, so
+//! they decode as unimplemented instructions. When [`Executor::execute_zbb`](super::Executor::execute_zbb) is set,
+//! the first-pass (Harvard) emulator executes them instead, so that binaries built with Zbb enabled can be run, though
+//! not proven.
+
+use nexus_common::cpu::Registers;
+
+use crate::{cpu::Cpu, riscv::Register};
+
+const OP: u32 = 0b0110011;
+const OP_IMM: u32 = 0b0010011;
+
+/// Decodes the raw instruction word as a Zbb instruction, returning the operation it computes on `rs1` and `rs2` (or
+/// the shift amount of `rori`).
+fn decode(raw: u32) -> Option<fn(u32, u32) -> u32> {
+    let funct3 = (raw >> 12) & 0x7;
+    let rs2 = (raw >> 20) & 0x1f;
+    let funct7 = raw >> 25;
+    let imm = raw >> 20;
+
+    let operation: fn(u32, u32) -> u32 = match (raw & 0x7f, funct7, funct3) {
+        (OP, 0b0100000, 0b111) => |a, b| a & !b,   // andn
+        (OP, 0b0100000, 0b110) => |a, b| a | !b,   // orn
+        (OP, 0b0100000, 0b100) => |a, b| !(a ^ b), // xnor
+        (OP, 0b0000101, 0b100) => |a, b| (a as i32).min(b as i32) as u32, // min
+        (OP, 0b0000101, 0b101) => |a, b| a.min(b), // minu
+        (OP, 0b0000101, 0b110) => |a, b| (a as i32).max(b as i32) as u32, // max
+        (OP, 0b0000101, 0b111) => |a, b| a.max(b), // maxu
+        (OP, 0b0110000, 0b001) => |a, b| a.rotate_left(b & 0x1f), // rol
+        (OP, 0b0110000, 0b101) => |a, b| a.rotate_right(b & 0x1f), // ror
+        (OP, 0b0000100, 0b100) if rs2 == 0 => |a, _| a & 0xffff, // zext.h
+        (OP_IMM, 0b0110000, 0b101) => |a, shamt| a.rotate_right(shamt), // rori
+        (OP_IMM, _, 0b001) => match imm {
+            0x600 => |a, _| a.leading_zeros(),      // clz
+            0x601 => |a, _| a.trailing_zeros(),     // ctz
+            0x602 => |a, _| a.count_ones(),         // cpop
+            0x604 => |a, _| a as i8 as i32 as u32,  // sext.b
+            0x605 => |a, _| a as i16 as i32 as u32, // sext.h
+            _ => return None,
+        },
+        (OP_IMM, _, 0b101) => match imm {
+            0x287 => |a, _| {
+                // orc.b
+                u32::from_le_bytes(a.to_le_bytes().map(|byte| if byte == 0 { 0 } else { 0xff }))
+            },
+            0x698 => |a, _| a.swap_bytes(), // rev8
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some(operation)
+}
+
+/// Returns whether the raw instruction word is a Zbb instruction.
+pub(crate) fn is_zbb_instruction(raw: u32) -> bool {
+    decode(raw).is_some()
+}
+
+/// Execute the Zbb instruction `raw`, returning the value written to `rd`.
+pub(crate) fn execute_zbb(raw: u32, cpu: &mut Cpu) -> Option<u32> {
+    let operation = decode(raw)?;
+    let rd = (raw >> 7) & 0x1f;
+    let rs1 = (raw >> 15) & 0x1f;
+    let rs2 = (raw >> 20) & 0x1f;
+
+    let a = cpu.registers.read(Register::from(rs1 as u8));
+    let b = if raw & 0x7f == OP {
+        cpu.registers.read(Register::from(rs2 as u8))
+    } else {
+        // The shift amount of rori, unused by other immediate instructions.
+        rs2
+    };
+    let result = operation(a, b);
+    cpu.registers.write(Register::from(rd as u8), result);
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Encode an R-type instruction.
+    fn r_type(funct7: u32, rs2: u32, rs1: u32, funct3: u32, rd: u32, opcode: u32) -> u32 {
+        (funct7 << 25) | (rs2 << 20) | (rs1 << 15) | (funct3 << 12) | (rd << 7) | opcode
+    }
+
+    #[test]
+    fn test_zbb() {
+        let mut cpu = Cpu::default();
+        cpu.registers.write(Register::X5, 0x0000_f0f0);
+        cpu.registers.write(Register::X6, (-3i32) as u32);
+
+        let cases = [
+            // andn, orn, xnor
+            (
+                r_type(0b0100000, 6, 5, 0b111, 7, OP),
+                0x0000_f0f0 & !(-3i32 as u32),
+            ),
+            (r_type(0b0100000, 6, 5, 0b110, 7, OP), 0x0000_f0f0 | 2),
+            (r_type(0b0100000, 5, 5, 0b100, 7, OP), u32::MAX),
+            // min, minu, max, maxu
+            (r_type(0b0000101, 6, 5, 0b100, 7, OP), -3i32 as u32),
+            (r_type(0b0000101, 6, 5, 0b101, 7, OP), 0x0000_f0f0),
+            (r_type(0b0000101, 6, 5, 0b110, 7, OP), 0x0000_f0f0),
+            (r_type(0b0000101, 6, 5, 0b111, 7, OP), -3i32 as u32),
+            // rol by 29 (the low five bits of -3), rori by 4
+            (
+                r_type(0b0110000, 6, 5, 0b001, 7, OP),
+                0x0000_f0f0u32.rotate_left(29),
+            ),
+            (r_type(0b0110000, 4, 5, 0b101, 7, OP_IMM), 0x0000_0f0f),
+            // clz, ctz, cpop
+            (r_type(0b0110000, 0, 5, 0b001, 7, OP_IMM), 16),
+            (r_type(0b0110000, 1, 5, 0b001, 7, OP_IMM), 4),
+            (r_type(0b0110000, 2, 5, 0b001, 7, OP_IMM), 8),
+            // sext.b, zext.h
+            (r_type(0b0110000, 4, 5, 0b001, 7, OP_IMM), 0xffff_fff0),
+            (r_type(0b0000100, 0, 6, 0b100, 7, OP), 0x0000_fffd),
+            // orc.b, rev8
+            (r_type(0b0010100, 7, 5, 0b101, 7, OP_IMM), 0x0000_ffff),
+            (r_type(0b0110100, 24, 5, 0b101, 7, OP_IMM), 0xf0f0_0000),
+        ];
+        for (raw, expected) in cases {
+            assert!(is_zbb_instruction(raw), "{raw:#010x}");
+            assert_eq!(execute_zbb(raw, &mut cpu), Some(expected), "{raw:#010x}");
+            assert_eq!(cpu.registers.read(Register::X7), expected);
+        }
+
+        // Base instructions (add) are not Zbb.
+        assert!(!is_zbb_instruction(r_type(0, 6, 5, 0b000, 7, OP)));
+    }
+}
@@ -0,0 +1,327 @@
+//! A GDB remote debugging server for guest programs.
+//!
+//! The server runs the first-pass (Harvard) emulator under the control of a debugger speaking the GDB remote serial
+//! protocol, such as `gdb` or `lldb` attached with the RV32 target, supporting software breakpoints, interruption,
+//! and reading and writing registers and memory. Since GDB single-steps RISC-V targets by placing temporary
+//! breakpoints, `stepi` and `nexti` work on top of breakpoints as well.
+//!
+//! Debugging has no bearing on the trace or the proof: the debugged execution is not traced.
+
+use std::{
+    collections::HashSet,
+    marker::PhantomData,
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+};
+
+use gdbstub::{
+    common::Signal,
+    conn::{Connection, ConnectionExt},
+    stub::{run_blocking, GdbStub, SingleThreadStopReason},
+    target::{
+        ext::{
+            base::{
+                singlethread::{SingleThreadBase, SingleThreadResume, SingleThreadResumeOps},
+                BaseOps,
+            },
+            breakpoints::{Breakpoints, BreakpointsOps, SwBreakpoint, SwBreakpointOps},
+        },
+        Target, TargetError, TargetResult,
+    },
+};
+use gdbstub_arch::riscv::{reg::RiscvCoreRegs, Riscv32};
+use nexus_common::{constants::WORD_SIZE, cpu::Registers, memory::MemAccessSize};
+
+use super::{Emulator, ExecutionLimits, HarvardEmulator};
+use crate::{
+    elf::ElfFile,
+    error::{Result, VMError, VMErrorKind},
+    memory::{LoadOp, MemoryProcessor},
+    riscv::Register,
+    trace::ExecutionHooks,
+};
+
+/// The number of instructions executed between checks for an interruption by the debugger.
+const INTERRUPT_CHECK_INTERVAL: usize = 1024;
+
+struct DebugTarget<'a> {
+    emulator: &'a mut HarvardEmulator,
+    breakpoints: HashSet<u32>,
+    exit_code: Option<u32>,
+    error: Option<VMError>,
+}
+
+impl<'a> DebugTarget<'a> {
+    fn new(emulator: &'a mut HarvardEmulator) -> Self {
+        Self {
+            emulator,
+            breakpoints: HashSet::new(),
+            exit_code: None,
+            error: None,
+        }
+    }
+
+    fn pc(&self) -> u32 {
+        self.emulator.executor.cpu.pc.value
+    }
+
+    /// Execute the instruction at the current PC, returning why the guest stopped, if it did.
+    fn step(&mut self) -> Option<SingleThreadStopReason<u32>> {
+        match self.execute_instruction() {
+            Ok(()) => None,
+            Err(VMError {
+                source: VMErrorKind::VMExited(exit_code),
+                ..
+            }) => {
+                self.exit_code = Some(exit_code);
+                Some(SingleThreadStopReason::Exited(exit_code as u8))
+            }
+            Err(e) => {
+                self.error = Some(e);
+                Some(SingleThreadStopReason::Terminated(Signal::SIGABRT))
+            }
+        }
+    }
+
+    fn execute_instruction(&mut self) -> Result<()> {
+        let pc = self.pc();
+        let basic_block_entry = self.emulator.fetch_block(pc)?;
+        let at = (pc - basic_block_entry.start) as usize / WORD_SIZE;
+        self.emulator
+            .execute_instruction(&basic_block_entry.block.0[at], false)
+            .map_err(|e| self.emulator.diagnose_unsupported_instruction(e))?;
+        Ok(())
+    }
+
+    /// Run the guest until it hits a breakpoint or stops, or until `interrupted` returns true, which is checked
+    /// every [`INTERRUPT_CHECK_INTERVAL`] instructions.
+    fn run(
+        &mut self,
+        mut interrupted: impl FnMut() -> bool,
+    ) -> Option<SingleThreadStopReason<u32>> {
+        let mut executed = 0usize;
+        loop {
+            if executed % INTERRUPT_CHECK_INTERVAL == 0 && interrupted() {
+                return None;
+            }
+            if let Some(reason) = self.step() {
+                return Some(reason);
+            }
+            if self.breakpoints.contains(&self.pc()) {
+                return Some(SingleThreadStopReason::SwBreak(()));
+            }
+            executed += 1;
+        }
+    }
+
+    fn read_byte(&self, address: u32) -> Option<u8> {
+        if let Ok(LoadOp::Op(_, _, value)) =
+            self.emulator.data_memory.read(address, MemAccessSize::Byte)
+        {
+            return Some(value as u8);
+        }
+        let word = self
+            .emulator
+            .fetch_instruction_word(address - address % WORD_SIZE as u32)?;
+        Some(word.to_le_bytes()[address as usize % WORD_SIZE])
+    }
+}
+
+impl Target for DebugTarget<'_> {
+    type Arch = Riscv32;
+    type Error = VMError;
+
+    fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
+        BaseOps::SingleThread(self)
+    }
+
+    fn support_breakpoints(&mut self) -> Option<BreakpointsOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl SingleThreadBase for DebugTarget<'_> {
+    fn read_registers(&mut self, regs: &mut RiscvCoreRegs<u32>) -> TargetResult<(), Self> {
+        let registers = &self.emulator.executor.cpu.registers;
+        for (i, reg) in regs.x.iter_mut().enumerate() {
+            *reg = registers.read(Register::from(i as u8));
+        }
+        regs.pc = self.pc();
+        Ok(())
+    }
+
+    fn write_registers(&mut self, regs: &RiscvCoreRegs<u32>) -> TargetResult<(), Self> {
+        let registers = &mut self.emulator.executor.cpu.registers;
+        // x0 is hardwired to zero.
+        for (i, reg) in regs.x.iter().enumerate().skip(1) {
+            registers.write(Register::from(i as u8), *reg);
+        }
+        self.emulator.executor.cpu.pc.value = regs.pc;
+        Ok(())
+    }
+
+    fn read_addrs(&mut self, start_addr: u32, data: &mut [u8]) -> TargetResult<usize, Self> {
+        for (i, byte) in data.iter_mut().enumerate() {
+            match self.read_byte(start_addr.wrapping_add(i as u32)) {
+                Some(value) => *byte = value,
+                None => return Ok(i),
+            }
+        }
+        Ok(data.len())
+    }
+
+    fn write_addrs(&mut self, start_addr: u32, data: &[u8]) -> TargetResult<(), Self> {
+        self.emulator
+            .data_memory
+            .write_bytes(start_addr, data)
+            .map_err(|_| TargetError::NonFatal)
+    }
+
+    fn support_resume(&mut self) -> Option<SingleThreadResumeOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl SingleThreadResume for DebugTarget<'_> {
+    // The guest runs once the event loop waits for it to stop.
+    fn resume(&mut self, _signal: Option<Signal>) -> std::result::Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl Breakpoints for DebugTarget<'_> {
+    fn support_sw_breakpoint(&mut self) -> Option<SwBreakpointOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl SwBreakpoint for DebugTarget<'_> {
+    fn add_sw_breakpoint(&mut self, addr: u32, _kind: usize) -> TargetResult<bool, Self> {
+        Ok(self.breakpoints.insert(addr))
+    }
+
+    fn remove_sw_breakpoint(&mut self, addr: u32, _kind: usize) -> TargetResult<bool, Self> {
+        Ok(self.breakpoints.remove(&addr))
+    }
+}
+
+struct EventLoop<'a>(PhantomData<&'a mut HarvardEmulator>);
+
+impl<'a> run_blocking::BlockingEventLoop for EventLoop<'a> {
+    type Target = DebugTarget<'a>;
+    type Connection = TcpStream;
+    type StopReason = SingleThreadStopReason<u32>;
+
+    fn wait_for_stop_reason(
+        target: &mut Self::Target,
+        conn: &mut Self::Connection,
+    ) -> std::result::Result<
+        run_blocking::Event<Self::StopReason>,
+        run_blocking::WaitForStopReasonError<
+            <Self::Target as Target>::Error,
+            <Self::Connection as Connection>::Error,
+        >,
+    > {
+        // Stop running once the debugger sends anything, such as an interrupt.
+        let reason = target.run(|| conn.peek().map(|byte| byte.is_some()).unwrap_or(true));
+
+        match reason {
+            Some(reason) => Ok(run_blocking::Event::TargetStopped(reason)),
+            None => {
+                let byte = conn
+                    .read()
+                    .map_err(run_blocking::WaitForStopReasonError::Connection)?;
+                Ok(run_blocking::Event::IncomingData(byte))
+            }
+        }
+    }
+
+    fn on_interrupt(
+        _target: &mut Self::Target,
+    ) -> std::result::Result<Option<Self::StopReason>, <Self::Target as Target>::Error> {
+        Ok(Some(SingleThreadStopReason::Signal(Signal::SIGINT)))
+    }
+}
+
+impl HarvardEmulator {
+    /// Serve a debugger connected through `conn`, until it detaches or kills the guest.
+    ///
+    /// Returns the exit code of the guest if it exited while being debugged, or the error it ran into, which is
+    /// reported to the debugger as an abort.
+    pub fn debug(&mut self, conn: TcpStream) -> Result<Option<u32>> {
+        let mut target = DebugTarget::new(self);
+        GdbStub::new(conn)
+            .run_blocking::<EventLoop<'_>>(&mut target)
+            .map_err(|e| VMErrorKind::DebuggerError(format!("{e:?}")))?;
+
+        match target.error {
+            Some(error) => Err(error),
+            None => Ok(target.exit_code),
+        }
+    }
+}
+
+/// Run the program under a debugger connecting to `addr`, with the inputs, hooks and limits of the first pass of
+/// [`k_trace_with_hooks`](crate::trace::k_trace_with_hooks), see [`HarvardEmulator::debug`].
+///
+/// Blocks until a debugger connects. Guest output is printed rather than captured.
+pub fn debug_with_hooks(
+    elf: &ElfFile,
+    public_input: &[u8],
+    private_input: &[u8],
+    hooks: ExecutionHooks,
+    limits: &ExecutionLimits,
+    addr: impl ToSocketAddrs,
+) -> Result<Option<u32>> {
+    let mut harvard = HarvardEmulator::from_elf(elf, public_input, private_input);
+    harvard.set_limits(limits.clone());
+    if let Some(provider) = hooks.input_provider {
+        harvard.set_input_provider(provider);
+    }
+    harvard.get_executor_mut().host_functions = hooks.host_functions;
+
+    let debugger_error = |e: std::io::Error| VMErrorKind::DebuggerError(e.to_string());
+    let (conn, _) = TcpListener::bind(addr)
+        .and_then(|listener| listener.accept())
+        .map_err(debugger_error)?;
+    harvard.debug(conn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::riscv::{BasicBlock, BuiltinOpcode, Instruction, Opcode};
+    use nexus_common::constants::ELF_TEXT_START;
+
+    #[test]
+    fn test_run_to_breakpoint() {
+        let basic_blocks = vec![BasicBlock::new(vec![
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 1, 0, 1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 2, 1, 1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 3, 2, 1),
+            // exit(0)
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 17, 0, 0x201),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ECALL), 0, 0, 0),
+        ])];
+        let mut emulator = HarvardEmulator::from_basic_blocks(&basic_blocks);
+        let mut target = DebugTarget::new(&mut emulator);
+        target.breakpoints.insert(ELF_TEXT_START + 8);
+
+        assert_eq!(
+            target.run(|| false),
+            Some(SingleThreadStopReason::SwBreak(()))
+        );
+        assert_eq!(target.pc(), ELF_TEXT_START + 8);
+
+        let mut regs = RiscvCoreRegs::default();
+        target.read_registers(&mut regs).unwrap();
+        assert_eq!(regs.x[2], 2);
+        assert_eq!(regs.x[3], 0);
+        assert_eq!(regs.pc, ELF_TEXT_START + 8);
+
+        assert_eq!(
+            target.run(|| false),
+            Some(SingleThreadStopReason::Exited(0))
+        );
+        assert_eq!(target.exit_code, Some(0));
+    }
+}
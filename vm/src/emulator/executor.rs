@@ -132,18 +132,27 @@
 //! basic block caching, custom instruction support, debug logging, and associated data handling.
 
 use super::{
-    layout::LinearMemoryLayout, memory_stats::*, registry::InstructionExecutorRegistry, *,
+    bitmanip::{execute_zbb, is_zbb_instruction},
+    float::{is_float_instruction, FloatRegisters},
+    input::{InputProvider, LazyInput},
+    layout::LinearMemoryLayout,
+    memory_stats::*,
+    registry::InstructionExecutorRegistry,
+    *,
 };
 use crate::{
     cpu::{instructions::InstructionResult, Cpu},
-    elf::ElfFile,
-    error::{Result, VMErrorKind},
+    elf::{ElfFile, FunctionSymbol},
+    error::{Result, VMError, VMErrorKind},
     memory::{
-        FixedMemory, LoadOp, MemoryProcessor, MemoryRecords, MemorySegmentImage, Modes, StoreOp,
-        UnifiedMemory, VariableMemory, NA, RO, RW, WO,
+        FixedMemory, LoadOp, LoadOps, MemoryProcessor, MemoryRecord, MemoryRecords,
+        MemorySegmentImage, Modes, StoreOp, StoreOps, UnifiedMemory, VariableMemory, NA, RO, RW,
+        WO,
     },
-    riscv::{decode_until_end_of_a_block, BasicBlock, Instruction, Opcode, Register},
-    system::SyscallInstruction,
+    riscv::{
+        decode_until_end_of_a_block, BasicBlock, BuiltinOpcode, Instruction, Opcode, Register,
+    },
+    system::{HostCall, HostFunctions, SyscallInstruction, HOST_CALL_CODES},
 };
 
 use nexus_common::{
@@ -171,6 +180,18 @@ pub struct Executor {
     // The private input tape as a FIFO queue.
     pub private_input_tape: VecDeque<u8>,
 
+    // The provider extending the private input tape on demand, if any
+    pub(crate) lazy_private_input: Option<LazyInput>,
+
+    // The host functions answering calls made by the guest
+    pub host_functions: HostFunctions,
+
+    // The calls made by the guest to host functions, in order
+    pub host_calls: Vec<HostCall>,
+
+    // The recorded host calls replayed by the second pass, in order
+    pub(crate) host_call_replay: VecDeque<HostCall>,
+
     // The global clock counter
     pub global_clock: usize,
 
@@ -192,11 +213,67 @@ pub struct Executor {
     // Debug logs written by the guest program
     pub logs: Option<Vec<Vec<u8>>>,
 
+    // Structured log records written by the guest program, captured along with the debug logs
+    pub log_records: Vec<LogRecord>,
+
     // A map of memory addresses to the last timestamp when they were accessed
     pub access_timestamps: HashMap<u32, usize>,
+
+    // Resource limits enforced during execution
+    pub limits: ExecutionLimits,
+
+    // The number of retired instructions in each class
+    pub instruction_mix: HashMap<InstrClass, u64>,
+
+    // The number of times each instruction of the program was executed, by its index from the base address
+    pub instruction_counts: Vec<u64>,
+
+    // The function symbols of the program, relocated to the base address
+    pub symbols: Vec<FunctionSymbol>,
+
+    // Statistics of the guest's heap allocations
+    pub alloc_stats: AllocStats,
+
+    // Report overflow-check panics as `ArithmeticOverflow` rather than a generic panic exit
+    pub report_arithmetic_overflow: bool,
+
+    // The overflow panic message written by the guest, if any
+    pub(crate) overflow_panic: Option<String>,
+
+    // Whether floating-point instructions are rejected or executed in software
+    pub float_policy: FloatPolicy,
+
+    // The floating-point registers, only used under `FloatPolicy::SoftFloat`
+    pub(crate) float_registers: FloatRegisters,
+
+    // Execute basic bit-manipulation (Zbb) instructions, which cannot be proven, rather than rejecting them. Only
+    // read by the first-pass (Harvard) emulator: the second pass rejects them, so such executions are never proven
+    pub execute_zbb: bool,
+
+    // Trap with `UninitializedRead` on loads from memory never written nor statically initialized, rather than
+    // reading zero
+    pub trap_uninitialized_reads: bool,
+
+    // The byte addresses of data memory written by the guest, only tracked when trapping on uninitialized reads
+    pub(crate) written_memory: HashSet<u32>,
 }
 
 impl Executor {
+    /// The bytes appended to the private input tape by the input provider so far, if any.
+    pub fn private_input_provided(&self) -> &[u8] {
+        self.lazy_private_input
+            .as_ref()
+            .map_or(&[], |input| input.provided.as_slice())
+    }
+
+    /// Counts an execution of the instruction at `pc`, see [`View::profile`].
+    fn count_instruction(&mut self, pc: u32) {
+        let index = (pc.wrapping_sub(self.base_address) / WORD_SIZE as u32) as usize;
+        if let Some(count) = self.instruction_counts.get_mut(index) {
+            *count += 1;
+        }
+    }
+
     /// Adds a new opcode and its corresponding execution function to the emulator.
     fn add_opcode<IE: InstructionExecutor>(&mut self, op: &Opcode) -> Result<()> {
         self.instruction_executor.add_opcode::<IE>(op)
@@ -207,6 +284,16 @@ impl Executor {
         self.private_input_tape = VecDeque::<u8>::from(private_input.to_vec());
     }
 
+    /// Set the provider extending the private input tape whenever the guest reads past its end.
+    fn set_input_provider(&mut self, provider: Box<dyn InputProvider>) {
+        self.lazy_private_input = Some(LazyInput::new(provider));
+    }
+
+    /// Set or overwrite the resource limits enforced during execution.
+    fn set_limits(&mut self, limits: ExecutionLimits) {
+        self.limits = limits;
+    }
+
     /// Set whether to capture logs or print out.
     pub(crate) fn capture_logs(&mut self, capture: bool) {
         if capture && self.logs.is_none() {
@@ -236,6 +323,14 @@ pub trait Emulator {
         bare_instruction: &Instruction,
         force_provable_transcript: bool,
     ) -> Result<(InstructionResult, (HashSet<LoadOp>, HashSet<StoreOp>))> {
+        let code = executor.cpu.registers.read(Register::X17);
+        if !executor.limits.allows_syscall(code) {
+            Err(VMErrorKind::SyscallNotAllowed(code, executor.cpu.pc.value))?
+        }
+        if HOST_CALL_CODES.contains(&code) {
+            return Self::execute_host_call(executor, code);
+        }
+
         let mut syscall_instruction = SyscallInstruction::decode(bare_instruction, &executor.cpu)?;
         let load_ops = syscall_instruction.memory_read(memory)?;
         syscall_instruction.execute(
@@ -254,6 +349,28 @@ pub trait Emulator {
         Ok((result, (load_ops, store_ops)))
     }
 
+    /// Execute a call to a host function, or replay its recorded result during the second pass.
+    #[allow(clippy::type_complexity)]
+    fn execute_host_call(
+        executor: &mut Executor,
+        code: u32,
+    ) -> Result<(InstructionResult, (HashSet<LoadOp>, HashSet<StoreOp>))> {
+        let pc = executor.cpu.pc.value;
+        let args: Vec<u32> = (10..=16)
+            .map(|i| executor.cpu.registers.read(Register::from(i)))
+            .collect();
+
+        let result = match executor.host_call_replay.pop_front() {
+            Some(call) if call.code == code && call.args == args => call.result,
+            Some(_) => Err(VMErrorKind::UnimplementedSyscall(code, pc))?,
+            None => executor.host_functions.call(code, &args, pc)?,
+        };
+        executor.cpu.registers.write(Register::X10, result);
+        executor.host_calls.push(HostCall { code, args, result });
+
+        Ok((Some(result), (HashSet::new(), HashSet::new())))
+    }
+
     /// Executes a single RISC-V instruction.
     ///
     /// 1. Retrieves the instruction executor function for the given opcode via HashMap.
@@ -277,6 +394,35 @@ pub trait Emulator {
     /// if success, return a `BasicBlockEntry` starting at the current PC.
     fn fetch_block(&mut self, pc: u32) -> Result<Rc<BasicBlockEntry>>;
 
+    /// Returns the raw (undecoded) instruction word at `pc`, if it lies within the program.
+    fn fetch_instruction_word(&self, pc: u32) -> Option<u32>;
+
+    /// Replace an unimplemented or undefined instruction error with an
+    /// [`VMErrorKind::UnsupportedInstruction`] naming the offending instruction at the current PC, or with
+    /// [`VMErrorKind::UnsupportedFloat`] for a floating-point instruction.
+    fn diagnose_unsupported_instruction(&self, error: VMError) -> VMError {
+        match &error.source {
+            VMErrorKind::UnimplementedInstruction(decoded)
+            | VMErrorKind::UndefinedInstruction(decoded) => {
+                let pc = self.get_executor().cpu.pc.value;
+
+                match self.fetch_instruction_word(pc) {
+                    Some(raw) if is_float_instruction(raw) => {
+                        VMErrorKind::UnsupportedFloat { pc }.into()
+                    }
+                    Some(raw) => VMErrorKind::UnsupportedInstruction {
+                        pc,
+                        raw,
+                        decoded: decoded.clone(),
+                    }
+                    .into(),
+                    None => error,
+                }
+            }
+            _ => error,
+        }
+    }
+
     /// Return a reference to the internal executor component used by the emulator.
     fn get_executor(&self) -> &Executor;
 
@@ -315,14 +461,33 @@ pub trait Emulator {
 
         loop {
             let basic_block_entry = self.fetch_block(self.get_executor().cpu.pc.value)?;
-            let (res, mem) =
-                self.execute_basic_block(&basic_block_entry, force_provable_transcript)?;
+            let (res, mem) = self
+                .execute_basic_block(&basic_block_entry, force_provable_transcript)
+                .map_err(|e| self.diagnose_unsupported_instruction(e))?;
 
             results.extend(res);
             transcript.extend(mem);
         }
     }
 
+    /// Execute an entire program like [`Self::execute`], without collecting the results and memory transcript of
+    /// its instructions, which the first pass discards.
+    ///
+    /// Decoded basic blocks are cached by [`Self::fetch_block`], so that each block is decoded once and the loop
+    /// only dispatches its instructions.
+    fn run(&mut self, force_provable_transcript: bool) -> Result<()> {
+        loop {
+            let basic_block_entry = self.fetch_block(self.get_executor().cpu.pc.value)?;
+            let at =
+                (self.get_executor().cpu.pc.value - basic_block_entry.start) as usize / WORD_SIZE;
+
+            for instruction in basic_block_entry.block.0[at..].iter() {
+                self.execute_instruction(instruction, force_provable_transcript)
+                    .map_err(|e| self.diagnose_unsupported_instruction(e))?;
+            }
+        }
+    }
+
     /// Adds a new opcode and its corresponding execution function to the emulator.
     fn add_opcode<IE: InstructionExecutor>(&mut self, op: &Opcode) -> Result<()> {
         self.get_executor_mut().add_opcode::<IE>(op)
@@ -333,6 +498,18 @@ pub trait Emulator {
         self.get_executor_mut().set_private_input(private_input)
     }
 
+    /// Set the provider extending the private input tape whenever the guest reads past its end.
+    ///
+    /// Every byte provided is recorded, see [`Executor::private_input_provided`].
+    fn set_input_provider(&mut self, provider: Box<dyn InputProvider>) {
+        self.get_executor_mut().set_input_provider(provider)
+    }
+
+    /// Set or overwrite the resource limits enforced during execution.
+    fn set_limits(&mut self, limits: ExecutionLimits) {
+        self.get_executor_mut().set_limits(limits)
+    }
+
     /// Update and return previous timestamps, but it currently works word-wise, so not used.
     #[allow(dead_code)]
     fn manage_timestamps(&mut self, size: &MemAccessSize, address: &u32) -> usize {
@@ -389,6 +566,19 @@ pub trait Emulator {
     fn finalize(&self) -> View;
 }
 
+/// A write to a watched address, see [`HarvardEmulator::run_with_watchpoint`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WatchHit {
+    /// The address of the writing instruction.
+    pub pc: u32,
+    /// The global clock when the write took place.
+    pub cycle: usize,
+    /// The value of the written location before the write.
+    pub old: u32,
+    /// The value written.
+    pub new: u32,
+}
+
 #[derive(Debug)]
 pub struct HarvardEmulator {
     // The core execution components
@@ -476,6 +666,8 @@ impl HarvardEmulator {
                 base_address: elf.base,
                 entrypoint: elf.entry,
                 global_clock: 1, // global_clock = 0 captures initalization for memory records
+                instruction_counts: vec![0; elf.instructions.len()],
+                symbols: elf.symbols.clone(),
                 ..Default::default()
             },
             instruction_memory: FixedMemory::<RO>::from_word_vec(
@@ -522,6 +714,93 @@ impl HarvardEmulator {
         emulator.executor.cpu.pc.value = emulator.executor.entrypoint;
         emulator
     }
+
+    /// Fails with [`VMErrorKind::UninitializedRead`] if any byte loaded was neither written before nor part of the
+    /// static memory image, then records the bytes stored as written.
+    fn check_initialized_reads(
+        &mut self,
+        pc: u32,
+        load_ops: &LoadOps,
+        store_ops: &StoreOps,
+    ) -> Result<()> {
+        let is_static = |address: u32| {
+            [&self.initial_rom_image, &self.initial_ram_image]
+                .iter()
+                .any(|image| (image.base()..image.end()).contains(&address))
+                // The public input and output start locations, see `from_elf`.
+                || (0x80..0x88).contains(&address)
+        };
+
+        for LoadOp::Op(size, address, _) in load_ops {
+            let uninitialized = (*address..address + *size as u32)
+                .find(|byte| !is_static(*byte) && !self.executor.written_memory.contains(byte));
+            if let Some(addr) = uninitialized {
+                Err(VMErrorKind::UninitializedRead { addr, pc })?
+            }
+        }
+
+        for StoreOp::Op(size, address, ..) in store_ops {
+            self.executor
+                .written_memory
+                .extend(*address..address + *size as u32);
+        }
+        Ok(())
+    }
+
+    /// Returns how much of each limited resource the execution has used so far.
+    pub fn limit_usage(&self) -> LimitUsage {
+        LimitUsage {
+            cycles: self.executor.global_clock.saturating_sub(1),
+            cycle_limit: self.executor.limits.cycle_limit,
+            memory: self.memory_stats.get_used_memory(),
+            max_memory: self.executor.limits.max_memory,
+        }
+    }
+
+    /// Returns the memory used by the execution so far.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        self.memory_stats.usage()
+    }
+
+    /// Runs the program until it exits, recording every write to memory that covers `addr`.
+    ///
+    /// Hits are recorded in execution order, with `old` and `new` being the values of the whole access, so their
+    /// width depends on the writing instruction. The program exiting with any exit code is not an error. This is
+    /// meant for debugging only, and has no bearing on the trace or the proof.
+    pub fn run_with_watchpoint(&mut self, addr: u32) -> Result<Vec<WatchHit>> {
+        let mut hits = Vec::new();
+
+        loop {
+            let basic_block_entry = self.fetch_block(self.executor.cpu.pc.value)?;
+            let at = (self.executor.cpu.pc.value - basic_block_entry.start) as usize / WORD_SIZE;
+
+            for instruction in basic_block_entry.block.0[at..].iter() {
+                let (pc, cycle) = (self.executor.cpu.pc.value, self.executor.global_clock);
+                let (_, memory_records) = match self.execute_instruction(instruction, false) {
+                    Ok(result) => result,
+                    Err(VMError {
+                        source: VMErrorKind::VMExited(_),
+                        ..
+                    }) => return Ok(hits),
+                    Err(e) => return Err(self.diagnose_unsupported_instruction(e)),
+                };
+
+                hits.extend(memory_records.iter().filter_map(|record| match record {
+                    MemoryRecord::StoreRecord((size, address, value, prev_value), _)
+                        if (*address..address + *size as u32).contains(&addr) =>
+                    {
+                        Some(WatchHit {
+                            pc,
+                            cycle,
+                            old: *prev_value,
+                            new: *value,
+                        })
+                    }
+                    _ => None,
+                }));
+            }
+        }
+    }
 }
 
 impl Emulator for HarvardEmulator {
@@ -536,7 +815,31 @@ impl Emulator for HarvardEmulator {
         bare_instruction: &Instruction,
         force_provable_transcript: bool,
     ) -> Result<(InstructionResult, MemoryRecords)> {
+        if let Some(limit) = self.executor.limits.cycle_limit {
+            if self.executor.global_clock > limit {
+                Err(VMErrorKind::CycleLimitExceeded {
+                    cycles: self.executor.global_clock,
+                    limit,
+                })?
+            }
+        }
+
+        // Floating-point instructions decode as UNIMPL, and are only executed under the soft-float policy.
+        let pc = self.executor.cpu.pc.value;
+        let soft_float = (self.executor.float_policy == FloatPolicy::SoftFloat
+            && bare_instruction.opcode == Opcode::from(BuiltinOpcode::UNIMPL))
+        .then(|| self.fetch_instruction_word(pc))
+        .flatten()
+        .filter(|&raw| is_float_instruction(raw));
+        let zbb = (self.executor.execute_zbb
+            && bare_instruction.opcode == Opcode::from(BuiltinOpcode::UNIMPL))
+        .then(|| self.fetch_instruction_word(pc))
+        .flatten()
+        .filter(|&raw| is_zbb_instruction(raw));
+
         let (res, (load_ops, store_ops)) = match (
+            soft_float,
+            zbb,
             self.executor
                 .instruction_executor
                 .get_for_read_input(&bare_instruction.opcode),
@@ -550,6 +853,16 @@ impl Emulator for HarvardEmulator {
                 .instruction_executor
                 .get(&bare_instruction.opcode),
         ) {
+            (Some(raw), ..) => self.executor.float_registers.execute(
+                raw,
+                pc,
+                &mut self.executor.cpu,
+                &mut self.data_memory,
+            )?,
+            (_, Some(raw), ..) => (
+                execute_zbb(raw, &mut self.executor.cpu),
+                (LoadOps::default(), StoreOps::default()),
+            ),
             _ if bare_instruction.is_system_instruction() => {
                 <HarvardEmulator as Emulator>::execute_syscall(
                     &mut self.executor,
@@ -560,17 +873,17 @@ impl Emulator for HarvardEmulator {
                     force_provable_transcript,
                 )?
             }
-            (Some(read_input), ..) => read_input(
+            (_, _, Some(read_input), ..) => read_input(
                 &mut self.executor.cpu,
                 &mut self.input_memory,
                 bare_instruction,
             )?,
-            (_, Some(write_output), ..) => write_output(
+            (_, _, _, Some(write_output), ..) => write_output(
                 &mut self.executor.cpu,
                 &mut self.output_memory,
                 bare_instruction,
             )?,
-            (_, _, Some(custom_executor), ..) => custom_executor(
+            (_, _, _, _, Some(custom_executor), ..) => custom_executor(
                 &mut self.executor.cpu,
                 &mut self.data_memory,
                 bare_instruction,
@@ -583,19 +896,54 @@ impl Emulator for HarvardEmulator {
             (.., Err(e)) => return Err(e),
         };
 
+        // Public input and output have their own memories, which are always initialized.
+        let accesses_data_memory = soft_float.is_some()
+            || !(self
+                .executor
+                .instruction_executor
+                .get_for_read_input(&bare_instruction.opcode)
+                .is_some()
+                || self
+                    .executor
+                    .instruction_executor
+                    .get_for_write_output(&bare_instruction.opcode)
+                    .is_some());
+        if self.executor.trap_uninitialized_reads && accesses_data_memory {
+            self.check_initialized_reads(pc, &load_ops, &store_ops)?;
+        }
+
         let mut memory_records = MemoryRecords::new();
 
         load_ops.iter().for_each(|op| {
+            self.memory_stats.record_access(op.get_address());
             memory_records.insert(op.as_record(self.executor.global_clock));
         });
 
         store_ops.iter().for_each(|op| {
+            self.memory_stats.record_access(op.get_address());
             memory_records.insert(op.as_record(self.executor.global_clock));
         });
 
         self.memory_stats
             .update_stack_access(self.executor.cpu.registers.read(Register::X2));
 
+        if let Some(max_memory) = self.executor.limits.max_memory {
+            let bytes = self.memory_stats.get_used_memory();
+            if bytes > max_memory {
+                Err(VMErrorKind::MemoryLimitExceeded {
+                    bytes,
+                    limit: max_memory,
+                })?
+            }
+        }
+
+        *self
+            .executor
+            .instruction_mix
+            .entry(InstrClass::from(&bare_instruction.opcode))
+            .or_default() += 1;
+        self.executor.count_instruction(pc);
+
         if !bare_instruction.is_branch_or_jump_instruction() {
             self.executor.cpu.pc.step();
         }
@@ -641,6 +989,13 @@ impl Emulator for HarvardEmulator {
         Ok(entry)
     }
 
+    fn fetch_instruction_word(&self, pc: u32) -> Option<u32> {
+        self.instruction_memory
+            .segment_words(pc, None)
+            .first()
+            .copied()
+    }
+
     fn get_executor(&self) -> &Executor {
         &self.executor
     }
@@ -721,6 +1076,8 @@ impl Emulator for HarvardEmulator {
         View {
             memory_layout: None,
             debug_logs,
+            log_records: self.get_executor().log_records.clone(),
+            host_calls: self.get_executor().host_calls.clone(),
             program_memory: ProgramInfo {
                 initial_pc: self.executor.entrypoint,
                 program: self
@@ -741,6 +1098,15 @@ impl Emulator for HarvardEmulator {
             exit_code,
             output_memory,
             associated_data: Vec::new(),
+            statement_context: Vec::new(),
+            input_transcript: None,
+            instruction_mix: self.executor.instruction_mix.clone(),
+            instruction_counts: self.executor.instruction_counts.clone(),
+            symbols: self.executor.symbols.clone(),
+            final_registers: self.executor.cpu.registers.to_array(),
+            alloc_stats: self.executor.alloc_stats,
+            limit_usage: self.limit_usage(),
+            memory_stats: self.memory_usage(),
         }
     }
 }
@@ -827,13 +1193,9 @@ impl LinearEmulator {
             )
             .unwrap();
 
-        Ok(Self::from_elf(
-            memory_layout,
-            ad,
-            &elf,
-            public_input,
-            private_input,
-        ))
+        let mut emulator = Self::from_elf(memory_layout, ad, &elf, public_input, private_input);
+        emulator.executor.host_call_replay = emulator_harvard.executor.host_calls.clone().into();
+        Ok(emulator)
     }
 
     /// Creates a Linear Emulator from an ELF file.
@@ -979,6 +1341,16 @@ impl LinearEmulator {
                 base_address: code_start,
                 entrypoint: code_start + (elf.entry - elf.base),
                 global_clock: 1, // global_clock = 0 captures initalization for memory records
+                instruction_counts: vec![0; elf.instructions.len()],
+                symbols: elf
+                    .symbols
+                    .iter()
+                    .filter(|symbol| symbol.address >= elf.base)
+                    .map(|symbol| FunctionSymbol {
+                        address: code_start + (symbol.address - elf.base),
+                        ..symbol.clone()
+                    })
+                    .collect(),
                 ..Default::default()
             },
             instruction_index: instruction_memory_index,
@@ -1008,6 +1380,7 @@ impl Emulator for LinearEmulator {
         bare_instruction: &Instruction,
         _force_second_pass: bool, // Linear Emulator always does second pass
     ) -> Result<(InstructionResult, MemoryRecords)> {
+        let pc = self.executor.cpu.pc.value;
         let (res, (load_ops, store_ops)) = match (
             self.executor
                 .instruction_executor
@@ -1057,6 +1430,13 @@ impl Emulator for LinearEmulator {
             memory_records.insert(op.as_record(self.executor.global_clock));
         });
 
+        *self
+            .executor
+            .instruction_mix
+            .entry(InstrClass::from(&bare_instruction.opcode))
+            .or_default() += 1;
+        self.executor.count_instruction(pc);
+
         if !bare_instruction.is_branch_or_jump_instruction() {
             self.executor.cpu.pc.step();
         }
@@ -1103,6 +1483,13 @@ impl Emulator for LinearEmulator {
         Ok(entry)
     }
 
+    fn fetch_instruction_word(&self, pc: u32) -> Option<u32> {
+        self.memory
+            .segment_words(self.instruction_index, pc, None)
+            .ok()
+            .and_then(|words| words.first().copied())
+    }
+
     fn get_executor(&self) -> &Executor {
         &self.executor
     }
@@ -1262,6 +1649,8 @@ impl Emulator for LinearEmulator {
         View {
             memory_layout: Some(self.memory_layout),
             debug_logs,
+            log_records: self.get_executor().log_records.clone(),
+            host_calls: self.get_executor().host_calls.clone(),
             program_memory: ProgramInfo {
                 // todo: this likely isn't robust, we need to rely on elf.entry,
                 //       but it seems to be working with the current runtime
@@ -1289,6 +1678,15 @@ impl Emulator for LinearEmulator {
             exit_code,
             output_memory,
             associated_data,
+            statement_context: Vec::new(),
+            input_transcript: None,
+            instruction_mix: self.executor.instruction_mix.clone(),
+            instruction_counts: self.executor.instruction_counts.clone(),
+            symbols: self.executor.symbols.clone(),
+            final_registers: self.executor.cpu.registers.to_array(),
+            alloc_stats: self.executor.alloc_stats,
+            limit_usage: LimitUsage::default(),
+            memory_stats: MemoryUsage::default(),
         }
     }
 }
@@ -1353,6 +1751,90 @@ mod tests {
         );
     }
 
+    #[test]
+    #[serial]
+    fn test_harvard_run_matches_execute() {
+        let elf_file = read_testing_elf_from_path!("/test/fib_10.elf");
+
+        let mut executed = HarvardEmulator::from_elf(&elf_file, &[], &[]);
+        let mut run = HarvardEmulator::from_elf(&elf_file, &[], &[]);
+
+        assert_eq!(
+            executed.execute(false).unwrap_err().source,
+            VMErrorKind::VMExited(0)
+        );
+        assert_eq!(run.run(false).unwrap_err().source, VMErrorKind::VMExited(0));
+        assert_eq!(run.executor.global_clock, executed.executor.global_clock);
+        assert_eq!(run.executor.cpu.registers, executed.executor.cpu.registers);
+    }
+
+    #[test]
+    #[serial]
+    fn test_harvard_memory_stats() {
+        let elf_file = read_testing_elf_from_path!("/test/fib_10.elf");
+
+        let mut emulator = HarvardEmulator::from_elf(&elf_file, &[], &[]);
+        let _ = emulator.execute(false);
+        let usage = emulator.memory_usage();
+        let view = emulator.finalize();
+
+        assert_eq!(view.memory_stats(), usage);
+        assert!(usage.peak_stack > 0);
+        assert!(usage.pages_touched > 0);
+        assert_eq!(
+            view.limit_usage().memory,
+            usage.peak_heap + usage.peak_stack
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_harvard_profile() {
+        let elf_file = read_testing_elf_from_path!("/test/fib_10.elf");
+
+        let mut emulator = HarvardEmulator::from_elf(&elf_file, &[], &[]);
+        let _ = emulator.execute(false);
+        let view = emulator.finalize();
+        let profile = view.profile();
+
+        assert_eq!(
+            profile.total_cycles(),
+            view.instruction_counts.iter().sum::<u64>()
+        );
+        let fib = profile
+            .functions
+            .iter()
+            .find(|f| f.name.starts_with("_ZN3fib3fib"))
+            .expect("fib was not profiled");
+        assert!(fib.cycles > 0);
+        assert!(fib.calls > 0);
+    }
+
+    #[test]
+    #[serial]
+    fn test_harvard_cycle_limit() {
+        let elf_file = read_testing_elf_from_path!("/test/fib_10.elf");
+
+        let mut emulator = HarvardEmulator::from_elf(&elf_file, &[], &[]);
+        emulator.set_limits(ExecutionLimits {
+            cycle_limit: Some(10),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            emulator.execute(false).unwrap_err().source,
+            VMErrorKind::CycleLimitExceeded {
+                cycles: 11,
+                limit: 10
+            }
+        );
+
+        let usage = emulator.limit_usage();
+        assert_eq!(usage.cycles, 10);
+        assert_eq!(usage.cycle_limit, Some(10));
+        assert_eq!(usage.cycle_fraction(), Some(1.0));
+    }
+
     #[test]
     fn test_harvard_fibonacci() {
         let basic_blocks = setup_basic_block_ir();
@@ -1367,6 +1849,101 @@ mod tests {
         assert_eq!(emulator.executor.cpu.registers[31.into()], 1346269);
     }
 
+    #[test]
+    fn test_harvard_instruction_mix() {
+        let basic_blocks = vec![
+            // x2 = 10
+            BasicBlock::new(vec![Instruction::new_ir(
+                Opcode::from(BuiltinOpcode::ADDI),
+                2,
+                0,
+                10,
+            )]),
+            // do { x1 += 1 } while x1 != x2
+            BasicBlock::new(vec![
+                Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 1, 1, 1),
+                Instruction::new_ir(
+                    Opcode::from(BuiltinOpcode::BNE),
+                    1,
+                    2,
+                    (!4u32).wrapping_add(1),
+                ),
+            ]),
+        ];
+        let mut emulator = HarvardEmulator::from_basic_blocks(&basic_blocks);
+
+        assert_eq!(
+            emulator.execute(false).unwrap_err().source,
+            VMErrorKind::VMOutOfInstructions
+        );
+
+        let mix = emulator.finalize().instruction_mix();
+        assert_eq!(mix.get(&InstrClass::Arithmetic), Some(&11));
+        assert_eq!(mix.get(&InstrClass::Branch), Some(&10));
+        assert_eq!(mix.get(&InstrClass::Memory), None);
+    }
+
+    #[test]
+    fn test_final_registers() {
+        let basic_blocks = vec![BasicBlock::new(vec![
+            // x7 = 0x12345000 + 0x678
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::LUI), 7, 0, 0x12345),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 7, 7, 0x678),
+            // writes to x0 are discarded
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 0, 7, 1),
+        ])];
+
+        let mut emulator = HarvardEmulator::from_basic_blocks(&basic_blocks);
+        assert_eq!(
+            emulator.execute(false).unwrap_err().source,
+            VMErrorKind::VMOutOfInstructions
+        );
+
+        let registers = emulator.finalize().final_registers();
+        assert_eq!(registers[7], 0x12345678);
+        assert_eq!(registers[0], 0);
+    }
+
+    #[test]
+    fn test_run_with_watchpoint() {
+        let basic_blocks = vec![BasicBlock::new(vec![
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 1, 0, 0x400),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 2, 0, 7),
+            // hit: 0 -> 7
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SW), 1, 2, 0),
+            // neither the next word nor another byte of the watched word are hits
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SW), 1, 2, 4),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SB), 1, 2, 1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 2, 0, 9),
+            // hit: 0x707 -> 9
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SW), 1, 2, 0),
+            // exit(0)
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 17, 0, 0x201),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ECALL), 0, 0, 0),
+        ])];
+
+        let mut emulator = HarvardEmulator::from_basic_blocks(&basic_blocks);
+        let hits = emulator.run_with_watchpoint(0x400).unwrap();
+
+        assert_eq!(
+            hits,
+            vec![
+                WatchHit {
+                    pc: ELF_TEXT_START + 8,
+                    cycle: 3,
+                    old: 0,
+                    new: 7,
+                },
+                WatchHit {
+                    pc: ELF_TEXT_START + 24,
+                    cycle: 7,
+                    old: 0x707,
+                    new: 9,
+                },
+            ]
+        );
+    }
+
     #[test]
     fn test_harvard_set_private_input() {
         let private_input: [u8; 5] = [1, 2, 3, 4, 5];
@@ -1471,4 +2048,31 @@ mod tests {
             VMErrorKind::UndefinedInstruction(op)
         );
     }
+
+    #[test]
+    fn test_unsupported_instruction_diagnostic() {
+        // `fadd.s f0, f0, f1`, from the (unsupported) F extension.
+        const FADD_S: u32 = 0x0010_0053;
+
+        let addi = Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 1, 0, 1);
+        let mut emulator =
+            HarvardEmulator::from_basic_blocks(&vec![BasicBlock::new(vec![addi.clone()])]);
+        emulator.instruction_memory = FixedMemory::<RO>::from_word_vec(
+            ELF_TEXT_START,
+            2 * WORD_SIZE,
+            vec![addi.encode(), FADD_S],
+        );
+
+        let err = emulator.execute(false).unwrap_err();
+
+        assert_eq!(
+            err.source,
+            VMErrorKind::UnsupportedInstruction {
+                pc: ELF_TEXT_START + WORD_SIZE as u32,
+                raw: FADD_S,
+                decoded: Opcode::from(BuiltinOpcode::UNIMPL),
+            }
+        );
+        assert!(err.to_string().contains("0x00100053"));
+    }
 }
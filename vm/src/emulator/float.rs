@@ -0,0 +1,290 @@
+//! Handling of single-precision floating-point (RV32F) instructions.
+//!
+//! No chip constrains floating-point instructions, so by default the emulator rejects them with
+//! [`VMErrorKind::UnsupportedFloat`] rather than executing something that cannot be proven. Guests built for the
+//! `riscv32im` target use soft-float library calls made of integer instructions, and never contain them.
+//!
+//! Under [`FloatPolicy::SoftFloat`] the first-pass (Harvard) emulator executes them instead, with IEEE 754
+//! single-precision arithmetic rounding to nearest even and canonical NaNs, so results are the same on every host.
+//! Exception flags and the `fcsr` register are not modelled.
+
+use nexus_common::{
+    cpu::Registers,
+    memory::{LoadOp, LoadOps, MemAccessSize, MemoryProcessor, StoreOps},
+};
+
+use crate::{
+    cpu::{instructions::InstructionResult, Cpu},
+    error::{Result, VMErrorKind},
+    riscv::Register,
+};
+
+/// How the emulator treats floating-point instructions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FloatPolicy {
+    /// Fail with [`VMErrorKind::UnsupportedFloat`].
+    #[default]
+    Reject,
+    /// Execute single-precision instructions deterministically in software. Only supported by the first-pass
+    /// (Harvard) emulator, so such executions can be run but not proven.
+    SoftFloat,
+}
+
+const LOAD_FP: u32 = 0b0000111;
+const STORE_FP: u32 = 0b0100111;
+const MADD: u32 = 0b1000011;
+const MSUB: u32 = 0b1000111;
+const NMSUB: u32 = 0b1001011;
+const NMADD: u32 = 0b1001111;
+const OP_FP: u32 = 0b1010011;
+
+/// The NaN produced by any operation whose result is NaN, as required by RISC-V.
+const CANONICAL_NAN: u32 = 0x7fc0_0000;
+const SIGN_BIT: u32 = 1 << 31;
+
+/// Returns whether the raw instruction word is a floating-point instruction (of any precision).
+pub(crate) fn is_float_instruction(raw: u32) -> bool {
+    matches!(
+        raw & 0x7f,
+        LOAD_FP | STORE_FP | MADD | MSUB | NMSUB | NMADD | OP_FP
+    )
+}
+
+fn canonicalize(value: f32) -> u32 {
+    if value.is_nan() {
+        CANONICAL_NAN
+    } else {
+        value.to_bits()
+    }
+}
+
+fn sign_extend_12(imm: u32) -> u32 {
+    ((imm << 20) as i32 >> 20) as u32
+}
+
+/// Returns the `fclass.s` mask of `value`.
+fn classify(value: f32) -> u32 {
+    use std::num::FpCategory::*;
+
+    let negative = value.is_sign_negative();
+    let bit = match (value.classify(), negative) {
+        (Infinite, true) => 0,
+        (Normal, true) => 1,
+        (Subnormal, true) => 2,
+        (Zero, true) => 3,
+        (Zero, false) => 4,
+        (Subnormal, false) => 5,
+        (Normal, false) => 6,
+        (Infinite, false) => 7,
+        // Signaling NaNs have the most significant bit of the mantissa clear.
+        (Nan, _) if value.to_bits() & (1 << 22) == 0 => 8,
+        (Nan, _) => 9,
+    };
+    1 << bit
+}
+
+/// The floating-point register file, only used under [`FloatPolicy::SoftFloat`].
+#[derive(Debug, Default)]
+pub(crate) struct FloatRegisters([u32; 32]);
+
+impl FloatRegisters {
+    fn get(&self, reg: u32) -> f32 {
+        f32::from_bits(self.0[reg as usize])
+    }
+
+    /// Execute the floating-point instruction `raw` at `pc`, returning the value written to an integer register, if
+    /// any, along with the memory operations.
+    ///
+    /// Fails with [`VMErrorKind::UnsupportedFloat`] for double-precision instructions, and rounding modes other than
+    /// round to nearest even where rounding can occur (except for conversions to integers).
+    pub(crate) fn execute(
+        &mut self,
+        raw: u32,
+        pc: u32,
+        cpu: &mut Cpu,
+        memory: &mut impl MemoryProcessor,
+    ) -> Result<(InstructionResult, (LoadOps, StoreOps))> {
+        let rd = (raw >> 7) & 0x1f;
+        let funct3 = (raw >> 12) & 0x7;
+        let rs1 = (raw >> 15) & 0x1f;
+        let rs2 = (raw >> 20) & 0x1f;
+        let funct7 = raw >> 25;
+        let x_rs1 = cpu.registers.read(Register::from(rs1 as u8));
+        // The dynamic rounding mode reads `fcsr`, which always holds round to nearest even.
+        let round_to_nearest_even = funct3 == 0b000 || funct3 == 0b111;
+
+        // Either an integer result, or a floating-point result (as bits) for `rd`.
+        let mut x_result = None;
+        let mut f_result = None;
+        let mut load_ops = LoadOps::default();
+        let mut store_ops = StoreOps::default();
+
+        match raw & 0x7f {
+            LOAD_FP if funct3 == 0b010 => {
+                let address = x_rs1.wrapping_add(sign_extend_12(raw >> 20));
+                let op = memory.read(address, MemAccessSize::Word)?;
+                let LoadOp::Op(_, _, value) = op;
+                f_result = Some(value);
+                load_ops = op.into();
+            }
+            STORE_FP if funct3 == 0b010 => {
+                let address = x_rs1.wrapping_add(sign_extend_12(((raw >> 25) << 5) | rd));
+                store_ops = memory
+                    .write(address, MemAccessSize::Word, self.0[rs2 as usize])?
+                    .into();
+            }
+            opcode @ (MADD | MSUB | NMSUB | NMADD)
+                if funct7 & 0b11 == 0 && round_to_nearest_even =>
+            {
+                let (a, b, c) = (self.get(rs1), self.get(rs2), self.get(raw >> 27));
+                let value = match opcode {
+                    MADD => a.mul_add(b, c),
+                    MSUB => a.mul_add(b, -c),
+                    NMSUB => (-a).mul_add(b, c),
+                    _ => (-a).mul_add(b, -c),
+                };
+                f_result = Some(canonicalize(value));
+            }
+            OP_FP => {
+                let (a, b) = (self.get(rs1), self.get(rs2));
+                match (funct7, funct3) {
+                    (0b0000000, _) if round_to_nearest_even => f_result = Some(canonicalize(a + b)),
+                    (0b0000100, _) if round_to_nearest_even => f_result = Some(canonicalize(a - b)),
+                    (0b0001000, _) if round_to_nearest_even => f_result = Some(canonicalize(a * b)),
+                    (0b0001100, _) if round_to_nearest_even => f_result = Some(canonicalize(a / b)),
+                    (0b0101100, _) if round_to_nearest_even => {
+                        f_result = Some(canonicalize(a.sqrt()))
+                    }
+                    // Sign injection operates on the raw bits.
+                    (0b0010000, 0b000..=0b010) => {
+                        let (a, b) = (a.to_bits(), b.to_bits());
+                        let sign = match funct3 {
+                            0b000 => b & SIGN_BIT,
+                            0b001 => !b & SIGN_BIT,
+                            _ => (a ^ b) & SIGN_BIT,
+                        };
+                        f_result = Some((a & !SIGN_BIT) | sign);
+                    }
+                    (0b0010100, 0b000 | 0b001) => {
+                        let max = funct3 == 0b001;
+                        f_result = Some(match (a.is_nan(), b.is_nan()) {
+                            (true, true) => CANONICAL_NAN,
+                            (true, false) => b.to_bits(),
+                            (false, true) => a.to_bits(),
+                            // -0.0 is considered smaller than +0.0.
+                            _ if a == b => {
+                                if a.is_sign_negative() != max {
+                                    a.to_bits()
+                                } else {
+                                    b.to_bits()
+                                }
+                            }
+                            _ if (a < b) != max => a.to_bits(),
+                            _ => b.to_bits(),
+                        });
+                    }
+                    (0b1010000, 0b000) => x_result = Some((a <= b) as u32),
+                    (0b1010000, 0b001) => x_result = Some((a < b) as u32),
+                    (0b1010000, 0b010) => x_result = Some((a == b) as u32),
+                    (0b1100000, _) if rs2 <= 1 => {
+                        let rounded = match funct3 {
+                            0b000 | 0b111 => a.round_ties_even(),
+                            0b001 => a.trunc(),
+                            0b010 => a.floor(),
+                            0b011 => a.ceil(),
+                            0b100 => a.round(),
+                            _ => Err(VMErrorKind::UnsupportedFloat { pc })?,
+                        };
+                        // Out of range values saturate, and NaN converts to the largest integer.
+                        x_result = Some(match (rs2, a.is_nan()) {
+                            (0, true) => i32::MAX as u32,
+                            (0, false) => rounded as i32 as u32,
+                            (_, true) => u32::MAX,
+                            (_, false) => rounded as u32,
+                        });
+                    }
+                    (0b1101000, _) if rs2 <= 1 && round_to_nearest_even => {
+                        let value = if rs2 == 0 {
+                            x_rs1 as i32 as f32
+                        } else {
+                            x_rs1 as f32
+                        };
+                        f_result = Some(value.to_bits());
+                    }
+                    (0b1110000, 0b000) if rs2 == 0 => x_result = Some(a.to_bits()),
+                    (0b1110000, 0b001) if rs2 == 0 => x_result = Some(classify(a)),
+                    (0b1111000, 0b000) if rs2 == 0 => f_result = Some(x_rs1),
+                    _ => Err(VMErrorKind::UnsupportedFloat { pc })?,
+                }
+            }
+            _ => Err(VMErrorKind::UnsupportedFloat { pc })?,
+        }
+
+        if let Some(value) = f_result {
+            self.0[rd as usize] = value;
+        }
+        if let Some(value) = x_result {
+            cpu.registers.write(Register::from(rd as u8), value);
+        }
+
+        Ok((x_result, (load_ops, store_ops)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::UnifiedMemory;
+
+    /// Encode an OP-FP instruction.
+    fn op_fp(funct7: u32, rs2: u32, rs1: u32, funct3: u32, rd: u32) -> u32 {
+        (funct7 << 25) | (rs2 << 20) | (rs1 << 15) | (funct3 << 12) | (rd << 7) | OP_FP
+    }
+
+    #[test]
+    fn test_soft_float_arithmetic() {
+        let mut cpu = Cpu::default();
+        let mut memory = UnifiedMemory::default();
+        let mut fregs = FloatRegisters::default();
+
+        // f1 = 1.5 (fmv.w.x), f2 = 0.0
+        cpu.registers.write(Register::X5, 1.5f32.to_bits());
+        fregs
+            .execute(op_fp(0b1111000, 0, 5, 0b000, 1), 0, &mut cpu, &mut memory)
+            .unwrap();
+
+        // f3 = f1 * f1 = 2.25, then fcvt.w.s rounding towards zero gives 2
+        fregs
+            .execute(op_fp(0b0001000, 1, 1, 0b111, 3), 0, &mut cpu, &mut memory)
+            .unwrap();
+        let (res, _) = fregs
+            .execute(op_fp(0b1100000, 0, 3, 0b001, 6), 0, &mut cpu, &mut memory)
+            .unwrap();
+        assert_eq!(res, Some(2));
+        assert_eq!(cpu.registers.read(Register::X6), 2);
+
+        // 0.0 / 0.0 is the canonical NaN, which converts to i32::MAX
+        fregs
+            .execute(op_fp(0b0001100, 2, 2, 0b000, 4), 0, &mut cpu, &mut memory)
+            .unwrap();
+        let (res, _) = fregs
+            .execute(op_fp(0b1110000, 0, 4, 0b000, 7), 0, &mut cpu, &mut memory)
+            .unwrap();
+        assert_eq!(res, Some(CANONICAL_NAN));
+        let (res, _) = fregs
+            .execute(op_fp(0b1100000, 0, 4, 0b000, 7), 0, &mut cpu, &mut memory)
+            .unwrap();
+        assert_eq!(res, Some(i32::MAX as u32));
+
+        // Double-precision instructions are not supported.
+        let err = fregs
+            .execute(
+                op_fp(0b0000001, 1, 1, 0b000, 3),
+                0x1000,
+                &mut cpu,
+                &mut memory,
+            )
+            .unwrap_err();
+        assert_eq!(err.source, VMErrorKind::UnsupportedFloat { pc: 0x1000 });
+    }
+}
@@ -0,0 +1,106 @@
+//! Lazily provided private input.
+//!
+//! Rather than supplying the whole private input tape up front, the host may register an
+//! [`InputProvider`] with the first-pass (Harvard) emulator, which is asked for the next chunk of
+//! the tape whenever the guest reads past its end. Every byte provided is recorded, so that the
+//! second-pass emulator replays exactly the prefix of the tape consumed by the guest.
+
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{self, BufReader, Read},
+    path::Path,
+};
+
+use crate::error::{Result, VMErrorKind};
+
+/// A source of the private input tape, queried on demand as the guest reads it.
+pub trait InputProvider: Send {
+    /// Return the next chunk of the tape, or `None` once the tape is exhausted.
+    fn next_chunk(&mut self) -> io::Result<Option<Vec<u8>>>;
+}
+
+impl<F: FnMut() -> io::Result<Option<Vec<u8>>> + Send> InputProvider for F {
+    fn next_chunk(&mut self) -> io::Result<Option<Vec<u8>>> {
+        self()
+    }
+}
+
+/// An [`InputProvider`] reading the tape from a file, a chunk at a time.
+pub struct FileInputProvider {
+    reader: BufReader<File>,
+    chunk_size: usize,
+}
+
+impl FileInputProvider {
+    /// The default number of bytes read from the file at a time.
+    pub const DEFAULT_CHUNK_SIZE: usize = 4096;
+
+    /// Open the file at `path` to provide the tape.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self {
+            reader: BufReader::new(File::open(path)?),
+            chunk_size: Self::DEFAULT_CHUNK_SIZE,
+        })
+    }
+
+    /// Set the number of bytes read from the file at a time.
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        assert!(chunk_size > 0);
+        self.chunk_size = chunk_size;
+        self
+    }
+}
+
+impl InputProvider for FileInputProvider {
+    fn next_chunk(&mut self) -> io::Result<Option<Vec<u8>>> {
+        let mut chunk = Vec::with_capacity(self.chunk_size);
+        (&mut self.reader)
+            .take(self.chunk_size as u64)
+            .read_to_end(&mut chunk)?;
+        Ok((!chunk.is_empty()).then_some(chunk))
+    }
+}
+
+/// The provider of a private input tape, along with the bytes it has provided so far.
+pub(crate) struct LazyInput {
+    provider: Box<dyn InputProvider>,
+    exhausted: bool,
+    pub(crate) provided: Vec<u8>,
+}
+
+impl std::fmt::Debug for LazyInput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LazyInput")
+            .field("exhausted", &self.exhausted)
+            .field("provided", &self.provided.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl LazyInput {
+    pub(crate) fn new(provider: Box<dyn InputProvider>) -> Self {
+        Self {
+            provider,
+            exhausted: false,
+            provided: Vec::new(),
+        }
+    }
+
+    /// Append the next non-empty chunk of the tape to `tape`, unless the provider is exhausted.
+    pub(crate) fn refill(&mut self, tape: &mut VecDeque<u8>) -> Result<()> {
+        while !self.exhausted {
+            match self.provider.next_chunk() {
+                Ok(Some(chunk)) if chunk.is_empty() => continue,
+                Ok(Some(chunk)) => {
+                    self.provided.extend_from_slice(&chunk);
+                    tape.extend(chunk);
+                    return Ok(());
+                }
+                Ok(None) => self.exhausted = true,
+                Err(e) => return Err(VMErrorKind::PrivateInputError(e.to_string()))?,
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+//! Resource limits enforced while executing a guest program.
+//!
+//! Limits are checked by the first-pass (Harvard) emulator, so a program that exceeds them is
+//! rejected before any trace is generated. All limits are optional, and an unset limit is not
+//! enforced.
+
+use serde::{Deserialize, Serialize};
+
+use crate::system::SyscallCode;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionLimits {
+    /// The maximum number of instructions the guest may execute.
+    pub cycle_limit: Option<usize>,
+    /// The maximum number of bytes of heap and stack the guest may use.
+    pub max_memory: Option<u32>,
+    /// The syscall codes the guest may invoke. The exit syscall is always permitted.
+    pub syscalls: Option<Vec<u32>>,
+}
+
+impl ExecutionLimits {
+    /// Returns whether the syscall with the given code may be invoked under these limits.
+    pub fn allows_syscall(&self, code: u32) -> bool {
+        code == u32::from(SyscallCode::Exit)
+            || self
+                .syscalls
+                .as_ref()
+                .is_none_or(|allowed| allowed.contains(&code))
+    }
+}
+
+/// How much of each resource an execution used, alongside the limit on it, if any.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LimitUsage {
+    /// The number of instructions executed.
+    pub cycles: usize,
+    pub cycle_limit: Option<usize>,
+    /// The number of bytes of heap and stack used.
+    pub memory: u32,
+    pub max_memory: Option<u32>,
+}
+
+impl LimitUsage {
+    /// Returns the fraction of the cycle limit used, if a cycle limit was set.
+    pub fn cycle_fraction(&self) -> Option<f64> {
+        self.cycle_limit
+            .map(|limit| self.cycles as f64 / limit as f64)
+    }
+
+    /// Returns the fraction of the memory limit used, if a memory limit was set.
+    pub fn memory_fraction(&self) -> Option<f64> {
+        self.max_memory
+            .map(|limit| self.memory as f64 / limit as f64)
+    }
+}
@@ -6,10 +6,12 @@
 //! # Key Components
 //!
 //! - `MemoryStats`: A struct that keeps track of memory access statistics.
+//! - `MemoryUsage`: The peak heap and stack usage and pages touched, reported by `View::memory_stats`.
 //!
 //! # Features
 //!
 //! - Tracks the maximum heap access and minimum stack access addresses.
+//! - Tracks the pages of memory touched by loads and stores.
 //! - Updates statistics based on load and store operations during program execution.
 //! - Creates an optimized `LinearMemoryLayout` based on observed memory usage.
 //!
@@ -34,8 +36,13 @@
 //! - This implementation prioritizes safety over potential further optimizations
 //!   that could be achieved by tracking actual stack accesses.
 
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
 use crate::emulator::layout::LinearMemoryLayout;
 use crate::error::Result;
+use crate::memory::page::page_number;
 
 #[derive(Debug)]
 pub struct MemoryStats {
@@ -43,6 +50,18 @@ pub struct MemoryStats {
     min_stack_access: u32,
     heap_bottom: u32,
     stack_top: u32,
+    touched_pages: BTreeSet<u32>,
+}
+
+/// The memory used by an execution.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryUsage {
+    /// The number of bytes of heap allocated.
+    pub peak_heap: u32,
+    /// The largest depth of the stack, in bytes.
+    pub peak_stack: u32,
+    /// The number of distinct pages of memory loaded from or stored to.
+    pub pages_touched: usize,
 }
 
 impl Default for MemoryStats {
@@ -58,6 +77,7 @@ impl MemoryStats {
             min_stack_access: stack_top,
             heap_bottom,
             stack_top,
+            touched_pages: BTreeSet::new(),
         }
     }
 
@@ -71,6 +91,20 @@ impl MemoryStats {
         }
     }
 
+    /// Record a load from or store to `address`.
+    pub fn record_access(&mut self, address: u32) {
+        self.touched_pages.insert(page_number(address));
+    }
+
+    /// Returns the memory used so far.
+    pub fn usage(&self) -> MemoryUsage {
+        MemoryUsage {
+            peak_heap: self.max_heap_access - self.heap_bottom,
+            peak_stack: self.stack_top - self.min_stack_access,
+            pages_touched: self.touched_pages.len(),
+        }
+    }
+
     /// Create an optimized linear memory layout based on the memory stats.
     ///
     /// Note: `input_size` is the size of the public input, and `output_size` is the size of the
@@ -95,6 +129,11 @@ impl MemoryStats {
         )
     }
 
+    /// Returns the number of bytes of heap and stack used so far.
+    pub fn get_used_memory(&self) -> u32 {
+        (self.max_heap_access - self.heap_bottom) + (self.stack_top - self.min_stack_access)
+    }
+
     /// Returns the total number of addresses under RAM memory checking.
     pub fn get_tracked_ram_size(&self, input_size: u32, output_size: u32) -> u32 {
         let heap_size = self.max_heap_access - self.heap_bottom;
@@ -142,4 +181,24 @@ mod tests {
         assert_eq!(layout.stack_top(), 0xF590);
         assert_eq!(layout.ad_end(), 0xF690);
     }
+
+    #[test]
+    fn test_usage() {
+        let mut stats = MemoryStats::new(0x1000, 0x10000);
+        stats.register_heap_allocation(0x1000, 0x20);
+        stats.update_stack_access(0xFF00);
+
+        stats.record_access(0x1000);
+        stats.record_access(0x1010);
+        stats.record_access(0xFF04);
+
+        assert_eq!(
+            stats.usage(),
+            MemoryUsage {
+                peak_heap: 0x20,
+                peak_stack: 0x100,
+                pages_touched: 2,
+            }
+        );
+    }
 }
@@ -9,6 +9,12 @@
 //! - `HarvardEmulator`: An implementation of the emulator using Harvard architecture.
 //! - `LinearEmulator`: An implementation of the emulator using Linear architecture.
 //! - `LinearMemoryLayout`: Defines the memory layout for the linear emulator.
+//! - `ExecutionLimits`: Optional resource limits (cycles, memory, syscalls) enforced during execution.
+//! - `LimitUsage`: How close an execution came to each resource limit, reported by `View::limit_usage`.
+//! - `MemoryUsage`: The peak heap and stack usage of an execution, reported by `View::memory_stats`.
+//! - `FloatPolicy`: Whether floating-point instructions are rejected or executed in software.
+//! - `Executor::execute_zbb`: Whether bit-manipulation (Zbb) instructions are rejected or executed.
+//! - `HarvardEmulator::debug`: A GDB remote debugging server for guest programs, behind the `gdb` feature.
 //!
 //! ## Memory Management
 //!
@@ -52,13 +58,27 @@
 //! supporting both Harvard and Linear architectures (unified memory from Harvard architecture
 //! with a single memory space, with added read and write protection), and offering detailed
 //! visibility into the emulator's state and execution results.
+mod bitmanip;
+#[cfg(feature = "gdb")]
+mod debug;
 mod executor;
+mod float;
+mod input;
 mod layout;
+mod limits;
 pub(crate) mod memory_stats;
+mod profile;
 mod registry;
 
-pub use executor::{Emulator, Executor, HarvardEmulator, LinearEmulator};
+#[cfg(feature = "gdb")]
+pub use debug::debug_with_hooks;
+pub use executor::{Emulator, Executor, HarvardEmulator, LinearEmulator, WatchHit};
+pub use float::FloatPolicy;
+pub use input::{FileInputProvider, InputProvider};
 pub use layout::LinearMemoryLayout;
+pub use limits::{ExecutionLimits, LimitUsage};
+pub use memory_stats::MemoryUsage;
+pub use profile::{CallEdge, FunctionProfile, Profile};
 
 mod utils;
 pub use utils::*;
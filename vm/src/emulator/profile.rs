@@ -0,0 +1,199 @@
+//! Cycle Profiling of Guest Programs
+//!
+//! This module attributes the cycles of an execution to the functions of the guest program, using the
+//! function symbols of its ELF file.
+//!
+//! # Key Components
+//!
+//! - `Profile`: The cycles spent in each function, and the direct calls between functions.
+//! - `FunctionProfile`: The cycles spent in, and the calls made to, a single function.
+//! - `CallEdge`: The number of direct calls from one function to another.
+//!
+//! # Attribution
+//!
+//! Every executed instruction is attributed to the function symbol containing it, that is the last symbol
+//! starting at or before it and, for symbols of known size, ending after it. Instructions outside of any symbol,
+//! including all of those of a stripped binary, are counted as unattributed.
+//!
+//! Calls are recovered from executed `jal ra, ...` instructions, which is how direct calls are emitted for
+//! RISC-V. Indirect calls through `jalr` have no static target and are not recorded as call edges, though the
+//! cycles of the callee are attributed as usual.
+
+use std::collections::BTreeMap;
+
+use super::View;
+use crate::elf::FunctionSymbol;
+
+/// The opcode of `jal`.
+const JAL_OPCODE: u32 = 0b110_1111;
+/// The return address register, `x1`.
+const RA: u32 = 1;
+
+/// The cycles spent in a single function of the guest program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionProfile {
+    /// The name of the function, as given by its symbol.
+    pub name: String,
+    /// The number of instructions executed within the function, excluding its callees.
+    pub cycles: u64,
+    /// The number of direct calls made to the function.
+    pub calls: u64,
+}
+
+/// The number of direct calls from one function to another.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallEdge {
+    /// The name of the calling function, if the call site is within a function symbol.
+    pub caller: Option<String>,
+    /// The name of the called function.
+    pub callee: String,
+    /// The number of calls executed.
+    pub count: u64,
+}
+
+/// The cycles of an execution attributed to the functions of the guest program, see [`View::profile`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Profile {
+    /// The executed functions, in descending order of cycles.
+    pub functions: Vec<FunctionProfile>,
+    /// The number of instructions executed outside of any function symbol.
+    pub unattributed_cycles: u64,
+    /// The direct calls executed, in descending order of count.
+    pub calls: Vec<CallEdge>,
+}
+
+impl Profile {
+    /// The total number of cycles of the execution.
+    pub fn total_cycles(&self) -> u64 {
+        self.functions.iter().map(|f| f.cycles).sum::<u64>() + self.unattributed_cycles
+    }
+}
+
+/// Find the function symbol containing `pc`, with `symbols` sorted by address.
+fn containing_symbol(symbols: &[FunctionSymbol], pc: u32) -> Option<&FunctionSymbol> {
+    let index = symbols.partition_point(|symbol| symbol.address <= pc);
+    let symbol = symbols.get(index.checked_sub(1)?)?;
+
+    let end = if symbol.size > 0 {
+        symbol.address.saturating_add(symbol.size)
+    } else {
+        symbols.get(index).map_or(u32::MAX, |next| next.address)
+    };
+
+    (pc < end).then_some(symbol)
+}
+
+/// Return the target of `instruction_word` at `pc` if it is a direct call, i.e., `jal ra, offset`.
+fn call_target(pc: u32, instruction_word: u32) -> Option<u32> {
+    if instruction_word & 0x7f != JAL_OPCODE || (instruction_word >> 7) & 0x1f != RA {
+        return None;
+    }
+
+    // imm[20|10:1|11|19:12], sign-extended from bit 20
+    let imm = ((instruction_word >> 31) << 20)
+        | (((instruction_word >> 21) & 0x3ff) << 1)
+        | (((instruction_word >> 20) & 0x1) << 11)
+        | (((instruction_word >> 12) & 0xff) << 12);
+    let offset = ((imm << 11) as i32) >> 11;
+
+    Some(pc.wrapping_add(offset as u32))
+}
+
+impl View {
+    /// Attribute the cycles of the execution to the functions of the guest program.
+    ///
+    /// Attribution uses the function symbols of the ELF file, so a stripped binary or a view not produced by an
+    /// emulator run yields a profile with all cycles unattributed.
+    pub fn profile(&self) -> Profile {
+        let symbols = &self.symbols;
+
+        let mut cycles: BTreeMap<&str, u64> = BTreeMap::new();
+        let mut calls: BTreeMap<(Option<&str>, &str), u64> = BTreeMap::new();
+        let mut unattributed_cycles = 0;
+
+        for (entry, &count) in self
+            .program_memory
+            .program
+            .iter()
+            .zip(self.instruction_counts.iter())
+            .filter(|(_, &count)| count > 0)
+        {
+            let caller = containing_symbol(symbols, entry.pc);
+            match caller {
+                Some(symbol) => *cycles.entry(&symbol.name).or_default() += count,
+                None => unattributed_cycles += count,
+            }
+
+            let callee = call_target(entry.pc, entry.instruction_word)
+                .and_then(|target| symbols.iter().find(|symbol| symbol.address == target));
+            if let Some(callee) = callee {
+                *calls
+                    .entry((caller.map(|s| s.name.as_str()), &callee.name))
+                    .or_default() += count;
+            }
+        }
+
+        let mut functions: Vec<FunctionProfile> = cycles
+            .into_iter()
+            .map(|(name, cycles)| FunctionProfile {
+                name: name.to_string(),
+                cycles,
+                calls: calls
+                    .iter()
+                    .filter(|((_, callee), _)| *callee == name)
+                    .map(|(_, count)| count)
+                    .sum(),
+            })
+            .collect();
+        functions.sort_by(|a, b| b.cycles.cmp(&a.cycles));
+
+        let mut calls: Vec<CallEdge> = calls
+            .into_iter()
+            .map(|((caller, callee), count)| CallEdge {
+                caller: caller.map(str::to_string),
+                callee: callee.to_string(),
+                count,
+            })
+            .collect();
+        calls.sort_by(|a, b| b.count.cmp(&a.count));
+
+        Profile {
+            functions,
+            unattributed_cycles,
+            calls,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbol(name: &str, address: u32, size: u32) -> FunctionSymbol {
+        FunctionSymbol {
+            name: name.to_string(),
+            address,
+            size,
+        }
+    }
+
+    #[test]
+    fn test_containing_symbol() {
+        let symbols = [symbol("a", 0x100, 0x8), symbol("b", 0x200, 0)];
+
+        assert_eq!(containing_symbol(&symbols, 0xfc), None);
+        assert_eq!(containing_symbol(&symbols, 0x104).unwrap().name, "a");
+        assert_eq!(containing_symbol(&symbols, 0x108), None);
+        assert_eq!(containing_symbol(&symbols, 0x300).unwrap().name, "b");
+    }
+
+    #[test]
+    fn test_call_target() {
+        // jal ra, 16
+        assert_eq!(call_target(0x100, 0x010000ef), Some(0x110));
+        // jal ra, -8
+        assert_eq!(call_target(0x100, 0xff9ff0ef), Some(0xf8));
+        // jal zero, 16
+        assert_eq!(call_target(0x100, 0x0100006f), None);
+    }
+}
@@ -1,14 +1,18 @@
-use crate::elf::ElfFile;
+use crate::elf::{ElfFile, FunctionSymbol};
 use crate::memory::MemorySegmentImage;
 use crate::riscv::{decode_instruction, BasicBlock};
+use crate::system::HostCall;
 
 pub use super::executor::Emulator;
 pub use super::layout::LinearMemoryLayout;
 use super::registry;
+use super::{LimitUsage, MemoryUsage};
 
 use nexus_common::constants::WORD_SIZE;
 use nexus_common::memory::MemoryRecords;
 use nexus_common::riscv::{opcode::BuiltinOpcode, Opcode};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 pub type MemoryTranscript = Vec<MemoryRecords>;
 
@@ -219,10 +223,178 @@ pub trait InternalView {
     fn add_logs(&mut self, emulator: &impl Emulator);
 }
 
+/// Every host-provided input consumed by an execution, sufficient to deterministically replay it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputTranscript {
+    /// The raw (encoded) public input.
+    pub public_input: Vec<u8>,
+    /// The prefix of the private input tape read by the guest.
+    pub private_input: Vec<u8>,
+    /// The associated data bound into the execution.
+    pub associated_data: Vec<u8>,
+}
+
+/// The severity of a [`LogRecord`], ordered from most to least severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum LogLevel {
+    Error = 1,
+    Warn = 2,
+    Info = 3,
+    Debug = 4,
+    Trace = 5,
+}
+
+impl TryFrom<u32> for LogLevel {
+    type Error = u32;
+
+    fn try_from(level: u32) -> std::result::Result<Self, u32> {
+        match level {
+            1 => Ok(Self::Error),
+            2 => Ok(Self::Warn),
+            3 => Ok(Self::Info),
+            4 => Ok(Self::Debug),
+            5 => Ok(Self::Trace),
+            _ => Err(level),
+        }
+    }
+}
+
+impl std::fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Error => "ERROR",
+            Self::Warn => "WARN",
+            Self::Info => "INFO",
+            Self::Debug => "DEBUG",
+            Self::Trace => "TRACE",
+        })
+    }
+}
+
+/// A structured log record written by the guest program through `nexus_rt::log!`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogRecord {
+    /// The severity of the record.
+    pub level: LogLevel,
+    /// The global clock of the emulator when the record was written.
+    pub cycle: usize,
+    /// The message of the record.
+    pub message: String,
+}
+
+/// A coarse category of retired instructions, for cost accounting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InstrClass {
+    /// Integer arithmetic, logic, shifts and comparisons, including `lui` and `auipc`.
+    Arithmetic,
+    /// Loads and stores.
+    Memory,
+    /// Conditional branches and jumps.
+    Branch,
+    /// Multiplication, division and remainder (the M extension).
+    MulDiv,
+    /// System and custom instructions, such as `ecall` and precompiles.
+    System,
+}
+
+/// A way a guest aborts, see [`HaltReason`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TrapKind {
+    /// The guest panicked.
+    Panic,
+    /// An assertion made with `nexus_rt::nexus_assert` failed.
+    AssertionFailed,
+}
+
+/// Why the guest halted, as attested to by its committed exit code.
+///
+/// Every halt goes through the exit syscall, so the reason is told apart by the exit codes the runtime reserves
+/// for aborting: a guest calling `exit` itself with one of them is reported as trapping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HaltReason {
+    /// The guest returned from `main` (with code 0) or called `exit` with the given code.
+    Exit(u32),
+    /// The guest aborted.
+    Trap(TrapKind),
+}
+
+impl HaltReason {
+    // The exit codes written by the runtime when aborting.
+    const EXIT_PANIC: u32 = 1;
+    const EXIT_ASSERTION_FAILED: u32 = 2;
+
+    /// Returns the halt reason of the given exit code.
+    pub fn from_exit_code(exit_code: u32) -> Self {
+        match exit_code {
+            Self::EXIT_PANIC => Self::Trap(TrapKind::Panic),
+            Self::EXIT_ASSERTION_FAILED => Self::Trap(TrapKind::AssertionFailed),
+            code => Self::Exit(code),
+        }
+    }
+
+    /// Returns the exit code the guest halts with for this reason.
+    pub fn exit_code(&self) -> u32 {
+        match self {
+            Self::Exit(code) => *code,
+            Self::Trap(TrapKind::Panic) => Self::EXIT_PANIC,
+            Self::Trap(TrapKind::AssertionFailed) => Self::EXIT_ASSERTION_FAILED,
+        }
+    }
+
+    /// Returns whether the guest halted cleanly with exit code 0.
+    pub fn is_clean_exit(&self) -> bool {
+        *self == Self::Exit(0)
+    }
+}
+
+/// Statistics of the guest's heap allocations.
+///
+/// The runtime's allocator never releases memory, so every allocation stays live until the
+/// execution halts and `frees` is always zero for guests using it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AllocStats {
+    /// The number of allocations made.
+    pub allocations: u64,
+    /// The number of allocations released.
+    pub frees: u64,
+    /// The largest number of bytes allocated at any point.
+    pub peak_bytes: u64,
+    /// The number of bytes still allocated when the execution halted.
+    pub bytes_leaked: u64,
+}
+
+impl AllocStats {
+    /// Record an allocation of `bytes` bytes.
+    pub(crate) fn record_allocation(&mut self, bytes: u32) {
+        self.allocations += 1;
+        self.bytes_leaked += u64::from(bytes);
+        self.peak_bytes = self.peak_bytes.max(self.bytes_leaked);
+    }
+}
+
+impl From<&Opcode> for InstrClass {
+    fn from(opcode: &Opcode) -> Self {
+        use BuiltinOpcode::*;
+
+        match opcode.builtin() {
+            Some(
+                ADD | SUB | SLL | SLT | SLTU | XOR | SRL | SRA | OR | AND | ADDI | SLLI | SLTI
+                | SLTIU | XORI | SRLI | SRAI | ORI | ANDI | LUI | AUIPC,
+            ) => Self::Arithmetic,
+            Some(LB | LH | LW | LBU | LHU | SB | SH | SW) => Self::Memory,
+            Some(BEQ | BNE | BLT | BGE | BLTU | BGEU | JAL | JALR) => Self::Branch,
+            Some(MUL | MULH | MULHSU | MULHU | DIV | DIVU | REM | REMU) => Self::MulDiv,
+            Some(ECALL | EBREAK | FENCE | UNIMPL) | None => Self::System,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct View {
     pub(crate) memory_layout: Option<LinearMemoryLayout>,
     pub(crate) debug_logs: Vec<Vec<u8>>,
+    pub(crate) log_records: Vec<LogRecord>,
+    pub(crate) host_calls: Vec<HostCall>,
     pub(crate) program_memory: ProgramInfo,
     // When not available, initial memories can be empty
     pub(crate) ro_initial_memory: Vec<MemoryInitializationEntry>,
@@ -233,6 +405,17 @@ pub struct View {
     pub(crate) exit_code: Vec<PublicOutputEntry>,
     pub(crate) output_memory: Vec<PublicOutputEntry>,
     pub(crate) associated_data: Vec<u8>,
+    /// Context the proof of the execution is bound to alongside the associated data, not visible to the guest.
+    pub(crate) statement_context: Vec<u8>,
+    pub(crate) input_transcript: Option<InputTranscript>,
+    pub(crate) instruction_mix: HashMap<InstrClass, u64>,
+    /// The number of times each entry of the program memory was executed.
+    pub(crate) instruction_counts: Vec<u64>,
+    pub(crate) symbols: Vec<FunctionSymbol>,
+    pub(crate) final_registers: [u32; 32],
+    pub(crate) alloc_stats: AllocStats,
+    pub(crate) limit_usage: LimitUsage,
+    pub(crate) memory_stats: MemoryUsage,
 }
 
 impl View {
@@ -253,6 +436,8 @@ impl View {
         Self {
             memory_layout: memory_layout.to_owned(),
             debug_logs: debug_logs.to_owned(),
+            log_records: Vec::new(),
+            host_calls: Vec::new(),
             program_memory: program_memory.to_owned(),
             ro_initial_memory: ro_initial_memory.to_owned(),
             rw_initial_memory: rw_initial_memory.to_owned(),
@@ -261,6 +446,15 @@ impl View {
             exit_code: exit_code.to_owned(),
             output_memory: output_memory.to_owned(),
             associated_data: associated_data.to_owned(),
+            statement_context: Vec::new(),
+            input_transcript: None,
+            instruction_mix: HashMap::new(),
+            instruction_counts: Vec::new(),
+            symbols: Vec::new(),
+            final_registers: [0; 32],
+            alloc_stats: AllocStats::default(),
+            limit_usage: LimitUsage::default(),
+            memory_stats: MemoryUsage::default(),
         }
     }
 
@@ -309,11 +503,112 @@ impl View {
         }
     }
 
+    /// Return the context the proof of the execution is bound to, see [`View::set_statement_context`].
+    pub fn view_statement_context(&self) -> &[u8] {
+        &self.statement_context
+    }
+
+    /// Bind proofs of the execution to `context`, such as a digest of the parameters they are made under, so that
+    /// they only verify against views with the same context.
+    ///
+    /// Unlike the associated data, the context is not placed in memory and has no effect on the execution.
+    pub fn set_statement_context(&mut self, context: Vec<u8>) {
+        self.statement_context = context;
+    }
+
     /// Retrieve the raw debug logs, if any.
     pub fn view_debug_logs(&self) -> Option<Vec<Vec<u8>>> {
         Some(self.debug_logs.clone())
     }
 
+    /// Retrieve the structured log records at `max_level` or more severe, in the order they were written.
+    ///
+    /// Records are also included in the raw debug logs, prefixed by their level.
+    pub fn view_log_records(&self, max_level: LogLevel) -> Vec<LogRecord> {
+        self.log_records
+            .iter()
+            .filter(|record| record.level <= max_level)
+            .cloned()
+            .collect()
+    }
+
+    /// Return the calls made by the guest to host functions, in order.
+    ///
+    /// The results of host functions are advice to the guest, and are not covered by the proof.
+    pub fn host_calls(&self) -> &[HostCall] {
+        &self.host_calls
+    }
+
+    /// Return the transcript of inputs consumed by the execution, if recorded.
+    ///
+    /// The transcript is only available to the prover, as it contains the private input.
+    pub fn view_input_transcript(&self) -> Option<InputTranscript> {
+        self.input_transcript.clone()
+    }
+
+    /// Return the number of retired instructions in each [`InstrClass`].
+    ///
+    /// This is accounting of the emulated execution only, and is not covered by the proof.
+    pub fn instruction_mix(&self) -> HashMap<InstrClass, u64> {
+        self.instruction_mix.clone()
+    }
+
+    /// Return the values of the registers `x0` to `x31` when the execution halted.
+    ///
+    /// The registers are not bound by the proof: a guest must place any value that the verifier
+    /// relies on in the public output.
+    pub fn final_registers(&self) -> [u32; 32] {
+        self.final_registers
+    }
+
+    /// Return why the guest halted, decoded from the exit code.
+    ///
+    /// The exit code is part of the public output, so the halt reason is covered by the proof. A view of an
+    /// execution that has not halted reports an exit code of 0.
+    pub fn halt_reason(&self) -> HaltReason {
+        let mut exit_code = [0u8; WORD_SIZE];
+        for (byte, entry) in exit_code.iter_mut().zip(&self.exit_code) {
+            *byte = entry.value;
+        }
+        HaltReason::from_exit_code(u32::from_le_bytes(exit_code))
+    }
+
+    /// Return the statistics of the guest's heap allocations.
+    ///
+    /// This is accounting of the emulated execution only, and is not covered by the proof.
+    pub fn alloc_stats(&self) -> AllocStats {
+        self.alloc_stats
+    }
+
+    /// Return how much of each limited resource the execution used, against the configured limits.
+    ///
+    /// Limits are enforced by the first pass, so this reports the usage it measured.
+    pub fn limit_usage(&self) -> LimitUsage {
+        self.limit_usage
+    }
+
+    /// Record the usage of limited resources measured by the first pass.
+    pub(crate) fn set_limit_usage(&mut self, limit_usage: LimitUsage) {
+        self.limit_usage = limit_usage;
+    }
+
+    /// Return the peak heap and stack usage of the execution, and the number of pages of memory it touched.
+    ///
+    /// This is measured by the first pass, and is not covered by the proof.
+    pub fn memory_stats(&self) -> MemoryUsage {
+        self.memory_stats
+    }
+
+    /// Record the memory usage measured by the first pass.
+    pub(crate) fn set_memory_stats(&mut self, memory_stats: MemoryUsage) {
+        self.memory_stats = memory_stats;
+    }
+
+    /// Record the transcript of inputs consumed by the execution.
+    pub(crate) fn set_input_transcript(&mut self, input_transcript: InputTranscript) {
+        self.input_transcript = Some(input_transcript);
+    }
+
     /// Return the memory layout, if any.
     // TODO: Remove once we split Supply-Side and Demand-Side Interfaces
     pub fn view_memory_layout(&self) -> Option<&LinearMemoryLayout> {
@@ -437,6 +732,7 @@ impl InternalView for View {
     fn add_logs(&mut self, emulator: &impl Emulator) {
         if let Some(logs) = &emulator.get_executor().logs {
             self.debug_logs = logs.to_vec();
+            self.log_records = emulator.get_executor().log_records.clone();
         }
     }
 }
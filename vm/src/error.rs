@@ -90,9 +90,9 @@ pub enum VMErrorKind {
     #[error("Unimplemented instruction \"{0}\" at pc=0x{1:08X}")]
     UnimplementedInstructionAt(Opcode, u32),
 
-    // Unsupported instruction (i.e., one with an invalid opcode)
-    #[error("Unsupported instruction \"{0}\"")]
-    UnsupportedInstruction(Opcode),
+    // Unsupported instruction encountered during execution, with the raw word found at `pc`
+    #[error("Unsupported instruction 0x{raw:08X} (decoded as \"{decoded}\") at pc=0x{pc:08X}")]
+    UnsupportedInstruction { pc: u32, raw: u32, decoded: Opcode },
 
     #[error("Integer overflow")]
     IntOverflowError(#[from] TryFromIntError),
@@ -104,6 +104,46 @@ pub enum VMErrorKind {
     // Merging non-contiguous memory segments
     #[error("Non-contiguous memory")]
     NonContiguousMemory,
+
+    // Execution exceeded the configured cycle limit
+    #[error("Cycle limit of {limit} exceeded after {cycles} cycles")]
+    CycleLimitExceeded { cycles: usize, limit: usize },
+
+    // Execution exceeded the configured memory limit
+    #[error("Memory limit of {limit} bytes exceeded by using {bytes} bytes")]
+    MemoryLimitExceeded { bytes: u32, limit: u32 },
+
+    // Syscall not permitted by the configured limits
+    #[error("Syscall not permitted: opcode={0:08X}, pc=0x{1:08X}")]
+    SyscallNotAllowed(u32, u32),
+
+    // Guest panicked on an overflow check, reported at the pc of the exit syscall ending the panic
+    #[error("Arithmetic overflow: \"{message}\", exited at pc=0x{pc:08X}")]
+    ArithmeticOverflow { pc: u32, message: String },
+
+    // Floating-point instruction rejected by the float policy, or not supported by the soft-float implementation
+    #[error("Unsupported floating-point instruction at pc=0x{pc:08X}")]
+    UnsupportedFloat { pc: u32 },
+
+    // Heap allocation reaching past the current stack pointer
+    #[error("Heap collides with stack: heap end=0x{heap_end:08X}, sp=0x{sp:08X}, pc=0x{pc:08X}")]
+    HeapStackCollision { heap_end: u32, sp: u32, pc: u32 },
+
+    // Load from memory never written nor statically initialized, when trapping on such reads
+    #[error("Read of uninitialized memory at address 0x{addr:08X}, pc=0x{pc:08X}")]
+    UninitializedRead { addr: u32, pc: u32 },
+
+    // Host function registered for a syscall code outside of the reserved range
+    #[error("Syscall code {0:08X} is not reserved for host functions")]
+    InvalidHostCallCode(u32),
+
+    // Input provider failing to provide the next chunk of the private input tape
+    #[error("Failed to provide private input: {0}")]
+    PrivateInputError(String),
+
+    // Debugger connection failing, or violating the GDB remote serial protocol
+    #[error("Debugger error: {0}")]
+    DebuggerError(String),
 }
 
 /// Result type for VM functions that can produce errors.
@@ -0,0 +1,333 @@
+//! Export of execution traces for external analysis tools.
+//!
+//! A [`TraceExport`] holds the per-step record of a [`Trace`] as columns, and is written in a compact binary format,
+//! so that auditors and tooling authors can cross-check prover columns or build coverage and profiling tools without
+//! depending on this crate.
+//!
+//! # Format
+//!
+//! All integers are little-endian. The file starts with a header:
+//!
+//! | Field          | Type      | Description                        |
+//! |----------------|-----------|------------------------------------|
+//! | magic          | `[u8; 8]` | `b"NXTRACE\0"`                     |
+//! | version        | `u32`     | [`TraceExport::VERSION`]           |
+//! | `num_steps`    | `u32`     | number of steps                    |
+//! | `num_accesses` | `u32`     | number of memory accesses          |
+//!
+//! followed by the step columns, each holding `num_steps` values in execution order:
+//!
+//! | Column            | Type  | Description                                                                   |
+//! |-------------------|-------|-------------------------------------------------------------------------------|
+//! | `timestamp`       | `u32` | timestamp of the step                                                         |
+//! | `pc`              | `u32` | program counter of the instruction                                            |
+//! | `next_pc`         | `u32` | program counter after the instruction                                         |
+//! | `raw_instruction` | `u32` | encoded instruction                                                           |
+//! | `opcode`          | `u8`  | 7-bit opcode field of the instruction                                         |
+//! | `op_a`            | `u8`  | register operand `a`, the destination register of instructions writing one   |
+//! | `op_b`            | `u8`  | register operand `b`                                                          |
+//! | `op_c`            | `u32` | operand `c`, an immediate or, for R-type instructions, a register             |
+//! | `value_a`         | `u32` | value of register `op_a` before the step                                      |
+//! | `value_b`         | `u32` | value of register `op_b` before the step                                      |
+//! | `value_c`         | `u32` | value of register `op_c` before the step for R-type instructions, else `op_c` |
+//! | `has_result`      | `u8`  | 1 if the step wrote `result` to register `op_a`, else 0                       |
+//! | `result`          | `u32` | value written to register `op_a`, or 0                                        |
+//!
+//! and the memory access columns, each holding `num_accesses` values ordered by step, then address:
+//!
+//! | Column       | Type  | Description                                       |
+//! |--------------|-------|---------------------------------------------------|
+//! | `step`       | `u32` | index of the accessing step                       |
+//! | `is_store`   | `u8`  | 1 for a store, 0 for a load                       |
+//! | `size`       | `u8`  | number of bytes accessed                          |
+//! | `address`    | `u32` | address accessed                                  |
+//! | `value`      | `u32` | value loaded or stored                            |
+//! | `prev_value` | `u32` | value overwritten by a store, or 0 for a load     |
+//! | `timestamp`  | `u32` | timestamp of the access                           |
+
+use std::io::{self, Read, Write};
+
+use nexus_common::{
+    cpu::Registers,
+    memory::{MemAccessSize, MemoryRecord},
+};
+
+use crate::{
+    riscv::{InstructionType, Register},
+    trace::Trace,
+};
+
+/// The per-step columns of a [`TraceExport`], see the [module documentation](self) for their meaning.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StepColumns {
+    pub timestamp: Vec<u32>,
+    pub pc: Vec<u32>,
+    pub next_pc: Vec<u32>,
+    pub raw_instruction: Vec<u32>,
+    pub opcode: Vec<u8>,
+    pub op_a: Vec<u8>,
+    pub op_b: Vec<u8>,
+    pub op_c: Vec<u32>,
+    pub value_a: Vec<u32>,
+    pub value_b: Vec<u32>,
+    pub value_c: Vec<u32>,
+    pub has_result: Vec<u8>,
+    pub result: Vec<u32>,
+}
+
+/// The memory access columns of a [`TraceExport`], see the [module documentation](self) for their meaning.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct AccessColumns {
+    pub step: Vec<u32>,
+    pub is_store: Vec<u8>,
+    pub size: Vec<u8>,
+    pub address: Vec<u32>,
+    pub value: Vec<u32>,
+    pub prev_value: Vec<u32>,
+    pub timestamp: Vec<u32>,
+}
+
+/// The per-step record of an execution trace, as columns.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TraceExport {
+    pub steps: StepColumns,
+    pub accesses: AccessColumns,
+}
+
+impl TraceExport {
+    /// The magic bytes the format starts with.
+    pub const MAGIC: [u8; 8] = *b"NXTRACE\0";
+    /// The version of the format, bumped on every incompatible change.
+    pub const VERSION: u32 = 1;
+
+    /// Collect the columns of `trace`, tracking register values from the starting register file of each block.
+    pub fn from_trace(trace: &impl Trace) -> Self {
+        let mut export = Self::default();
+
+        for block in trace.get_blocks_iter() {
+            let mut regs = block.regs;
+            for step in &block.steps {
+                let instruction = &step.instruction;
+                let value_c = if instruction.ins_type == InstructionType::RType {
+                    regs.read(Register::from(instruction.op_c as u8))
+                } else {
+                    instruction.op_c
+                };
+
+                let steps = &mut export.steps;
+                let step_idx = steps.pc.len() as u32;
+                steps.timestamp.push(step.timestamp);
+                steps.pc.push(step.pc);
+                steps.next_pc.push(step.next_pc);
+                steps.raw_instruction.push(step.raw_instruction);
+                steps.opcode.push((step.raw_instruction & 0x7f) as u8);
+                steps.op_a.push(instruction.op_a as u8);
+                steps.op_b.push(instruction.op_b as u8);
+                steps.op_c.push(instruction.op_c);
+                steps.value_a.push(regs.read(instruction.op_a));
+                steps.value_b.push(regs.read(instruction.op_b));
+                steps.value_c.push(value_c);
+                steps.has_result.push(step.result.is_some() as u8);
+                steps.result.push(step.result.unwrap_or_default());
+
+                if let Some(result) = step.result {
+                    regs.write(instruction.op_a, result);
+                }
+
+                let mut records: Vec<&MemoryRecord> = step.memory_records.iter().collect();
+                records.sort_by_key(|record| match record {
+                    MemoryRecord::LoadRecord((_, address, _), _) => (*address, false),
+                    MemoryRecord::StoreRecord((_, address, _, _), _) => (*address, true),
+                });
+                let accesses = &mut export.accesses;
+                for record in records {
+                    let (is_store, size, address, value, prev_value, timestamp) = match *record {
+                        MemoryRecord::LoadRecord((size, address, value), timestamp) => {
+                            (0, size, address, value, 0, timestamp)
+                        }
+                        MemoryRecord::StoreRecord(
+                            (size, address, value, prev_value),
+                            timestamp,
+                        ) => (1, size, address, value, prev_value, timestamp),
+                    };
+                    accesses.step.push(step_idx);
+                    accesses.is_store.push(is_store);
+                    accesses.size.push(access_size(size));
+                    accesses.address.push(address);
+                    accesses.value.push(value);
+                    accesses.prev_value.push(prev_value);
+                    accesses.timestamp.push(timestamp);
+                }
+            }
+        }
+
+        export
+    }
+
+    /// Write the export in the format described in the [module documentation](self).
+    pub fn write_to(&self, mut writer: impl Write) -> io::Result<()> {
+        let Self { steps, accesses } = self;
+
+        writer.write_all(&Self::MAGIC)?;
+        writer.write_all(&Self::VERSION.to_le_bytes())?;
+        writer.write_all(&(steps.pc.len() as u32).to_le_bytes())?;
+        writer.write_all(&(accesses.step.len() as u32).to_le_bytes())?;
+
+        for column in [
+            &steps.timestamp,
+            &steps.pc,
+            &steps.next_pc,
+            &steps.raw_instruction,
+        ] {
+            write_u32s(&mut writer, column)?;
+        }
+        writer.write_all(&steps.opcode)?;
+        writer.write_all(&steps.op_a)?;
+        writer.write_all(&steps.op_b)?;
+        for column in [&steps.op_c, &steps.value_a, &steps.value_b, &steps.value_c] {
+            write_u32s(&mut writer, column)?;
+        }
+        writer.write_all(&steps.has_result)?;
+        write_u32s(&mut writer, &steps.result)?;
+
+        write_u32s(&mut writer, &accesses.step)?;
+        writer.write_all(&accesses.is_store)?;
+        writer.write_all(&accesses.size)?;
+        for column in [
+            &accesses.address,
+            &accesses.value,
+            &accesses.prev_value,
+            &accesses.timestamp,
+        ] {
+            write_u32s(&mut writer, column)?;
+        }
+
+        Ok(())
+    }
+
+    /// Read an export written by [`Self::write_to`].
+    pub fn read_from(mut reader: impl Read) -> io::Result<Self> {
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if magic != Self::MAGIC {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "not a trace export",
+            ));
+        }
+        let version = read_u32(&mut reader)?;
+        if version != Self::VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unsupported trace export version {version}"),
+            ));
+        }
+        let num_steps = read_u32(&mut reader)? as usize;
+        let num_accesses = read_u32(&mut reader)? as usize;
+
+        let reader = &mut reader;
+        let steps = StepColumns {
+            timestamp: read_u32s(reader, num_steps)?,
+            pc: read_u32s(reader, num_steps)?,
+            next_pc: read_u32s(reader, num_steps)?,
+            raw_instruction: read_u32s(reader, num_steps)?,
+            opcode: read_u8s(reader, num_steps)?,
+            op_a: read_u8s(reader, num_steps)?,
+            op_b: read_u8s(reader, num_steps)?,
+            op_c: read_u32s(reader, num_steps)?,
+            value_a: read_u32s(reader, num_steps)?,
+            value_b: read_u32s(reader, num_steps)?,
+            value_c: read_u32s(reader, num_steps)?,
+            has_result: read_u8s(reader, num_steps)?,
+            result: read_u32s(reader, num_steps)?,
+        };
+        let accesses = AccessColumns {
+            step: read_u32s(reader, num_accesses)?,
+            is_store: read_u8s(reader, num_accesses)?,
+            size: read_u8s(reader, num_accesses)?,
+            address: read_u32s(reader, num_accesses)?,
+            value: read_u32s(reader, num_accesses)?,
+            prev_value: read_u32s(reader, num_accesses)?,
+            timestamp: read_u32s(reader, num_accesses)?,
+        };
+
+        Ok(Self { steps, accesses })
+    }
+}
+
+/// Write the per-step record of `trace` to `writer`, see [`TraceExport`].
+pub fn export_trace(trace: &impl Trace, writer: impl Write) -> io::Result<()> {
+    TraceExport::from_trace(trace).write_to(writer)
+}
+
+fn access_size(size: MemAccessSize) -> u8 {
+    match size {
+        MemAccessSize::Byte => 1,
+        MemAccessSize::HalfWord => 2,
+        MemAccessSize::Word => 4,
+    }
+}
+
+fn write_u32s(writer: &mut impl Write, column: &[u32]) -> io::Result<()> {
+    let bytes: Vec<u8> = column
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .collect();
+    writer.write_all(&bytes)
+}
+
+fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut bytes = [0u8; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u32s(reader: &mut impl Read, len: usize) -> io::Result<Vec<u32>> {
+    let bytes = read_u8s(reader, len * 4)?;
+    Ok(bytes
+        .chunks_exact(4)
+        .map(|chunk| u32::from_le_bytes(chunk.try_into().unwrap()))
+        .collect())
+}
+
+fn read_u8s(reader: &mut impl Read, len: usize) -> io::Result<Vec<u8>> {
+    let mut bytes = vec![0u8; len];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        riscv::{BasicBlock, BuiltinOpcode, Instruction, Opcode},
+        trace::k_trace_direct,
+    };
+
+    #[test]
+    fn test_export_round_trip() {
+        let basic_blocks = vec![BasicBlock::new(vec![
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 1, 0, 0x400),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 2, 0, 7),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SW), 1, 2, 0),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::LW), 3, 1, 0),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADD), 4, 3, 2),
+        ])];
+        let (_, trace) = k_trace_direct(&basic_blocks, 1).expect("error generating trace");
+
+        let export = TraceExport::from_trace(&trace);
+        assert_eq!(export.steps.pc.len(), 5);
+        // add x4, x3, x2 reads 7 from both registers
+        assert_eq!(export.steps.value_b[4], 7);
+        assert_eq!(export.steps.value_c[4], 7);
+        assert_eq!(export.steps.result[4], 14);
+        assert_eq!(export.accesses.step, vec![2, 3]);
+        assert_eq!(export.accesses.is_store, vec![1, 0]);
+        assert_eq!(export.accesses.address, vec![0x400, 0x400]);
+        assert_eq!(export.accesses.value, vec![7, 7]);
+
+        let mut bytes = Vec::new();
+        export.write_to(&mut bytes).unwrap();
+        assert_eq!(TraceExport::read_from(bytes.as_slice()).unwrap(), export);
+    }
+}
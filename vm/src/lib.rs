@@ -2,6 +2,7 @@ pub mod cpu;
 pub mod elf;
 pub mod emulator;
 pub mod error;
+pub mod export;
 pub mod memory;
 pub mod riscv;
 pub mod system;
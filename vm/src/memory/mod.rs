@@ -1,6 +1,6 @@
 mod fixed;
 mod memory_image;
-mod page;
+pub(crate) mod page;
 mod paged_memory;
 mod unified;
 mod variable;
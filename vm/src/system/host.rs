@@ -0,0 +1,77 @@
+//! Host functions callable by the guest through reserved syscall codes.
+//!
+//! A host function answers an `ecall` made with a syscall code in [`HOST_CALL_CODES`], reading its arguments from
+//! registers `a0` through `a6` and returning its result in `a0`, so that oracles such as price feeds or storage reads
+//! can be provided to the guest without extending the emulator.
+//!
+//! The answers of host functions are non-deterministic advice to the guest: every call is recorded as a [`HostCall`]
+//! by the first-pass (Harvard) emulator, and the second-pass emulator replays the recorded results rather than
+//! calling the host function again.
+
+use std::{collections::HashMap, ops::RangeInclusive, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, VMErrorKind};
+
+/// The syscall codes reserved for host functions.
+pub const HOST_CALL_CODES: RangeInclusive<u32> = 0x1000..=0x1FFF;
+
+/// A function of the host, answering calls made by the guest.
+pub trait HostFunction: Send + Sync {
+    /// Answer a call with arguments `a0` through `a6`, returning the result placed in `a0`.
+    fn call(&self, args: &[u32]) -> u32;
+}
+
+impl<F: Fn(&[u32]) -> u32 + Send + Sync> HostFunction for F {
+    fn call(&self, args: &[u32]) -> u32 {
+        self(args)
+    }
+}
+
+/// A call made by the guest to a host function.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HostCall {
+    /// The syscall code of the host function.
+    pub code: u32,
+    /// The arguments of the call, from `a0` through `a6`.
+    pub args: Vec<u32>,
+    /// The result of the call.
+    pub result: u32,
+}
+
+/// The host functions registered with an emulator, by syscall code.
+#[derive(Clone, Default)]
+pub struct HostFunctions {
+    functions: HashMap<u32, Arc<dyn HostFunction>>,
+}
+
+impl std::fmt::Debug for HostFunctions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.functions.keys()).finish()
+    }
+}
+
+impl HostFunctions {
+    /// Register `function` to answer calls with the syscall code `code`, which must be in [`HOST_CALL_CODES`].
+    pub fn register(&mut self, code: u32, function: impl HostFunction + 'static) -> Result<()> {
+        if !HOST_CALL_CODES.contains(&code) {
+            Err(VMErrorKind::InvalidHostCallCode(code))?
+        }
+        self.functions.insert(code, Arc::new(function));
+        Ok(())
+    }
+
+    /// Returns whether no host function is registered.
+    pub fn is_empty(&self) -> bool {
+        self.functions.is_empty()
+    }
+
+    /// Call the host function registered for `code`.
+    pub(crate) fn call(&self, code: u32, args: &[u32], pc: u32) -> Result<u32> {
+        match self.functions.get(&code) {
+            Some(function) => Ok(function.call(args)),
+            None => Err(VMErrorKind::UnimplementedSyscall(code, pc))?,
+        }
+    }
+}
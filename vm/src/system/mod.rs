@@ -1,3 +1,5 @@
+mod host;
 mod syscall;
 
-pub use syscall::{SyscallCode, SyscallInstruction};
+pub use host::{HostCall, HostFunction, HostFunctions, HOST_CALL_CODES};
+pub use syscall::{SyscallCode, SyscallInstruction, LOG_RECORD_FD};
@@ -13,7 +13,7 @@
 //!
 //! 1. Decoding syscall instructions from CPU state.
 //! 2. Executing various syscalls, such as:
-//!    - Write: Output data to a file descriptor (currently only supports stdout).
+//!    - Write: Output data to a file descriptor (currently only supports stdout and structured logs).
 //!    - Exit: Terminate the program with a specified error code.
 //!    - CycleCount: Profile function execution time.
 //!    - ReadFromPrivateInput: Read data from a private input tape.
@@ -32,7 +32,7 @@ use nexus_common::cpu::Registers;
 
 use crate::{
     cpu::Cpu,
-    emulator::{memory_stats::MemoryStats, Executor, LinearMemoryLayout},
+    emulator::{memory_stats::MemoryStats, Executor, LinearMemoryLayout, LogLevel, LogRecord},
     error::{Result, VMErrorKind},
     memory::{LoadOp, MemoryProcessor, StoreOp},
     riscv::{BuiltinOpcode, Instruction, Register},
@@ -99,6 +99,24 @@ impl From<SyscallCode> for u32 {
     }
 }
 
+impl SyscallCode {
+    /// Look up a syscall by its snake case name, e.g. `"read_from_private_input"`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let code = match name {
+            "write" => SyscallCode::Write,
+            "exit" => SyscallCode::Exit,
+            "read_from_private_input" => SyscallCode::ReadFromPrivateInput,
+            "cycle_count" => SyscallCode::CycleCount,
+            "overwrite_stack_pointer" => SyscallCode::OverwriteStackPointer,
+            "overwrite_heap_pointer" => SyscallCode::OverwriteHeapPointer,
+            "read_from_auxiliary_input" => SyscallCode::ReadFromAuxiliaryInput,
+            "memory_advise" => SyscallCode::MemoryAdvise,
+            _ => return None,
+        };
+        Some(code)
+    }
+}
+
 /// Represents a system call instruction in the RISC-V emulator.
 ///
 /// This struct encapsulates all the necessary information for executing a system call,
@@ -123,6 +141,9 @@ pub struct SyscallInstruction {
     args: Vec<u32>,
 }
 
+/// The file descriptor of the structured log, written to with the log level in `a3`.
+pub const LOG_RECORD_FD: u32 = 3;
+
 impl SyscallInstruction {
     pub fn decode(ins: &Instruction, cpu: &Cpu) -> Result<Self> {
         if !matches!(ins.opcode.builtin(), Some(BuiltinOpcode::ECALL)) {
@@ -176,6 +197,40 @@ impl SyscallInstruction {
         Ok(())
     }
 
+    /// Executes the write syscall to the structured log, recording a message at the level given in `a3`.
+    fn execute_log_record(
+        &mut self,
+        executor: &mut Executor,
+        memory: &impl MemoryProcessor,
+        buf_addr: u32,
+        count: u32,
+        level: u32,
+    ) -> Result<()> {
+        let Ok(level) = LogLevel::try_from(level) else {
+            // Return -1
+            self.result = Some((Register::X10, u32::MAX));
+            return Ok(());
+        };
+
+        let buffer = memory.read_bytes(buf_addr, count as _)?;
+        let message = String::from_utf8_lossy(&buffer).into_owned();
+        let line = format!("[{level}] {message}\n");
+
+        if let Some(logger) = &mut executor.logs {
+            logger.push(line.into_bytes());
+            executor.log_records.push(LogRecord {
+                level,
+                cycle: executor.global_clock,
+                message,
+            });
+        } else {
+            print!("{line}");
+        }
+
+        self.result = Some((Register::X10, count));
+        Ok(())
+    }
+
     /// Executes the exit syscall to terminate the program.
     ///
     /// This function sets the exit code and signals the VM to terminate execution.
@@ -328,6 +383,18 @@ impl SyscallInstruction {
                 let fd = self.args[0];
                 let buf = self.args[1];
                 let count = self.args[2];
+                if fd == LOG_RECORD_FD {
+                    return self.execute_log_record(executor, memory, buf, count, self.args[3]);
+                }
+                if executor.report_arithmetic_overflow && fd == 1 {
+                    let message = memory.read_bytes(buf, count as _)?;
+                    let message = String::from_utf8_lossy(&message);
+                    // The panic handler writes the message of an overflow check, e.g. "attempt to add
+                    // with overflow", as its own fragment.
+                    if message.starts_with("attempt to ") && message.ends_with("with overflow") {
+                        executor.overflow_panic = Some(message.into_owned());
+                    }
+                }
                 self.execute_write(&mut executor.logs, memory, fd, buf, count)
             }
 
@@ -352,10 +419,24 @@ impl SyscallInstruction {
                     return Err(VMErrorKind::VMExited(error_code))?;
                 }
                 let error_code = self.args[0];
+                // The runtime exits with code 1 after a panic.
+                if error_code == 1 {
+                    if let Some(message) = executor.overflow_panic.take() {
+                        return Err(VMErrorKind::ArithmeticOverflow {
+                            pc: executor.cpu.pc.value,
+                            message,
+                        })?;
+                    }
+                }
                 self.execute_exit(error_code)
             }
 
             SyscallCode::ReadFromPrivateInput => {
+                if executor.private_input_tape.is_empty() {
+                    if let Some(input) = &mut executor.lazy_private_input {
+                        input.refill(&mut executor.private_input_tape)?;
+                    }
+                }
                 self.execute_read_from_private_input(&mut executor.private_input_tape)
             }
 
@@ -368,6 +449,9 @@ impl SyscallInstruction {
             SyscallCode::ReadFromAuxiliaryInput => unreachable!(), // unreachable since parsing of the code will fail
 
             SyscallCode::MemoryAdvise => {
+                // Allocations are accounted for on both passes, so that either view reports them.
+                executor.alloc_stats.record_allocation(self.args[1]);
+
                 // No-op on second pass.
                 if second_pass {
                     self.result = None;
@@ -377,6 +461,17 @@ impl SyscallInstruction {
                 let addr = self.args[0];
                 let len = self.args[1];
 
+                // Catch the heap growing into the stack before the allocation can corrupt it.
+                let sp = executor.cpu.registers[Register::X2];
+                let heap_end = addr.saturating_add(len);
+                if sp > 0 && heap_end > sp {
+                    return Err(VMErrorKind::HeapStackCollision {
+                        heap_end,
+                        sp,
+                        pc: executor.cpu.pc.value,
+                    })?;
+                }
+
                 self.execute_allocate_heap(addr, len, memory_stats)
             }
         }
@@ -3,10 +3,14 @@ use serde::{Deserialize, Serialize};
 use crate::{
     cpu::{instructions::InstructionResult, RegisterFile},
     elf::ElfFile,
-    emulator::{Emulator, HarvardEmulator, InternalView, LinearEmulator, LinearMemoryLayout, View},
+    emulator::{
+        Emulator, ExecutionLimits, HarvardEmulator, InputProvider, InputTranscript, InternalView,
+        LinearEmulator, LinearMemoryLayout, View,
+    },
     error::{Result, VMError, VMErrorKind},
     memory::MemoryRecords,
     riscv::{BasicBlock, Instruction},
+    system::HostFunctions,
     WORD_SIZE,
 };
 
@@ -289,6 +293,24 @@ fn k_step(
     (Some(block), Ok(()))
 }
 
+/// Collect the inputs consumed by a completed first-pass execution.
+fn input_transcript(
+    harvard: &HarvardEmulator,
+    public_input: &[u8],
+    private_input: &[u8],
+    ad: &[u8],
+) -> InputTranscript {
+    let executor = harvard.get_executor();
+    let unread = executor.private_input_tape.len();
+    let private_input = [private_input, executor.private_input_provided()].concat();
+
+    InputTranscript {
+        public_input: public_input.to_vec(),
+        private_input: private_input[..private_input.len() - unread].to_vec(),
+        associated_data: ad.to_vec(),
+    }
+}
+
 /// Trace a program over an ELF for a given `k`.
 ///
 /// This function generates a trace of the program execution using the provided ELF file.
@@ -305,18 +327,84 @@ pub fn k_trace(
     public_input: &[u8],
     private_input: &[u8],
     k: usize,
+) -> Result<(View, UniformTrace)> {
+    k_trace_with_limits(
+        elf,
+        ad,
+        public_input,
+        private_input,
+        k,
+        &ExecutionLimits::default(),
+    )
+}
+
+/// Trace a program over an ELF for a given `k`, enforcing the given resource limits.
+///
+/// See [`k_trace`] for details on the generated trace.
+pub fn k_trace_with_limits(
+    elf: ElfFile,
+    ad: &[u8],
+    public_input: &[u8],
+    private_input: &[u8],
+    k: usize,
+    limits: &ExecutionLimits,
+) -> Result<(View, UniformTrace)> {
+    k_trace_with_hooks(
+        elf,
+        ad,
+        public_input,
+        private_input,
+        ExecutionHooks::default(),
+        k,
+        limits,
+    )
+}
+
+/// Host-side extensions of an execution, see [`k_trace_with_hooks`].
+#[derive(Default)]
+pub struct ExecutionHooks {
+    /// The provider extending the private input tape on demand, if any.
+    pub input_provider: Option<Box<dyn InputProvider>>,
+    /// The host functions answering calls made by the guest.
+    pub host_functions: HostFunctions,
+}
+
+/// Trace a program over an ELF for a given `k`, with the host-side extensions given by `hooks`.
+///
+/// Any bytes provided by the input provider follow `private_input` on the tape. Only the prefix of the tape
+/// consumed by the first pass is replayed by the second pass, and recorded in the input transcript of the view.
+/// Likewise, host functions are only called by the first pass, and their recorded results are replayed by the
+/// second.
+///
+/// See [`k_trace`] for details on the generated trace.
+pub fn k_trace_with_hooks(
+    elf: ElfFile,
+    ad: &[u8],
+    public_input: &[u8],
+    private_input: &[u8],
+    hooks: ExecutionHooks,
+    k: usize,
+    limits: &ExecutionLimits,
 ) -> Result<(View, UniformTrace)> {
     assert!(k > 0);
     let mut harvard = HarvardEmulator::from_elf(&elf, public_input, private_input);
     harvard.get_executor_mut().capture_logs(true);
+    harvard.set_limits(limits.clone());
+    if let Some(provider) = hooks.input_provider {
+        harvard.set_input_provider(provider);
+    }
+    harvard.get_executor_mut().host_functions = hooks.host_functions;
 
-    match harvard.execute(false) {
+    match harvard.run(false) {
         Err(VMError {
             source: VMErrorKind::VMExited(_),
             ..
         }) => {
+            let transcript = input_transcript(&harvard, public_input, private_input, ad);
+
             // todo: consistency check i/o between harvard and linear?
-            let mut linear = LinearEmulator::from_harvard(&harvard, elf, ad, private_input)?;
+            let mut linear =
+                LinearEmulator::from_harvard(&harvard, elf, ad, &transcript.private_input)?;
 
             let mut trace = UniformTrace {
                 memory_layout: linear.memory_layout,
@@ -337,6 +425,9 @@ pub fn k_trace(
                             VMErrorKind::VMExited(_) => {
                                 let mut view = linear.finalize();
                                 view.add_logs(&harvard);
+                                view.set_limit_usage(harvard.limit_usage());
+                                view.set_memory_stats(harvard.memory_usage());
+                                view.set_input_transcript(transcript);
                                 return Ok((view, trace));
                             }
                             _ => return Err(e),
@@ -352,6 +443,38 @@ pub fn k_trace(
     }
 }
 
+/// Run a program over an ELF on the first-pass emulator only, with the host-side extensions given by `hooks`, and
+/// return a view of the execution without tracing it.
+///
+/// If `execute_zbb` is set, Zbb bit-manipulation instructions are executed rather than rejected. No chip constrains
+/// them, so such executions can only be run this way: the second pass of [`k_trace_with_hooks`] rejects them.
+pub fn emulate_with_hooks(
+    elf: &ElfFile,
+    public_input: &[u8],
+    private_input: &[u8],
+    hooks: ExecutionHooks,
+    limits: &ExecutionLimits,
+    execute_zbb: bool,
+) -> Result<View> {
+    let mut harvard = HarvardEmulator::from_elf(elf, public_input, private_input);
+    harvard.get_executor_mut().capture_logs(true);
+    harvard.get_executor_mut().execute_zbb = execute_zbb;
+    harvard.set_limits(limits.clone());
+    if let Some(provider) = hooks.input_provider {
+        harvard.set_input_provider(provider);
+    }
+    harvard.get_executor_mut().host_functions = hooks.host_functions;
+
+    match harvard.run(false) {
+        Err(VMError {
+            source: VMErrorKind::VMExited(_),
+            ..
+        }) => Ok(harvard.finalize()),
+        Err(e) => Err(e),
+        Ok(_) => unreachable!(),
+    }
+}
+
 /// Similar to `k_trace`, but uses HarvardEmulator and supports Intermediate Representation (IR) as input instead of an ELF file.
 pub fn k_trace_direct(basic_blocks: &Vec<BasicBlock>, k: usize) -> Result<(View, UniformTrace)> {
     let mut harvard = HarvardEmulator::from_basic_blocks(basic_blocks);
@@ -438,7 +561,7 @@ pub fn bb_trace(
     let mut harvard = HarvardEmulator::from_elf(&elf, public_input, private_input);
     harvard.get_executor_mut().capture_logs(true);
 
-    match harvard.execute(false) {
+    match harvard.run(false) {
         Err(VMError {
             source: VMErrorKind::VMExited(_),
             ..
@@ -464,6 +587,14 @@ pub fn bb_trace(
                             VMErrorKind::VMExited(_) => {
                                 let mut view = linear.finalize();
                                 view.add_logs(&harvard);
+                                view.set_limit_usage(harvard.limit_usage());
+                                view.set_memory_stats(harvard.memory_usage());
+                                view.set_input_transcript(input_transcript(
+                                    &harvard,
+                                    public_input,
+                                    private_input,
+                                    ad,
+                                ));
                                 return Ok((view, trace));
                             }
                             _ => return Err(e),